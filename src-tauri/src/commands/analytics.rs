@@ -0,0 +1,182 @@
+//! Analytics Commands
+//!
+//! Tauri commands for delivery analytics such as cycle time reports.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::commands::search::CommandError;
+use crate::services::{CycleTimeReport, TeamFlowMetrics, TicketFlowMetrics};
+
+/// Cycle time request parameters: tickets completed in `[from, to)`
+#[derive(Debug, Clone, Deserialize)]
+pub struct CycleTimeParams {
+    pub project: Option<String>,
+    /// RFC 3339 timestamps
+    pub from: String,
+    pub to: String,
+}
+
+impl CycleTimeParams {
+    /// Parse and check the period
+    pub fn period(&self) -> Result<(DateTime<Utc>, DateTime<Utc>), CommandError> {
+        let parse = |value: &str| {
+            DateTime::parse_from_rfc3339(value)
+                .map(|d| d.with_timezone(&Utc))
+                .map_err(|_| CommandError::validation(&format!("Invalid date: {}", value)))
+        };
+        let (from, to) = (parse(&self.from)?, parse(&self.to)?);
+
+        if from >= to {
+            return Err(CommandError::validation("Period start must be before its end"));
+        }
+        Ok((from, to))
+    }
+}
+
+/// Cycle time report response
+#[derive(Debug, Clone, Serialize)]
+pub struct CycleTimeReportDto {
+    pub tickets: Vec<TicketFlowDto>,
+    pub teams: Vec<TeamFlowDto>,
+    pub overall: TeamFlowDto,
+    #[serde(rename = "generatedAt")]
+    pub generated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TicketFlowDto {
+    pub key: String,
+    pub summary: String,
+    pub team: Option<String>,
+    pub assignee: Option<String>,
+    #[serde(rename = "completedAt")]
+    pub completed_at: Option<String>,
+    #[serde(rename = "leadTimeHours")]
+    pub lead_time_hours: Option<f64>,
+    #[serde(rename = "cycleTimeHours")]
+    pub cycle_time_hours: Option<f64>,
+    #[serde(rename = "timeInStatusHours")]
+    pub time_in_status_hours: HashMap<String, f64>,
+    #[serde(rename = "reopenCount")]
+    pub reopen_count: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TeamFlowDto {
+    pub team: String,
+    #[serde(rename = "ticketCount")]
+    pub ticket_count: usize,
+    #[serde(rename = "completedCount")]
+    pub completed_count: usize,
+    #[serde(rename = "avgLeadTimeHours")]
+    pub avg_lead_time_hours: Option<f64>,
+    #[serde(rename = "avgCycleTimeHours")]
+    pub avg_cycle_time_hours: Option<f64>,
+    #[serde(rename = "medianCycleTimeHours")]
+    pub median_cycle_time_hours: Option<f64>,
+    #[serde(rename = "p85CycleTimeHours")]
+    pub p85_cycle_time_hours: Option<f64>,
+    #[serde(rename = "timeInStatusHours")]
+    pub time_in_status_hours: HashMap<String, f64>,
+    #[serde(rename = "reopenCount")]
+    pub reopen_count: u32,
+    #[serde(rename = "reopenRatePercent")]
+    pub reopen_rate_percent: f64,
+}
+
+impl From<TicketFlowMetrics> for TicketFlowDto {
+    fn from(metrics: TicketFlowMetrics) -> Self {
+        Self {
+            key: metrics.key,
+            summary: metrics.summary,
+            team: metrics.team,
+            assignee: metrics.assignee,
+            completed_at: metrics.completed_at.map(|d| d.to_rfc3339()),
+            lead_time_hours: metrics.lead_time_hours,
+            cycle_time_hours: metrics.cycle_time_hours,
+            time_in_status_hours: metrics.time_in_status_hours,
+            reopen_count: metrics.reopen_count,
+        }
+    }
+}
+
+impl From<TeamFlowMetrics> for TeamFlowDto {
+    fn from(metrics: TeamFlowMetrics) -> Self {
+        Self {
+            team: metrics.team,
+            ticket_count: metrics.ticket_count,
+            completed_count: metrics.completed_count,
+            avg_lead_time_hours: metrics.avg_lead_time_hours,
+            avg_cycle_time_hours: metrics.avg_cycle_time_hours,
+            median_cycle_time_hours: metrics.median_cycle_time_hours,
+            p85_cycle_time_hours: metrics.p85_cycle_time_hours,
+            time_in_status_hours: metrics.time_in_status_hours,
+            reopen_count: metrics.reopen_count,
+            reopen_rate_percent: metrics.reopen_rate_percent,
+        }
+    }
+}
+
+impl From<CycleTimeReport> for CycleTimeReportDto {
+    fn from(report: CycleTimeReport) -> Self {
+        Self {
+            tickets: report.tickets.into_iter().map(TicketFlowDto::from).collect(),
+            teams: report.teams.into_iter().map(TeamFlowDto::from).collect(),
+            overall: report.overall.into(),
+            generated_at: report.generated_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Get lead time, cycle time, time in status and reopens of tickets completed in a period
+#[tauri::command]
+pub async fn get_cycle_time_report(params: CycleTimeParams) -> Result<CycleTimeReportDto, CommandError> {
+    let (_from, _to) = params.period()?;
+
+    // TODO: Wire up to actual CycleTimeService::get_completed_report
+    Err(CommandError::internal("Jira is not configured"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cycle_time_params_period() {
+        let params: CycleTimeParams = serde_json::from_str(
+            r#"{"project": "PAY", "from": "2024-03-01T00:00:00Z", "to": "2024-04-01T00:00:00+02:00"}"#,
+        )
+        .unwrap();
+        let (from, to) = params.period().unwrap();
+        assert_eq!(from.to_rfc3339(), "2024-03-01T00:00:00+00:00");
+        assert_eq!(to.to_rfc3339(), "2024-03-31T22:00:00+00:00");
+
+        let reversed = CycleTimeParams { from: params.to.clone(), to: params.from.clone(), project: None };
+        assert_eq!(reversed.period().unwrap_err().code, "VALIDATION_ERROR");
+
+        let invalid = CycleTimeParams { from: "March".to_string(), ..params };
+        assert_eq!(invalid.period().unwrap_err().code, "VALIDATION_ERROR");
+    }
+
+    #[test]
+    fn test_team_flow_serialization() {
+        let team = TeamFlowMetrics {
+            team: "Payments".to_string(),
+            ticket_count: 3,
+            completed_count: 3,
+            avg_lead_time_hours: Some(72.0),
+            avg_cycle_time_hours: Some(48.0),
+            median_cycle_time_hours: Some(48.0),
+            p85_cycle_time_hours: Some(72.0),
+            time_in_status_hours: HashMap::from([("In Review".to_string(), 12.0)]),
+            reopen_count: 1,
+            reopen_rate_percent: 33.3,
+        };
+
+        let json = serde_json::to_string(&TeamFlowDto::from(team)).unwrap();
+        assert!(json.contains("\"p85CycleTimeHours\":72.0"));
+        assert!(json.contains("\"timeInStatusHours\":{\"In Review\":12.0}"));
+    }
+}
@@ -7,9 +7,15 @@ pub mod search;
 pub mod prs;
 pub mod incidents;
 pub mod settings;
+pub mod tickets;
+pub mod sprints;
+pub mod analytics;
 
 // Re-export command handlers for registration
 pub use search::*;
 pub use prs::*;
 pub use incidents::*;
 pub use settings::*;
+pub use tickets::*;
+pub use sprints::*;
+pub use analytics::*;
@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::commands::search::CommandError;
+use crate::integrations::git::qualified_repository;
 
 /// PR list request parameters
 #[derive(Debug, Clone, Deserialize)]
@@ -31,12 +32,29 @@ pub struct PrSummaryResponse {
     pub total_open: usize,
     #[serde(rename = "pendingReview")]
     pub pending_review: usize,
+    #[serde(rename = "changesRequested")]
+    pub changes_requested: usize,
     #[serde(rename = "staleCount")]
     pub stale_count: usize,
+    #[serde(rename = "failingChecks")]
+    pub failing_checks: usize,
+    /// PRs of size L or XL
+    #[serde(rename = "largeCount")]
+    pub large_count: usize,
     #[serde(rename = "byRepository")]
     pub by_repository: HashMap<String, usize>,
     #[serde(rename = "trayState")]
     pub tray_state: String,
+    #[serde(rename = "failedRepositories")]
+    pub failed_repositories: Vec<RepositoryErrorDto>,
+}
+
+/// Repository that could not be fetched
+#[derive(Debug, Clone, Serialize)]
+pub struct RepositoryErrorDto {
+    pub repository: String,
+    pub kind: String,
+    pub message: String,
 }
 
 /// PR item for list response
@@ -62,6 +80,16 @@ pub struct PrItemDto {
     pub url: String,
     #[serde(rename = "ageHours")]
     pub age_hours: i64,
+    /// Git connection the PR came from
+    pub connection: Option<String>,
+    pub labels: Vec<String>,
+    /// Diff size, absent when the provider did not report it
+    pub additions: Option<u64>,
+    pub deletions: Option<u64>,
+    #[serde(rename = "changedFiles")]
+    pub changed_files: Option<u64>,
+    /// Size bucket from XS to XL
+    pub size: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -74,6 +102,7 @@ pub struct UserDto {
 #[derive(Debug, Clone, Serialize)]
 pub struct ReviewerDto {
     pub user: UserDto,
+    pub state: String,
     pub approved: bool,
 }
 
@@ -93,6 +122,44 @@ pub struct PrGroupDto {
     pub stale_count: usize,
 }
 
+/// A review comment of a PR
+#[derive(Debug, Clone, Serialize)]
+pub struct ReviewCommentDto {
+    pub id: String,
+    #[serde(rename = "threadId")]
+    pub thread_id: String,
+    pub author: UserDto,
+    pub body: String,
+    /// File and line the comment is anchored to, absent on the conversation
+    pub path: Option<String>,
+    pub line: Option<u32>,
+    pub resolved: Option<bool>,
+    #[serde(rename = "createdAt")]
+    pub created_at: String,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: String,
+}
+
+/// Latest review comments of a PR with thread statistics
+#[derive(Debug, Clone, Serialize)]
+pub struct ReviewCommentsResponse {
+    pub comments: Vec<ReviewCommentDto>,
+    #[serde(rename = "totalCount")]
+    pub total_count: usize,
+    #[serde(rename = "unresolvedThreads")]
+    pub unresolved_threads: usize,
+    #[serde(rename = "lastActivityBy")]
+    pub last_activity_by: Option<UserDto>,
+    #[serde(rename = "lastActivityAt")]
+    pub last_activity_at: Option<String>,
+    /// Whose turn it is: "author" or "reviewers"
+    pub court: Option<String>,
+}
+
+fn default_comment_limit() -> usize {
+    5
+}
+
 /// Get PR summary
 #[tauri::command]
 pub async fn get_pr_summary() -> Result<PrSummaryResponse, CommandError> {
@@ -100,9 +167,13 @@ pub async fn get_pr_summary() -> Result<PrSummaryResponse, CommandError> {
     Ok(PrSummaryResponse {
         total_open: 0,
         pending_review: 0,
+        changes_requested: 0,
         stale_count: 0,
+        failing_checks: 0,
+        large_count: 0,
         by_repository: HashMap::new(),
         tray_state: "neutral".to_string(),
+        failed_repositories: vec![],
     })
 }
 
@@ -145,6 +216,43 @@ pub async fn get_prs_grouped_by_age() -> Result<GroupedPrsResponse, CommandError
     })
 }
 
+/// Get PRs grouped by size, largest first
+#[tauri::command]
+pub async fn get_prs_grouped_by_size() -> Result<GroupedPrsResponse, CommandError> {
+    // TODO: Wire up to PrAggregator::group_prs with PrGrouping::BySize
+    Ok(GroupedPrsResponse {
+        groups: vec![],
+        total_count: 0,
+    })
+}
+
+/// Get the latest review comments of a PR, from `connection` when given
+#[tauri::command]
+pub async fn get_pr_review_comments(
+    repository: String,
+    id: String,
+    connection: Option<String>,
+    limit: Option<usize>,
+) -> Result<ReviewCommentsResponse, CommandError> {
+    if repository.is_empty() || id.is_empty() {
+        return Err(CommandError::validation("Repository and PR ID are required"));
+    }
+    let _repository = match connection {
+        Some(connection) => qualified_repository(&connection, &repository),
+        None => repository,
+    };
+    let _limit = limit.unwrap_or_else(default_comment_limit);
+    // TODO: Wire up to PullRequestRepository::get_review_comments
+    Ok(ReviewCommentsResponse {
+        comments: vec![],
+        total_count: 0,
+        unresolved_threads: 0,
+        last_activity_by: None,
+        last_activity_at: None,
+        court: None,
+    })
+}
+
 /// Refresh PR data (bypass cache)
 #[tauri::command]
 pub async fn refresh_prs() -> Result<PrSummaryResponse, CommandError> {
@@ -170,14 +278,26 @@ mod tests {
         let summary = PrSummaryResponse {
             total_open: 5,
             pending_review: 2,
+            changes_requested: 1,
             stale_count: 1,
+            failing_checks: 1,
+            large_count: 2,
             by_repository: HashMap::from([("repo1".to_string(), 3)]),
             tray_state: "amber".to_string(),
+            failed_repositories: vec![RepositoryErrorDto {
+                repository: "repo2".to_string(),
+                kind: "Auth".to_string(),
+                message: "Authentication failed: Access denied to repo2".to_string(),
+            }],
         };
 
         let json = serde_json::to_string(&summary).unwrap();
         assert!(json.contains("\"totalOpen\":5"));
         assert!(json.contains("\"staleCount\":1"));
+        assert!(json.contains("\"changesRequested\":1"));
+        assert!(json.contains("\"failingChecks\":1"));
+        assert!(json.contains("\"largeCount\":2"));
+        assert!(json.contains("\"failedRepositories\":[{\"repository\":\"repo2\""));
     }
 
     #[test]
@@ -193,7 +313,15 @@ mod tests {
                 name: "John".to_string(),
                 avatar: None,
             },
-            reviewers: vec![],
+            reviewers: vec![ReviewerDto {
+                user: UserDto {
+                    id: "u2".to_string(),
+                    name: "Jane".to_string(),
+                    avatar: None,
+                },
+                state: "ChangesRequested".to_string(),
+                approved: false,
+            }],
             source_branch: "feature".to_string(),
             target_branch: "main".to_string(),
             checks_status: "pass".to_string(),
@@ -201,11 +329,21 @@ mod tests {
             updated_at: "2024-01-01T00:00:00Z".to_string(),
             url: "https://example.com".to_string(),
             age_hours: 24,
+            connection: Some("github".to_string()),
+            labels: vec!["backend".to_string()],
+            additions: Some(640),
+            deletions: Some(12),
+            changed_files: Some(9),
+            size: Some("L".to_string()),
         };
 
         let json = serde_json::to_string(&pr).unwrap();
         assert!(json.contains("\"sourceBranch\":\"feature\""));
         assert!(json.contains("\"isStale\":false"));
+        assert!(json.contains("\"connection\":\"github\""));
+        assert!(json.contains("\"changedFiles\":9"));
+        assert!(json.contains("\"size\":\"L\""));
+        assert!(json.contains("\"state\":\"ChangesRequested\""));
     }
 
     #[tokio::test]
@@ -225,4 +363,45 @@ mod tests {
         let result = get_prs(params).await;
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_get_pr_review_comments() {
+        let result = get_pr_review_comments("acme/api".to_string(), "42".to_string(), Some("work".to_string()), None).await;
+        assert_eq!(result.unwrap().unresolved_threads, 0);
+
+        let result = get_pr_review_comments("acme/api".to_string(), String::new(), None, Some(3)).await;
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_review_comments_serialization() {
+        let response = ReviewCommentsResponse {
+            comments: vec![ReviewCommentDto {
+                id: "1".to_string(),
+                thread_id: "T1".to_string(),
+                author: UserDto {
+                    id: "u1".to_string(),
+                    name: "bob".to_string(),
+                    avatar: None,
+                },
+                body: "Why?".to_string(),
+                path: Some("src/lib.rs".to_string()),
+                line: Some(12),
+                resolved: Some(false),
+                created_at: "2024-03-01T09:00:00Z".to_string(),
+                updated_at: "2024-03-01T09:00:00Z".to_string(),
+            }],
+            total_count: 1,
+            unresolved_threads: 1,
+            last_activity_by: None,
+            last_activity_at: Some("2024-03-01T09:00:00Z".to_string()),
+            court: Some("author".to_string()),
+        };
+
+        let json = serde_json::to_string(&response).unwrap();
+        assert!(json.contains("\"threadId\":\"T1\""));
+        assert!(json.contains("\"unresolvedThreads\":1"));
+        assert!(json.contains("\"lastActivityBy\":null"));
+        assert!(json.contains("\"court\":\"author\""));
+    }
 }
@@ -5,6 +5,7 @@
 use serde::{Deserialize, Serialize};
 use tauri::State;
 
+use crate::integrations::traits::IntegrationError;
 use crate::services::{SearchResult, SearchResultType};
 
 /// Search query from frontend
@@ -123,6 +124,25 @@ impl CommandError {
     pub fn auth(message: &str) -> Self {
         Self::new("AUTH_ERROR", message)
     }
+
+    /// The integration a command needs has not been set up
+    pub fn not_configured(message: &str) -> Self {
+        Self::new("NOT_CONFIGURED", message)
+    }
+}
+
+impl From<IntegrationError> for CommandError {
+    fn from(error: IntegrationError) -> Self {
+        let message = error.to_string();
+        match error {
+            IntegrationError::Auth(_) => Self::auth(&message),
+            IntegrationError::NotFound(_) => Self::not_found(&message),
+            IntegrationError::PermissionDenied(_) => Self::new("PERMISSION_DENIED", &message),
+            IntegrationError::InvalidTransition(_) => Self::new("INVALID_TRANSITION", &message),
+            IntegrationError::RateLimit { .. } => Self::new("RATE_LIMITED", &message),
+            _ => Self::internal(&message),
+        }
+    }
 }
 
 /// Perform unified search
@@ -234,6 +254,19 @@ mod tests {
         assert_eq!(err.message, "Invalid input");
     }
 
+    #[test]
+    fn test_command_error_from_integration_error() {
+        let err = CommandError::from(IntegrationError::PermissionDenied("PROJ-1".to_string()));
+        assert_eq!(err.code, "PERMISSION_DENIED");
+        assert_eq!(err.message, "Permission denied: PROJ-1");
+
+        let err = CommandError::from(IntegrationError::InvalidTransition("Done".to_string()));
+        assert_eq!(err.code, "INVALID_TRANSITION");
+
+        let err = CommandError::from(IntegrationError::ParseError("bad".to_string()));
+        assert_eq!(err.code, "INTERNAL_ERROR");
+    }
+
     #[test]
     fn test_search_result_dto_from() {
         use crate::services::SearchResultMetadata;
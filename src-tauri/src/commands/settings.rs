@@ -5,12 +5,15 @@
 use serde::{Deserialize, Serialize};
 
 use crate::commands::search::CommandError;
+use crate::core::DEFAULT_GIT_CONNECTION;
 
 /// Integration configuration for frontend
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IntegrationConfigDto {
     pub jira: Option<JiraConfigDto>,
-    pub git: Option<GitConfigDto>,
+    /// Git connections, one per hosting account
+    #[serde(default)]
+    pub git: Vec<GitConfigDto>,
     pub gemini: Option<GeminiConfigDto>,
     pub grafana: Option<GrafanaConfigDto>,
 }
@@ -22,12 +25,34 @@ pub struct JiraConfigDto {
     pub username: String,
     #[serde(rename = "defaultProject")]
     pub default_project: Option<String>,
+    /// "cloud" or "datacenter"
+    #[serde(default = "default_jira_deployment")]
+    pub deployment: String,
     #[serde(rename = "hasToken")]
     pub has_token: bool,
+    /// Custom field ids overriding the discovered ones
+    #[serde(rename = "fieldMapping", default)]
+    pub field_mapping: JiraFieldMappingDto,
+}
+
+fn default_jira_deployment() -> String {
+    "cloud".to_string()
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JiraFieldMappingDto {
+    pub sprint: Option<String>,
+    #[serde(rename = "storyPoints")]
+    pub story_points: Option<String>,
+    pub epic: Option<String>,
+    pub team: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GitConfigDto {
+    /// Connection id, unique among git connections
+    #[serde(default = "default_git_connection_id")]
+    pub id: String,
     pub provider: String,
     #[serde(rename = "baseUrl")]
     pub base_url: Option<String>,
@@ -36,6 +61,52 @@ pub struct GitConfigDto {
     pub repositories: Vec<String>,
     #[serde(rename = "hasToken")]
     pub has_token: bool,
+    /// Discover repositories in addition to the listed ones
+    #[serde(default)]
+    pub discovery: Option<RepoDiscoveryDto>,
+    /// "rest" or "graphql", GitHub only
+    #[serde(rename = "githubApi", default = "default_github_api")]
+    pub github_api: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepoDiscoveryDto {
+    /// GitHub org/user, Bitbucket workspace or GitLab group; defaults to the workspace
+    pub owner: Option<String>,
+    /// Bitbucket project key
+    pub project: Option<String>,
+    #[serde(default)]
+    pub include: Vec<String>,
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    #[serde(rename = "includeArchived", default)]
+    pub include_archived: bool,
+    #[serde(rename = "refreshIntervalMinutes", default = "default_discovery_refresh_minutes")]
+    pub refresh_interval_minutes: u32,
+}
+
+fn default_discovery_refresh_minutes() -> u32 {
+    60
+}
+
+fn default_github_api() -> String {
+    "rest".to_string()
+}
+
+fn default_git_connection_id() -> String {
+    DEFAULT_GIT_CONNECTION.to_string()
+}
+
+/// Validate a git connection id, which is also used to name its credential slot
+fn validate_connection_id(id: &str) -> Result<(), CommandError> {
+    let valid = !id.is_empty()
+        && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(CommandError::validation(
+            "Git connection id must only contain letters, digits, '-' and '_'",
+        ));
+    }
+    Ok(())
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -91,6 +162,28 @@ pub struct SaveCredentialRequest {
     #[serde(rename = "credentialType")]
     pub credential_type: String,
     pub value: String,
+    /// Git connection the token belongs to, for `git_token`
+    #[serde(rename = "connectionId", default)]
+    pub connection_id: Option<String>,
+}
+
+/// Check the credential type, and that only git tokens name a connection
+fn validate_credential_type(credential_type: &str, connection_id: Option<&str>) -> Result<(), CommandError> {
+    let valid_types = ["jira_token", "git_token", "gemini_api_key", "grafana_api_key"];
+    if !valid_types.contains(&credential_type) {
+        return Err(CommandError::validation(&format!(
+            "Invalid credential type: {}",
+            credential_type
+        )));
+    }
+
+    match connection_id {
+        Some(_) if credential_type != "git_token" => Err(CommandError::validation(
+            "Only git tokens belong to a connection",
+        )),
+        Some(id) => validate_connection_id(id),
+        None => Ok(()),
+    }
 }
 
 /// Get all settings
@@ -99,7 +192,7 @@ pub async fn get_settings() -> Result<SettingsResponse, CommandError> {
     Ok(SettingsResponse {
         integrations: IntegrationConfigDto {
             jira: None,
-            git: None,
+            git: Vec::new(),
             gemini: None,
             grafana: None,
         },
@@ -126,6 +219,9 @@ pub async fn save_jira_config(config: JiraConfigDto) -> Result<(), CommandError>
     if config.username.is_empty() {
         return Err(CommandError::validation("Jira username is required"));
     }
+    if !["cloud", "datacenter"].contains(&config.deployment.as_str()) {
+        return Err(CommandError::validation("Jira deployment must be cloud or datacenter"));
+    }
     // TODO: Wire up to actual config storage
     log::info!("Saving Jira config for: {}", config.base_url);
     Ok(())
@@ -134,11 +230,38 @@ pub async fn save_jira_config(config: JiraConfigDto) -> Result<(), CommandError>
 /// Save Git configuration
 #[tauri::command]
 pub async fn save_git_config(config: GitConfigDto) -> Result<(), CommandError> {
+    validate_connection_id(&config.id)?;
     if config.username.is_empty() {
         return Err(CommandError::validation("Git username is required"));
     }
+    match config.github_api.as_str() {
+        "rest" => {}
+        "graphql" if config.provider == "github" => {}
+        "graphql" => return Err(CommandError::validation("The GraphQL API is only available for GitHub")),
+        _ => return Err(CommandError::validation("GitHub API must be rest or graphql")),
+    }
+    if let Some(ref discovery) = config.discovery {
+        if discovery.owner.is_none() && config.workspace.is_none() {
+            return Err(CommandError::validation("Repository discovery needs an owner or workspace"));
+        }
+        if discovery.include.iter().chain(&discovery.exclude).any(|p| p.trim().is_empty()) {
+            return Err(CommandError::validation("Repository patterns cannot be empty"));
+        }
+        if discovery.refresh_interval_minutes < 5 {
+            return Err(CommandError::validation("Discovery refresh interval must be at least 5 minutes"));
+        }
+    }
     // TODO: Wire up to actual config storage
-    log::info!("Saving Git config for provider: {}", config.provider);
+    log::info!("Saving Git connection {} for provider: {}", config.id, config.provider);
+    Ok(())
+}
+
+/// Remove a Git connection along with its token
+#[tauri::command]
+pub async fn delete_git_config(id: String) -> Result<(), CommandError> {
+    validate_connection_id(&id)?;
+    // TODO: Wire up to config storage and CredentialManager::delete_git_token
+    log::info!("Deleting Git connection: {}", id);
     Ok(())
 }
 
@@ -170,26 +293,20 @@ pub async fn save_credential(request: SaveCredentialRequest) -> Result<(), Comma
     if request.value.is_empty() {
         return Err(CommandError::validation("Credential value cannot be empty"));
     }
+    validate_credential_type(&request.credential_type, request.connection_id.as_deref())?;
 
-    let valid_types = ["jira_token", "git_token", "gemini_api_key", "grafana_api_key"];
-    if !valid_types.contains(&request.credential_type.as_str()) {
-        return Err(CommandError::validation(&format!(
-            "Invalid credential type: {}",
-            request.credential_type
-        )));
-    }
-
-    // TODO: Wire up to CredentialManager
+    // TODO: Wire up to CredentialManager (store_git_token for git connections)
     log::info!("Saving credential: {}", request.credential_type);
     Ok(())
 }
 
 /// Delete a credential
 #[tauri::command]
-pub async fn delete_credential(credential_type: String) -> Result<(), CommandError> {
+pub async fn delete_credential(credential_type: String, connection_id: Option<String>) -> Result<(), CommandError> {
     if credential_type.is_empty() {
         return Err(CommandError::validation("Credential type is required"));
     }
+    validate_credential_type(&credential_type, connection_id.as_deref())?;
     // TODO: Wire up to CredentialManager
     log::info!("Deleting credential: {}", credential_type);
     Ok(())
@@ -197,7 +314,8 @@ pub async fn delete_credential(credential_type: String) -> Result<(), CommandErr
 
 /// Check if a credential exists
 #[tauri::command]
-pub async fn has_credential(credential_type: String) -> Result<bool, CommandError> {
+pub async fn has_credential(credential_type: String, connection_id: Option<String>) -> Result<bool, CommandError> {
+    validate_credential_type(&credential_type, connection_id.as_deref())?;
     // TODO: Wire up to CredentialManager
     Ok(false)
 }
@@ -259,12 +377,18 @@ mod tests {
             base_url: "https://company.atlassian.net".to_string(),
             username: "user@example.com".to_string(),
             default_project: Some("PROJ".to_string()),
+            deployment: "cloud".to_string(),
             has_token: true,
+            field_mapping: JiraFieldMappingDto {
+                story_points: Some("customfield_10016".to_string()),
+                ..Default::default()
+            },
         };
 
         let json = serde_json::to_string(&config).unwrap();
         assert!(json.contains("\"baseUrl\":"));
         assert!(json.contains("\"hasToken\":true"));
+        assert!(json.contains("\"storyPoints\":\"customfield_10016\""));
     }
 
     #[test]
@@ -295,7 +419,9 @@ mod tests {
             base_url: "".to_string(),
             username: "user".to_string(),
             default_project: None,
+            deployment: "datacenter".to_string(),
             has_token: false,
+            field_mapping: JiraFieldMappingDto::default(),
         };
 
         let result = save_jira_config(config).await;
@@ -308,6 +434,7 @@ mod tests {
         let request = SaveCredentialRequest {
             credential_type: "jira_token".to_string(),
             value: "".to_string(),
+            connection_id: None,
         };
 
         let result = save_credential(request).await;
@@ -316,12 +443,82 @@ mod tests {
         let request = SaveCredentialRequest {
             credential_type: "invalid_type".to_string(),
             value: "token".to_string(),
+            connection_id: None,
         };
 
         let result = save_credential(request).await;
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_save_git_token_per_connection() {
+        let request: SaveCredentialRequest = serde_json::from_str(
+            r#"{"credentialType": "git_token", "value": "ghp_x", "connectionId": "github-work"}"#,
+        )
+        .unwrap();
+        assert!(save_credential(request).await.is_ok());
+
+        let request = SaveCredentialRequest {
+            credential_type: "jira_token".to_string(),
+            value: "token".to_string(),
+            connection_id: Some("github-work".to_string()),
+        };
+        assert_eq!(save_credential(request).await.unwrap_err().code, "VALIDATION_ERROR");
+
+        let result = delete_credential("git_token".to_string(), Some("work:github".to_string())).await;
+        assert_eq!(result.unwrap_err().code, "VALIDATION_ERROR");
+    }
+
+    #[tokio::test]
+    async fn test_save_git_config_defaults_connection_id() {
+        let config: GitConfigDto = serde_json::from_str(
+            r#"{"provider": "github", "baseUrl": null, "workspace": null, "username": "octocat",
+                "repositories": ["acme/api"], "hasToken": true}"#,
+        )
+        .unwrap();
+        assert_eq!(config.id, DEFAULT_GIT_CONNECTION);
+        assert!(save_git_config(config.clone()).await.is_ok());
+
+        let invalid = GitConfigDto { id: "".to_string(), ..config };
+        assert_eq!(save_git_config(invalid).await.unwrap_err().code, "VALIDATION_ERROR");
+    }
+
+    #[tokio::test]
+    async fn test_save_git_config_github_api() {
+        let config: GitConfigDto = serde_json::from_str(
+            r#"{"provider": "github", "baseUrl": null, "workspace": null, "username": "octocat",
+                "repositories": ["acme/api"], "hasToken": true, "githubApi": "graphql"}"#,
+        )
+        .unwrap();
+        assert!(save_git_config(config.clone()).await.is_ok());
+
+        let gitlab = GitConfigDto { provider: "gitlab".to_string(), ..config.clone() };
+        assert_eq!(save_git_config(gitlab).await.unwrap_err().code, "VALIDATION_ERROR");
+
+        let unknown = GitConfigDto { github_api: "soap".to_string(), ..config };
+        assert_eq!(save_git_config(unknown).await.unwrap_err().code, "VALIDATION_ERROR");
+    }
+
+    #[tokio::test]
+    async fn test_save_git_config_discovery_validation() {
+        let config: GitConfigDto = serde_json::from_str(
+            r#"{"id": "github", "provider": "github", "baseUrl": null, "workspace": null, "username": "octocat",
+                "repositories": [], "hasToken": true,
+                "discovery": {"owner": "acme", "project": null, "include": ["svc-*"], "exclude": ["*-sandbox"]}}"#,
+        )
+        .unwrap();
+        assert_eq!(config.discovery.as_ref().unwrap().refresh_interval_minutes, 60);
+        assert!(save_git_config(config.clone()).await.is_ok());
+
+        let mut ownerless = config.clone();
+        ownerless.discovery.as_mut().unwrap().owner = None;
+        assert_eq!(save_git_config(ownerless).await.unwrap_err().code, "VALIDATION_ERROR");
+
+        let mut too_frequent = config;
+        too_frequent.discovery.as_mut().unwrap().refresh_interval_minutes = 1;
+        assert_eq!(save_git_config(too_frequent).await.unwrap_err().code, "VALIDATION_ERROR");
+    }
+
     #[tokio::test]
     async fn test_save_appearance_validation() {
         let appearance = AppearanceConfigDto {
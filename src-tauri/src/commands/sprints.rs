@@ -0,0 +1,199 @@
+//! Sprint Commands
+//!
+//! Tauri commands for sprint health reports.
+
+use serde::Serialize;
+
+use crate::commands::search::CommandError;
+use crate::services::{SprintDataPoint, SprintHealthReport, SprintTicket};
+
+/// Sprint health response
+#[derive(Debug, Clone, Serialize)]
+pub struct SprintHealthDto {
+    #[serde(rename = "sprintId")]
+    pub sprint_id: u64,
+    #[serde(rename = "sprintName")]
+    pub sprint_name: String,
+    pub goal: Option<String>,
+    #[serde(rename = "startDate")]
+    pub start_date: Option<String>,
+    #[serde(rename = "endDate")]
+    pub end_date: Option<String>,
+    #[serde(rename = "daysLeft")]
+    pub days_left: Option<i64>,
+    pub headline: String,
+    /// "points" or "tickets"
+    pub unit: String,
+    #[serde(rename = "totalScope")]
+    pub total_scope: f64,
+    pub completed: f64,
+    #[serde(rename = "committedScope")]
+    pub committed_scope: f64,
+    #[serde(rename = "addedScope")]
+    pub added_scope: f64,
+    #[serde(rename = "scopeCreepPercent")]
+    pub scope_creep_percent: f64,
+    pub added: Vec<SprintTicketDto>,
+    #[serde(rename = "carriedOver")]
+    pub carried_over: Vec<SprintTicketDto>,
+    pub blocked: Vec<SprintTicketDto>,
+    #[serde(rename = "unassignedInProgress")]
+    pub unassigned_in_progress: Vec<SprintTicketDto>,
+    pub series: Vec<SprintDataPointDto>,
+    pub forecast: Option<SprintForecastDto>,
+    #[serde(rename = "generatedAt")]
+    pub generated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SprintTicketDto {
+    pub key: String,
+    pub summary: String,
+    pub status: String,
+    pub assignee: Option<String>,
+    #[serde(rename = "storyPoints")]
+    pub story_points: Option<f64>,
+}
+
+/// One day of the burndown / burnup chart
+#[derive(Debug, Clone, Serialize)]
+pub struct SprintDataPointDto {
+    pub date: String,
+    pub scope: f64,
+    pub completed: f64,
+    pub remaining: f64,
+    #[serde(rename = "idealRemaining")]
+    pub ideal_remaining: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SprintForecastDto {
+    #[serde(rename = "dailyRate")]
+    pub daily_rate: f64,
+    #[serde(rename = "projectedCompletion")]
+    pub projected_completion: String,
+    #[serde(rename = "onTrack")]
+    pub on_track: bool,
+}
+
+impl From<SprintTicket> for SprintTicketDto {
+    fn from(ticket: SprintTicket) -> Self {
+        Self {
+            key: ticket.key,
+            summary: ticket.summary,
+            status: ticket.status,
+            assignee: ticket.assignee,
+            story_points: ticket.story_points,
+        }
+    }
+}
+
+impl From<SprintDataPoint> for SprintDataPointDto {
+    fn from(point: SprintDataPoint) -> Self {
+        Self {
+            date: point.date.to_string(),
+            scope: point.scope,
+            completed: point.completed,
+            remaining: point.remaining,
+            ideal_remaining: point.ideal_remaining,
+        }
+    }
+}
+
+impl From<SprintHealthReport> for SprintHealthDto {
+    fn from(report: SprintHealthReport) -> Self {
+        let tickets = |tickets: Vec<SprintTicket>| tickets.into_iter().map(SprintTicketDto::from).collect();
+
+        Self {
+            headline: report.headline(),
+            sprint_id: report.sprint.id,
+            sprint_name: report.sprint.name,
+            goal: report.sprint.goal,
+            start_date: report.sprint.start_date.map(|d| d.to_rfc3339()),
+            end_date: report.sprint.end_date.map(|d| d.to_rfc3339()),
+            days_left: report.days_left,
+            unit: if report.uses_story_points { "points" } else { "tickets" }.to_string(),
+            total_scope: report.total_scope,
+            completed: report.completed,
+            committed_scope: report.committed_scope,
+            added_scope: report.added_scope,
+            scope_creep_percent: report.scope_creep_percent,
+            added: tickets(report.added),
+            carried_over: tickets(report.carried_over),
+            blocked: tickets(report.blocked),
+            unassigned_in_progress: tickets(report.unassigned_in_progress),
+            series: report.series.into_iter().map(SprintDataPointDto::from).collect(),
+            forecast: report.forecast.map(|f| SprintForecastDto {
+                daily_rate: f.daily_rate,
+                projected_completion: f.projected_completion.to_rfc3339(),
+                on_track: f.on_track,
+            }),
+            generated_at: report.generated_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Get the health report of a board's active sprint, `None` when no sprint is running
+#[tauri::command]
+pub async fn get_sprint_health(board_id: u64) -> Result<Option<SprintHealthDto>, CommandError> {
+    if board_id == 0 {
+        return Err(CommandError::validation("Board id is required"));
+    }
+
+    // TODO: Wire up to actual SprintHealthService
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::traits::{Sprint, SprintState};
+    use chrono::{NaiveDate, TimeZone, Utc};
+
+    #[test]
+    fn test_sprint_health_serialization() {
+        let report = SprintHealthReport {
+            sprint: Sprint {
+                id: 42,
+                name: "Sprint 42".to_string(),
+                state: SprintState::Active,
+                goal: Some("Ship refunds".to_string()),
+                start_date: Some(Utc.with_ymd_and_hms(2024, 3, 4, 9, 0, 0).unwrap()),
+                end_date: Some(Utc.with_ymd_and_hms(2024, 3, 15, 17, 0, 0).unwrap()),
+                complete_date: None,
+                board_id: Some(1),
+            },
+            days_left: Some(6),
+            uses_story_points: true,
+            total_scope: 30.0,
+            completed: 18.0,
+            committed_scope: 27.0,
+            added_scope: 3.0,
+            scope_creep_percent: 11.1,
+            added: vec![],
+            carried_over: vec![],
+            blocked: vec![],
+            unassigned_in_progress: vec![],
+            series: vec![SprintDataPoint {
+                date: NaiveDate::from_ymd_opt(2024, 3, 4).unwrap(),
+                scope: 27.0,
+                completed: 0.0,
+                remaining: 27.0,
+                ideal_remaining: Some(27.0),
+            }],
+            forecast: None,
+            generated_at: Utc::now(),
+        };
+
+        let json = serde_json::to_string(&SprintHealthDto::from(report)).unwrap();
+        assert!(json.contains("\"headline\":\"Sprint 42: 6 days left, 18/30 points done\""));
+        assert!(json.contains("\"scopeCreepPercent\":11.1"));
+        assert!(json.contains("\"series\":[{\"date\":\"2024-03-04\""));
+    }
+
+    #[tokio::test]
+    async fn test_get_sprint_health_requires_board() {
+        let result = get_sprint_health(0).await;
+        assert_eq!(result.unwrap_err().code, "VALIDATION_ERROR");
+    }
+}
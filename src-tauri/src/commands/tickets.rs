@@ -0,0 +1,339 @@
+//! Ticket Commands
+//!
+//! Tauri commands for ticket details and triage actions.
+
+use serde::{Deserialize, Serialize};
+
+use crate::commands::search::CommandError;
+use crate::integrations::traits::{Assignee, Comment, Ticket, Transition};
+
+/// Comment request parameters
+#[derive(Debug, Clone, Deserialize)]
+pub struct CommentParams {
+    pub key: String,
+    #[serde(default = "default_comment_limit")]
+    pub limit: usize,
+}
+
+fn default_comment_limit() -> usize {
+    3
+}
+
+/// Assign request parameters
+#[derive(Debug, Clone, Deserialize)]
+pub struct AssignParams {
+    pub key: String,
+    /// Account id (Cloud) or username (Data Center); `None` unassigns
+    pub assignee: Option<String>,
+    #[serde(rename = "assignToMe", default)]
+    pub assign_to_me: bool,
+}
+
+impl AssignParams {
+    pub fn assignee(&self) -> Assignee {
+        if self.assign_to_me {
+            return Assignee::Me;
+        }
+        match self.assignee.as_deref().map(str::trim) {
+            Some(id) if !id.is_empty() => Assignee::User(id.to_string()),
+            _ => Assignee::Unassigned,
+        }
+    }
+}
+
+/// Ticket detail response
+#[derive(Debug, Clone, Serialize)]
+pub struct TicketDetailDto {
+    pub id: String,
+    pub key: String,
+    pub summary: String,
+    pub description: Option<String>,
+    #[serde(rename = "descriptionMarkdown")]
+    pub description_markdown: Option<String>,
+    pub status: String,
+    #[serde(rename = "statusCategory")]
+    pub status_category: String,
+    pub assignee: Option<String>,
+    pub reporter: Option<String>,
+    pub priority: Option<String>,
+    pub sprint: Option<String>,
+    #[serde(rename = "storyPoints")]
+    pub story_points: Option<f64>,
+    pub epic: Option<String>,
+    pub team: Option<String>,
+    pub labels: Vec<String>,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: String,
+    #[serde(rename = "createdAt")]
+    pub created_at: String,
+}
+
+impl From<Ticket> for TicketDetailDto {
+    fn from(ticket: Ticket) -> Self {
+        Self {
+            id: ticket.id,
+            key: ticket.key,
+            summary: ticket.summary,
+            description: ticket.description,
+            description_markdown: ticket.description_markdown,
+            status: ticket.status.name,
+            status_category: format!("{:?}", ticket.status.category),
+            assignee: ticket.assignee.map(|u| u.name),
+            reporter: ticket.reporter.map(|u| u.name),
+            priority: ticket.priority.map(|p| format!("{:?}", p)),
+            sprint: ticket.sprint,
+            story_points: ticket.story_points,
+            epic: ticket.epic,
+            team: ticket.team,
+            labels: ticket.labels,
+            updated_at: ticket.updated_at.to_rfc3339(),
+            created_at: ticket.created_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Ticket comment for the detail panel
+#[derive(Debug, Clone, Serialize)]
+pub struct CommentDto {
+    pub id: String,
+    pub author: Option<String>,
+    #[serde(rename = "authorAvatar")]
+    pub author_avatar: Option<String>,
+    pub body: String,
+    #[serde(rename = "bodyMarkdown")]
+    pub body_markdown: String,
+    #[serde(rename = "createdAt")]
+    pub created_at: String,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: String,
+}
+
+impl From<Comment> for CommentDto {
+    fn from(comment: Comment) -> Self {
+        let (author, author_avatar) = match comment.author {
+            Some(user) => (Some(user.name), user.avatar_url),
+            None => (None, None),
+        };
+
+        Self {
+            id: comment.id,
+            author,
+            author_avatar,
+            body: comment.body,
+            body_markdown: comment.body_markdown,
+            created_at: comment.created_at.to_rfc3339(),
+            updated_at: comment.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Workflow transition offered in the detail panel
+#[derive(Debug, Clone, Serialize)]
+pub struct TransitionDto {
+    pub id: String,
+    pub name: String,
+    #[serde(rename = "toStatus")]
+    pub to_status: String,
+    #[serde(rename = "toStatusCategory")]
+    pub to_status_category: String,
+}
+
+impl From<Transition> for TransitionDto {
+    fn from(transition: Transition) -> Self {
+        Self {
+            id: transition.id,
+            name: transition.name,
+            to_status: transition.to.name,
+            to_status_category: format!("{:?}", transition.to.category),
+        }
+    }
+}
+
+fn validate_key(key: &str) -> Result<(), CommandError> {
+    if key.trim().is_empty() {
+        return Err(CommandError::validation("Ticket key cannot be empty"));
+    }
+    Ok(())
+}
+
+/// Get a ticket with its rendered description
+#[tauri::command]
+pub async fn get_ticket(key: String) -> Result<TicketDetailDto, CommandError> {
+    validate_key(&key)?;
+
+    // TODO: Wire up to actual TicketRepository
+    Err(CommandError::not_configured("Jira is not configured"))
+}
+
+/// Get the latest comments of a ticket (cached by the search service)
+#[tauri::command]
+pub async fn get_ticket_comments(params: CommentParams) -> Result<Vec<CommentDto>, CommandError> {
+    validate_key(&params.key)?;
+
+    // TODO: Wire up to actual SearchService::get_comments
+    Err(CommandError::not_configured("Jira is not configured"))
+}
+
+/// Get the transitions the user can perform on a ticket
+#[tauri::command]
+pub async fn get_ticket_transitions(key: String) -> Result<Vec<TransitionDto>, CommandError> {
+    validate_key(&key)?;
+
+    // TODO: Wire up to actual TicketCommands::get_transitions
+    Err(CommandError::not_configured("Jira is not configured"))
+}
+
+/// Move a ticket through a workflow transition
+#[tauri::command]
+pub async fn transition_ticket(key: String, transition_id: String) -> Result<(), CommandError> {
+    validate_key(&key)?;
+    if transition_id.trim().is_empty() {
+        return Err(CommandError::validation("Transition id cannot be empty"));
+    }
+
+    // TODO: Wire up to actual TicketCommands::transition
+    Err(CommandError::not_configured("Jira is not configured"))
+}
+
+/// Assign a ticket to a user, to the current user, or unassign it
+#[tauri::command]
+pub async fn assign_ticket(params: AssignParams) -> Result<(), CommandError> {
+    validate_key(&params.key)?;
+
+    // TODO: Wire up to actual TicketCommands::assign with params.assignee()
+    Err(CommandError::not_configured("Jira is not configured"))
+}
+
+/// Add a comment to a ticket
+#[tauri::command]
+pub async fn add_ticket_comment(key: String, body: String) -> Result<CommentDto, CommandError> {
+    validate_key(&key)?;
+    if body.trim().is_empty() {
+        return Err(CommandError::validation("Comment cannot be empty"));
+    }
+
+    // TODO: Wire up to actual TicketCommands::add_comment
+    Err(CommandError::not_configured("Jira is not configured"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::traits::{StatusCategory, TicketStatus, User};
+    use chrono::Utc;
+
+    #[test]
+    fn test_ticket_detail_serialization() {
+        let ticket = Ticket {
+            id: "10001".to_string(),
+            key: "PROJ-1".to_string(),
+            summary: "Login fails".to_string(),
+            description: Some("Steps for SSO".to_string()),
+            description_markdown: Some("Steps for **SSO**".to_string()),
+            status: TicketStatus {
+                name: "In Progress".to_string(),
+                category: StatusCategory::InProgress,
+            },
+            assignee: None,
+            reporter: None,
+            priority: None,
+            sprint: None,
+            story_points: None,
+            epic: None,
+            team: None,
+            labels: vec![],
+            updated_at: Utc::now(),
+            created_at: Utc::now(),
+        };
+
+        let json = serde_json::to_string(&TicketDetailDto::from(ticket)).unwrap();
+        assert!(json.contains("\"descriptionMarkdown\":\"Steps for **SSO**\""));
+        assert!(json.contains("\"statusCategory\":\"InProgress\""));
+    }
+
+    #[test]
+    fn test_comment_params_defaults() {
+        let params: CommentParams = serde_json::from_str(r#"{"key": "PROJ-1"}"#).unwrap();
+        assert_eq!(params.limit, 3);
+    }
+
+    #[test]
+    fn test_comment_dto_serialization() {
+        let comment = Comment {
+            id: "100".to_string(),
+            author: Some(User {
+                id: "abc".to_string(),
+                name: "Jane Doe".to_string(),
+                email: None,
+                avatar_url: Some("https://avatar.example.com/jane".to_string()),
+            }),
+            body: "Looks good".to_string(),
+            body_markdown: "Looks **good**".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        let json = serde_json::to_string(&CommentDto::from(comment)).unwrap();
+        assert!(json.contains("\"author\":\"Jane Doe\""));
+        assert!(json.contains("\"authorAvatar\":\"https://avatar.example.com/jane\""));
+        assert!(json.contains("\"bodyMarkdown\":\"Looks **good**\""));
+    }
+
+    #[test]
+    fn test_assign_params() {
+        let me: AssignParams = serde_json::from_str(r#"{"key": "PROJ-1", "assignToMe": true}"#).unwrap();
+        assert_eq!(me.assignee(), Assignee::Me);
+
+        let user: AssignParams = serde_json::from_str(r#"{"key": "PROJ-1", "assignee": "jdoe"}"#).unwrap();
+        assert_eq!(user.assignee(), Assignee::User("jdoe".to_string()));
+
+        let none: AssignParams = serde_json::from_str(r#"{"key": "PROJ-1", "assignee": null}"#).unwrap();
+        assert_eq!(none.assignee(), Assignee::Unassigned);
+    }
+
+    #[test]
+    fn test_transition_dto_serialization() {
+        let transition = Transition {
+            id: "31".to_string(),
+            name: "Start review".to_string(),
+            to: TicketStatus {
+                name: "In Review".to_string(),
+                category: StatusCategory::InProgress,
+            },
+        };
+
+        let json = serde_json::to_string(&TransitionDto::from(transition)).unwrap();
+        assert!(json.contains("\"toStatus\":\"In Review\""));
+        assert!(json.contains("\"toStatusCategory\":\"InProgress\""));
+    }
+
+    #[tokio::test]
+    async fn test_add_ticket_comment_requires_body() {
+        let result = add_ticket_comment("PROJ-1".to_string(), " ".to_string()).await;
+        assert_eq!(result.unwrap_err().code, "VALIDATION_ERROR");
+    }
+
+    #[tokio::test]
+    async fn test_ticket_updates_report_missing_configuration() {
+        let result = get_ticket("PROJ-1".to_string()).await;
+        assert_eq!(result.unwrap_err().code, "NOT_CONFIGURED");
+
+        let result = get_ticket_transitions("PROJ-1".to_string()).await;
+        assert_eq!(result.unwrap_err().code, "NOT_CONFIGURED");
+
+        let result = transition_ticket("PROJ-1".to_string(), "31".to_string()).await;
+        assert_eq!(result.unwrap_err().code, "NOT_CONFIGURED");
+
+        let result = add_ticket_comment("PROJ-1".to_string(), "On it".to_string()).await;
+        assert_eq!(result.unwrap_err().code, "NOT_CONFIGURED");
+
+        let params = CommentParams { key: "PROJ-1".to_string(), limit: default_comment_limit() };
+        assert_eq!(get_ticket_comments(params).await.unwrap_err().code, "NOT_CONFIGURED");
+    }
+
+    #[tokio::test]
+    async fn test_get_ticket_requires_key() {
+        let result = get_ticket("  ".to_string()).await;
+        assert_eq!(result.unwrap_err().code, "VALIDATION_ERROR");
+    }
+}
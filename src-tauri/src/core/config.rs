@@ -3,7 +3,10 @@
 //! Defines the structure of configuration settings for all integrations
 //! and application preferences.
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Id of the git connection migrated from single-connection configs
+pub const DEFAULT_GIT_CONNECTION: &str = "default";
 
 /// Main application configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -23,8 +26,9 @@ pub struct AppConfig {
 pub struct IntegrationConfig {
     /// Jira configuration
     pub jira: Option<JiraConfig>,
-    /// Git hosting configuration
-    pub git: Option<GitConfig>,
+    /// Git hosting connections, e.g. GitHub and Bitbucket side by side
+    #[serde(default, deserialize_with = "deserialize_git_connections")]
+    pub git: Vec<GitConfig>,
     /// Documentation platform configuration
     pub docs: Option<DocsConfig>,
     /// Monitoring platform configuration
@@ -53,9 +57,22 @@ pub enum GitProviderType {
     GitLab,
 }
 
-/// Git hosting configuration
+/// API used to fetch GitHub pull requests
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum GitHubApi {
+    #[default]
+    Rest,
+    #[serde(rename = "graphql")]
+    GraphQl,
+}
+
+/// Git hosting connection
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GitConfig {
+    /// Unique connection id, which also names its credential slot
+    #[serde(default = "default_git_connection_id")]
+    pub id: String,
     /// Provider type (Bitbucket, GitHub, GitLab)
     pub provider: GitProviderType,
     /// Base URL (for self-hosted instances)
@@ -64,6 +81,63 @@ pub struct GitConfig {
     pub workspace: Option<String>,
     /// Repositories to monitor
     pub repositories: Vec<String>,
+    /// Discover repositories in addition to the listed ones
+    #[serde(default)]
+    pub discovery: Option<RepoDiscoveryConfig>,
+    /// GitHub only: fetch PRs through the REST or the GraphQL API
+    #[serde(default)]
+    pub github_api: GitHubApi,
+}
+
+/// Repository discovery for a git connection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepoDiscoveryConfig {
+    /// GitHub org/user, Bitbucket workspace or GitLab group (defaults to the workspace)
+    pub owner: Option<String>,
+    /// Bitbucket project key
+    pub project: Option<String>,
+    /// Glob patterns of repositories to include
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// Glob patterns of repositories to exclude
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// Whether archived repositories are included
+    #[serde(default)]
+    pub include_archived: bool,
+    /// Refresh interval in minutes
+    pub refresh_interval_minutes: u32,
+}
+
+fn default_git_connection_id() -> String {
+    DEFAULT_GIT_CONNECTION.to_string()
+}
+
+/// Read git connections as a list, accepting the single connection (or null)
+/// stored by older versions
+fn deserialize_git_connections<'de, D>(deserializer: D) -> Result<Vec<GitConfig>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Connections {
+        Many(Vec<GitConfig>),
+        One(GitConfig),
+    }
+
+    Ok(match Option::<Connections>::deserialize(deserializer)? {
+        Some(Connections::Many(connections)) => connections,
+        Some(Connections::One(connection)) => vec![connection],
+        None => Vec::new(),
+    })
+}
+
+impl IntegrationConfig {
+    /// Git connection by id
+    pub fn git_connection(&self, id: &str) -> Option<&GitConfig> {
+        self.git.iter().find(|c| c.id == id)
+    }
 }
 
 /// Documentation platform configuration
@@ -263,16 +337,47 @@ mod tests {
     #[test]
     fn test_git_provider_serialization() {
         let config = GitConfig {
+            id: "work".to_string(),
             provider: GitProviderType::Bitbucket,
             base_url: None,
             workspace: Some("myworkspace".to_string()),
             repositories: vec!["repo1".to_string()],
+            discovery: None,
+            github_api: GitHubApi::default(),
         };
         
         let json = serde_json::to_string(&config).unwrap();
         assert!(json.contains("\"provider\":\"bitbucket\""));
     }
 
+    #[test]
+    fn test_git_connections_list() {
+        let integrations: IntegrationConfig = serde_json::from_str(
+            r#"{"git": [
+                {"id": "github", "provider": "github", "base_url": null, "workspace": null, "repositories": ["acme/api"]},
+                {"id": "bitbucket", "provider": "bitbucket", "base_url": null, "workspace": "acme", "repositories": ["web"]}
+            ]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(integrations.git.len(), 2);
+        assert_eq!(integrations.git_connection("bitbucket").unwrap().provider, GitProviderType::Bitbucket);
+        assert!(integrations.git_connection("gitlab").is_none());
+    }
+
+    #[test]
+    fn test_single_git_connection_is_migrated() {
+        let integrations: IntegrationConfig = serde_json::from_str(
+            r#"{"git": {"provider": "github", "base_url": null, "workspace": null, "repositories": []}}"#,
+        )
+        .unwrap();
+        assert_eq!(integrations.git.len(), 1);
+        assert_eq!(integrations.git[0].id, DEFAULT_GIT_CONNECTION);
+
+        let empty: IntegrationConfig = serde_json::from_str(r#"{"git": null}"#).unwrap();
+        assert!(empty.git.is_empty());
+    }
+
     #[test]
     fn test_jira_config() {
         let jira = JiraConfig {
@@ -8,5 +8,6 @@ pub mod events;
 
 pub use config::AppConfig;
 pub use config::IntegrationConfig;
+pub use config::DEFAULT_GIT_CONNECTION;
 pub use errors::CockpitError;
 pub use events::{AppEvent, EventBus, SharedEventBus, SubscriptionId, create_event_bus};
@@ -5,6 +5,7 @@
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
+use crate::integrations::rate_limit::RateLimitTracker;
 use crate::integrations::traits::IntegrationError;
 
 /// Gemini configuration
@@ -77,6 +78,7 @@ pub struct Risk {
 pub struct GeminiClient {
     config: GeminiConfig,
     http_client: Client,
+    rate_limiter: RateLimitTracker,
 }
 
 impl GeminiClient {
@@ -92,7 +94,21 @@ impl GeminiClient {
             .build()
             .map_err(|e| IntegrationError::Network(e.to_string()))?;
 
-        Ok(Self { config, http_client })
+        Ok(Self {
+            config,
+            http_client,
+            rate_limiter: RateLimitTracker::new("gemini"),
+        })
+    }
+
+    /// Share a rate limit tracker, e.g. with the background poller
+    pub fn with_rate_limiter(mut self, tracker: RateLimitTracker) -> Self {
+        self.rate_limiter = tracker;
+        self
+    }
+
+    pub fn rate_limiter(&self) -> &RateLimitTracker {
+        &self.rate_limiter
     }
 
     /// Analyze a spec/PRD for clarity and completeness
@@ -159,6 +175,7 @@ Respond ONLY with valid JSON, no markdown formatting."#, content)
 
         let api_key = self.config.api_key.as_ref().unwrap();
 
+        self.rate_limiter.check()?;
         let response = self.http_client
             .post(&url)
             .header("Content-Type", "application/json")
@@ -167,7 +184,12 @@ Respond ONLY with valid JSON, no markdown formatting."#, content)
             .send()
             .await?;
 
-        match response.status().as_u16() {
+        let status = response.status().as_u16();
+        if let Some(err) = self.rate_limiter.observe(status, response.headers()) {
+            return Err(err);
+        }
+
+        match status {
             200 => {
                 let result: GeminiResponse = response.json().await
                     .map_err(|e| IntegrationError::ParseError(e.to_string()))?;
@@ -179,7 +201,6 @@ Respond ONLY with valid JSON, no markdown formatting."#, content)
                     .ok_or_else(|| IntegrationError::ApiError("Empty response".to_string()))
             }
             401 => Err(IntegrationError::Auth("Invalid API key".to_string())),
-            429 => Err(IntegrationError::RateLimit),
             status => {
                 let body = response.text().await.unwrap_or_default();
                 Err(IntegrationError::ApiError(format!("Status {}: {}", status, body)))
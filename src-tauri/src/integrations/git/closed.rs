@@ -0,0 +1,519 @@
+//! Closed Pull Requests
+//!
+//! Fetches PRs merged or declined since a point in time, with when they were
+//! closed, who merged them and when they were first reviewed, for lead time,
+//! review latency and throughput metrics. Closing a PR updates it, so listings
+//! are walked by last update and stop once PRs predate the period. GitHub is
+//! always queried through REST here, whatever `GitConfig::github_api` says.
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use super::provider::{
+    fan_out, parse_next_link, BitbucketPr, BitbucketPrList, BitbucketUser, GitHubPr, GitHubReview,
+    GitLabMr, GitLabNote, GitProvider, GitProviderType, BITBUCKET_LIST_FIELDS,
+};
+use crate::integrations::traits::{
+    ChecksStatus, ClosedPrFetchResult, ClosedPullRequest, IntegrationError, PrFilter, PrState, PullRequest,
+    User,
+};
+
+/// Upper bound of activity entries read per Bitbucket PR; past it the first review is unknown
+const MAX_BITBUCKET_ACTIVITY: usize = 500;
+
+/// Closed PR with its state set and staleness cleared, which only applies to open PRs
+fn closed(
+    mut pr: PullRequest,
+    state: PrState,
+    closed_at: DateTime<Utc>,
+    merged_by: Option<User>,
+    first_review_at: Option<DateTime<Utc>>,
+) -> ClosedPullRequest {
+    pr.state = state;
+    pr.is_stale = false;
+    ClosedPullRequest {
+        merged_at: (state == PrState::Merged).then_some(closed_at),
+        pr,
+        closed_at,
+        merged_by,
+        first_review_at,
+    }
+}
+
+/// Earliest submitted review by someone other than the author
+fn first_github_review(author_id: i64, reviews: &[GitHubReview]) -> Option<DateTime<Utc>> {
+    reviews
+        .iter()
+        .filter(|r| r.state != "PENDING" && r.user.as_ref().is_none_or(|u| u.id != author_id))
+        .filter_map(|r| r.submitted_at)
+        .min()
+}
+
+fn map_bitbucket_state(state: Option<&str>) -> Option<PrState> {
+    match state? {
+        "MERGED" => Some(PrState::Merged),
+        "DECLINED" | "SUPERSEDED" => Some(PrState::Declined),
+        _ => None,
+    }
+}
+
+/// Whether a GitLab note counts as a review: a comment, or the approval system note
+fn is_gitlab_review_note(note: &GitLabNote) -> bool {
+    !note.system || note.body.starts_with("approved this merge request")
+}
+
+impl GitProvider {
+    /// PRs of all repositories closed since `since`, most recently closed first
+    pub(super) async fn fetch_closed_prs(
+        &self,
+        since: DateTime<Utc>,
+        filter: &PrFilter,
+    ) -> Result<ClosedPrFetchResult, IntegrationError> {
+        if self.config().provider == GitProviderType::Bitbucket && self.config().workspace.is_none() {
+            return Err(IntegrationError::ConfigError("Workspace required for Bitbucket".to_string()));
+        }
+
+        let repos = if filter.repositories.is_empty() {
+            self.repositories().await?
+        } else {
+            filter.repositories.clone()
+        };
+
+        let results = fan_out(
+            &repos,
+            self.config().max_concurrency,
+            std::time::Duration::from_secs(self.config().repo_timeout_secs),
+            |repo| {
+                let provider = self.clone();
+                let filter = filter.clone();
+                async move { provider.fetch_repo_closed_prs(&repo, since, &filter).await }
+            },
+        )
+        .await;
+
+        let (mut prs, errors) = Self::collect_repo_results(&repos, results)?;
+        prs.sort_by_key(|c| std::cmp::Reverse(c.closed_at));
+        prs.truncate(filter.limit);
+        Ok(ClosedPrFetchResult { prs, errors })
+    }
+
+    async fn fetch_repo_closed_prs(
+        &self,
+        repo: &str,
+        since: DateTime<Utc>,
+        filter: &PrFilter,
+    ) -> Result<Vec<ClosedPullRequest>, IntegrationError> {
+        match self.config().provider {
+            GitProviderType::Bitbucket => self.fetch_bitbucket_closed_prs(repo, since, filter).await,
+            GitProviderType::GitHub => self.fetch_github_closed_prs(repo, since, filter).await,
+            GitProviderType::GitLab => self.fetch_gitlab_closed_prs(repo, since, filter).await,
+        }
+    }
+
+    async fn fetch_github_closed_prs(
+        &self,
+        repo: &str,
+        since: DateTime<Utc>,
+        filter: &PrFilter,
+    ) -> Result<Vec<ClosedPullRequest>, IntegrationError> {
+        let mut prs = Vec::new();
+        let mut next_url = Some(format!(
+            "{}/repos/{}/pulls?state=closed&sort=updated&direction=desc&per_page={}",
+            self.config().api_base_url(), repo, self.config().effective_page_size()
+        ));
+
+        while let Some(url) = next_url.take() {
+            let response = self.get(&url).await?;
+            if response.status != 200 {
+                return Err(self.repo_status_error(response.status, repo));
+            }
+
+            next_url = response.header("link").and_then(parse_next_link);
+
+            let page: Vec<GitHubPr> = response.json()?;
+
+            for pr in page {
+                if pr.updated_at < since {
+                    return Ok(prs);
+                }
+                let Some(closed_at) = pr.closed_at.filter(|at| *at >= since) else { continue };
+                let state = if pr.merged_at.is_some() { PrState::Merged } else { PrState::Declined };
+                if !filter.accepts_closed(state) {
+                    continue;
+                }
+
+                // Only the single pull request endpoint names the merger
+                let pr = match state {
+                    PrState::Merged => {
+                        let url = format!("{}/repos/{}/pulls/{}", self.config().api_base_url(), repo, pr.number);
+                        self.get_optional::<GitHubPr>(&url).await?.unwrap_or(pr)
+                    }
+                    _ => pr,
+                };
+
+                let reviews = self.fetch_github_reviews(repo, pr.number).await?;
+                let mapped = self.map_github_pr(&pr, repo, &reviews, ChecksStatus::None);
+                let merged_by = pr.merged_by.as_ref().map(|u| self.map_github_user(u));
+                let first_review_at = first_github_review(pr.user.id, &reviews);
+                prs.push(closed(mapped, state, closed_at, merged_by, first_review_at));
+
+                if prs.len() >= filter.limit {
+                    return Ok(prs);
+                }
+            }
+        }
+
+        Ok(prs)
+    }
+
+    async fn fetch_bitbucket_closed_prs(
+        &self,
+        repo: &str,
+        since: DateTime<Utc>,
+        filter: &PrFilter,
+    ) -> Result<Vec<ClosedPullRequest>, IntegrationError> {
+        let workspace = self.config().workspace.as_deref()
+            .ok_or_else(|| IntegrationError::ConfigError("Workspace required for Bitbucket".to_string()))?;
+
+        let states = [("MERGED", PrState::Merged), ("DECLINED", PrState::Declined)]
+            .into_iter()
+            .filter(|(_, state)| filter.accepts_closed(*state))
+            .map(|(name, _)| format!("state={}", name))
+            .collect::<Vec<_>>()
+            .join("&");
+
+        let mut prs = Vec::new();
+        let mut next_url = Some(format!(
+            "{}/repositories/{}/{}/pullrequests?{}&sort=-updated_on&pagelen={}&fields={}",
+            self.config().api_base_url(), workspace, repo, states, self.config().effective_page_size(),
+            BITBUCKET_LIST_FIELDS
+        ));
+
+        while let Some(url) = next_url.take() {
+            let response = self.get(&url).await?;
+            if response.status != 200 {
+                return Err(self.repo_status_error(response.status, repo));
+            }
+
+            let result: BitbucketPrList = response.json()?;
+
+            for pr in result.values {
+                if pr.updated_on < since {
+                    return Ok(prs);
+                }
+                let Some(state) = map_bitbucket_state(pr.state.as_deref()) else { continue };
+
+                let activity = self.fetch_bitbucket_activity(workspace, repo, pr.id).await?;
+                let (closed_at, first_review_at) = match activity {
+                    Some((ref activity, complete)) => {
+                        let (closed_at, first_review_at) = self.bitbucket_milestones(&pr, activity);
+                        // Truncation drops the oldest entries, where the first review is
+                        (closed_at, first_review_at.filter(|_| complete))
+                    }
+                    None => (None, None),
+                };
+                // The last update is the closing one unless the PR was touched since
+                let closed_at = closed_at.unwrap_or(pr.updated_on);
+                if closed_at < since {
+                    continue;
+                }
+
+                let merged_by = match state {
+                    PrState::Merged => pr.closed_by.as_ref().map(|u| self.map_bitbucket_user(u)),
+                    _ => None,
+                };
+                let mapped = self.map_bitbucket_pr(&pr, repo, ChecksStatus::None);
+                prs.push(closed(mapped, state, closed_at, merged_by, first_review_at));
+
+                if prs.len() >= filter.limit {
+                    return Ok(prs);
+                }
+            }
+
+            next_url = result.next;
+        }
+
+        Ok(prs)
+    }
+
+    /// Activity of a Bitbucket PR, newest first, and whether it is complete
+    /// (`None` when unavailable)
+    async fn fetch_bitbucket_activity(
+        &self,
+        workspace: &str,
+        repo: &str,
+        id: i64,
+    ) -> Result<Option<(Vec<BitbucketActivity>, bool)>, IntegrationError> {
+        let mut activity = Vec::new();
+        let mut next_url = Some(format!(
+            "{}/repositories/{}/{}/pullrequests/{}/activity?pagelen=50",
+            self.config().api_base_url(), workspace, repo, id
+        ));
+
+        while let Some(url) = next_url.take() {
+            let Some(page) = self.get_optional::<BitbucketActivityPage>(&url).await? else {
+                return Ok(None);
+            };
+            activity.extend(page.values);
+            next_url = page.next;
+            if activity.len() >= MAX_BITBUCKET_ACTIVITY {
+                break;
+            }
+        }
+
+        Ok(Some((activity, next_url.is_none())))
+    }
+
+    /// When the PR was closed and first reviewed, according to its activity
+    fn bitbucket_milestones(
+        &self,
+        pr: &BitbucketPr,
+        activity: &[BitbucketActivity],
+    ) -> (Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+        let author_id = self.map_bitbucket_user(&pr.author).id;
+        let by_reviewer = |user: &Option<BitbucketUser>| {
+            user.as_ref().is_none_or(|u| self.map_bitbucket_user(u).id != author_id)
+        };
+
+        let closed_at = activity
+            .iter()
+            .filter_map(|a| a.update.as_ref())
+            .filter(|u| u.state.is_some() && u.state == pr.state)
+            .map(|u| u.date)
+            .max();
+
+        let first_review_at = activity
+            .iter()
+            .filter_map(|a| {
+                if let Some(event) = a.approval.as_ref().or(a.changes_requested.as_ref()) {
+                    return by_reviewer(&event.user).then_some(event.date);
+                }
+                let comment = a.comment.as_ref()?;
+                by_reviewer(&comment.user).then_some(comment.created_on)
+            })
+            .min();
+
+        (closed_at, first_review_at)
+    }
+
+    async fn fetch_gitlab_closed_prs(
+        &self,
+        repo: &str,
+        since: DateTime<Utc>,
+        filter: &PrFilter,
+    ) -> Result<Vec<ClosedPullRequest>, IntegrationError> {
+        let project = self.config().gitlab_project_path(repo);
+        let mut prs = Vec::new();
+
+        for (name, state) in [("merged", PrState::Merged), ("closed", PrState::Declined)] {
+            if !filter.accepts_closed(state) {
+                continue;
+            }
+
+            let mut found = 0;
+            let mut page = Some(1);
+            while let Some(current) = page {
+                let url = format!(
+                    "{}/projects/{}/merge_requests?state={}&updated_after={}&order_by=updated_at&sort=desc&per_page={}&page={}",
+                    self.config().api_base_url(),
+                    urlencoding::encode(&project),
+                    name,
+                    urlencoding::encode(&since.to_rfc3339()),
+                    self.config().effective_page_size(),
+                    current
+                );
+
+                let response = self.get(&url).await?;
+                if response.status != 200 {
+                    return Err(self.repo_status_error(response.status, repo));
+                }
+
+                page = response
+                    .header("x-next-page")
+                    .and_then(|v| v.trim().parse::<usize>().ok());
+
+                let mrs: Vec<GitLabMr> = response.json()?;
+
+                for mr in mrs {
+                    let closed_at = match state {
+                        PrState::Merged => mr.merged_at,
+                        _ => mr.closed_at,
+                    };
+                    let closed_at = closed_at.unwrap_or(mr.updated_at);
+                    if closed_at < since {
+                        continue;
+                    }
+
+                    let approved_by = self.fetch_gitlab_approvals(&project, mr.iid).await?;
+                    let first_review_at = self.fetch_gitlab_first_review(&project, &mr).await?;
+                    let merged_by = mr.merge_user.as_ref().or(mr.merged_by.as_ref()).map(|u| self.map_gitlab_user(u));
+                    let mapped = self.map_gitlab_mr(&mr, repo, &approved_by, None);
+                    prs.push(closed(mapped, state, closed_at, merged_by, first_review_at));
+
+                    found += 1;
+                    if found >= filter.limit {
+                        page = None;
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(prs)
+    }
+
+    /// First comment or approval on a merge request by someone other than the author
+    async fn fetch_gitlab_first_review(&self, project: &str, mr: &GitLabMr) -> Result<Option<DateTime<Utc>>, IntegrationError> {
+        let url = format!(
+            "{}/projects/{}/merge_requests/{}/notes?sort=asc&order_by=created_at&per_page=100",
+            self.config().api_base_url(), urlencoding::encode(project), mr.iid
+        );
+
+        let notes: Vec<GitLabNote> = self.get_optional(&url).await?.unwrap_or_default();
+        let author_id = self.map_gitlab_user(&mr.author).id;
+        Ok(notes
+            .iter()
+            .filter(|n| {
+                is_gitlab_review_note(n)
+                    && n.author.as_ref().is_none_or(|u| self.map_gitlab_user(u).id != author_id)
+            })
+            .map(|n| n.created_at)
+            .min())
+    }
+}
+
+// ===== Closed PR API Types =====
+
+#[derive(Debug, Deserialize)]
+struct BitbucketActivityPage {
+    values: Vec<BitbucketActivity>,
+    next: Option<String>,
+}
+
+/// One entry of a PR's activity, holding exactly one of its fields
+#[derive(Debug, Deserialize)]
+struct BitbucketActivity {
+    update: Option<BitbucketActivityUpdate>,
+    approval: Option<BitbucketActivityReview>,
+    changes_requested: Option<BitbucketActivityReview>,
+    comment: Option<BitbucketActivityComment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketActivityUpdate {
+    state: Option<String>,
+    date: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketActivityReview {
+    date: DateTime<Utc>,
+    /// Null for deleted accounts
+    user: Option<BitbucketUser>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketActivityComment {
+    created_on: DateTime<Utc>,
+    user: Option<BitbucketUser>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::git::GitConfig;
+    use chrono::TimeZone;
+
+    fn at(hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 3, 1, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_first_github_review() {
+        let reviews: Vec<GitHubReview> = serde_json::from_value(serde_json::json!([
+            {"user": {"id": 1, "login": "alice", "avatar_url": ""}, "state": "COMMENTED",
+             "submitted_at": "2024-03-01T02:00:00Z"},
+            {"user": {"id": 2, "login": "bob", "avatar_url": ""}, "state": "PENDING", "submitted_at": null},
+            {"user": {"id": 3, "login": "carol", "avatar_url": ""}, "state": "APPROVED",
+             "submitted_at": "2024-03-01T05:00:00Z"},
+            {"user": {"id": 2, "login": "bob", "avatar_url": ""}, "state": "CHANGES_REQUESTED",
+             "submitted_at": "2024-03-01T04:00:00Z"}
+        ]))
+        .unwrap();
+
+        // The author's own comment does not count as a review
+        assert_eq!(first_github_review(1, &reviews), Some(at(4)));
+        assert_eq!(first_github_review(1, &reviews[..2]), None);
+    }
+
+    #[test]
+    fn test_map_bitbucket_state() {
+        assert_eq!(map_bitbucket_state(Some("MERGED")), Some(PrState::Merged));
+        assert_eq!(map_bitbucket_state(Some("SUPERSEDED")), Some(PrState::Declined));
+        assert_eq!(map_bitbucket_state(Some("OPEN")), None);
+        assert_eq!(map_bitbucket_state(None), None);
+    }
+
+    #[test]
+    fn test_bitbucket_milestones() {
+        let provider = GitProvider::new(GitConfig::bitbucket("acme", "alice").with_token("t")).unwrap();
+        let user = |name: &str| serde_json::json!({"uuid": format!("{{{}}}", name), "display_name": name, "links": {}});
+        let pr: BitbucketPr = serde_json::from_value(serde_json::json!({
+            "id": 7, "title": "Fix", "description": null, "author": user("alice"),
+            "source": {"branch": {"name": "fix"}}, "destination": {"branch": {"name": "main"}},
+            "created_on": "2024-03-01T00:00:00Z", "updated_on": "2024-03-01T12:00:00Z",
+            "links": {"html": {"href": "https://bitbucket.org/acme/api/pull-requests/7"}},
+            "state": "MERGED", "closed_by": user("bob")
+        }))
+        .unwrap();
+        let activity: Vec<BitbucketActivity> = serde_json::from_value(serde_json::json!([
+            {"comment": {"created_on": "2024-03-01T11:00:00Z", "user": user("alice")}},
+            {"update": {"state": "MERGED", "date": "2024-03-01T10:00:00Z"}},
+            {"approval": {"date": "2024-03-01T06:00:00Z", "user": user("bob")}},
+            {"comment": {"created_on": "2024-03-01T03:00:00Z", "user": user("carol")}},
+            {"comment": {"created_on": "2024-03-01T01:00:00Z", "user": user("alice")}},
+            {"update": {"state": "OPEN", "date": "2024-03-01T00:00:00Z"}}
+        ]))
+        .unwrap();
+
+        let (closed_at, first_review_at) = provider.bitbucket_milestones(&pr, &activity);
+        assert_eq!(closed_at, Some(at(10)));
+        assert_eq!(first_review_at, Some(at(3)));
+    }
+
+    #[test]
+    fn test_gitlab_review_notes() {
+        let notes: Vec<GitLabNote> = serde_json::from_value(serde_json::json!([
+            {"id": 1, "body": "added 2 commits", "author": {"id": 2, "username": "bob", "avatar_url": null},
+             "system": true, "created_at": "2024-03-01T01:00:00Z", "updated_at": "2024-03-01T01:00:00Z"},
+            {"id": 2, "body": "approved this merge request", "author": {"id": 2, "username": "bob", "avatar_url": null},
+             "system": true, "created_at": "2024-03-01T02:00:00Z", "updated_at": "2024-03-01T02:00:00Z"},
+            {"id": 3, "body": "Looks good", "author": {"id": 3, "username": "carol", "avatar_url": null},
+             "created_at": "2024-03-01T03:00:00Z", "updated_at": "2024-03-01T03:00:00Z"}
+        ]))
+        .unwrap();
+
+        let reviews: Vec<bool> = notes.iter().map(is_gitlab_review_note).collect();
+        assert_eq!(reviews, vec![false, true, true]);
+    }
+
+    #[test]
+    fn test_closed_sets_state_and_merge_time() {
+        let pr: PullRequest = serde_json::from_value(serde_json::json!({
+            "id": "1", "repository": "repo", "title": "PR", "description": null, "state": "Open",
+            "author": {"id": "alice", "name": "alice", "email": null, "avatar_url": null},
+            "reviewers": [], "source_branch": "feature", "target_branch": "main",
+            "checks_status": "None", "is_stale": true,
+            "updated_at": "2024-03-01T00:00:00Z", "created_at": "2024-03-01T00:00:00Z", "url": ""
+        }))
+        .unwrap();
+
+        let merged = closed(pr.clone(), PrState::Merged, at(9), None, Some(at(2)));
+        assert_eq!((merged.pr.state, merged.pr.is_stale), (PrState::Merged, false));
+        assert_eq!(merged.merged_at, Some(at(9)));
+
+        let declined = closed(pr, PrState::Declined, at(9), None, None);
+        assert_eq!(declined.pr.state, PrState::Declined);
+        assert_eq!(declined.merged_at, None);
+    }
+}
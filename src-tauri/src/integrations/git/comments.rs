@@ -0,0 +1,498 @@
+//! Pull Request Review Comments
+//!
+//! Fetches the comments of a PR, both those anchored to a line of the diff and
+//! those on the conversation, grouped into threads with their resolved state.
+//! GitHub only exposes whether a thread is resolved through GraphQL, so its
+//! comments are always fetched that way regardless of `GitConfig::github_api`.
+
+use chrono::{DateTime, Utc};
+use serde::de::IgnoredAny;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use super::graphql::{field_error, literal, split_repo, GqlActor, GqlNodes};
+use super::provider::{BitbucketUser, GitLabUser, GitProvider};
+use crate::integrations::traits::{IntegrationError, ReviewComment, User};
+
+/// Upper bound of comments loaded per PR; pages stop once it is reached
+const MAX_REVIEW_COMMENTS: usize = 500;
+
+const GITHUB_COMMENT_FIELDS: &str = "databaseId body createdAt updatedAt \
+author { login avatarUrl ... on User { databaseId } ... on Bot { databaseId } }";
+
+fn build_comments_query(owner: &str, name: &str, number: u64) -> String {
+    format!(
+        "query {{ repository(owner: {}, name: {}) {{ pullRequest(number: {}) {{ \
+         reviewThreads(last: 100) {{ nodes {{ id isResolved path line originalLine \
+         comments(first: 50) {{ nodes {{ {fields} }} }} }} }} \
+         comments(last: 100) {{ nodes {{ {fields} }} }} }} }} }}",
+        literal(owner),
+        literal(name),
+        number,
+        fields = GITHUB_COMMENT_FIELDS
+    )
+}
+
+/// Error for a comment listing that did not answer 200
+fn comments_error(status: u16, id: &str) -> IntegrationError {
+    match status {
+        401 | 403 => IntegrationError::Auth(format!("Access denied to comments of PR {}", id)),
+        404 => IntegrationError::NotFound(format!("PR {} not found", id)),
+        429 => IntegrationError::RateLimit { reset_at: None },
+        status => IntegrationError::ApiError(format!("Status {} for comments of PR {}", status, id)),
+    }
+}
+
+/// Id of the comment a Bitbucket comment replies to at the top, itself for top-level comments
+fn bitbucket_thread_root(id: i64, parents: &HashMap<i64, i64>) -> i64 {
+    let mut root = id;
+    // Bounded in case a reply's parent is missing from the listing
+    for _ in 0..parents.len() {
+        match parents.get(&root) {
+            Some(&parent) => root = parent,
+            None => break,
+        }
+    }
+    root
+}
+
+impl GitProvider {
+    /// Review threads and conversation comments of a GitHub PR
+    pub(super) async fn fetch_github_review_comments(
+        &self,
+        repo: &str,
+        id: &str,
+    ) -> Result<Vec<ReviewComment>, IntegrationError> {
+        let (owner, name) = split_repo(repo).ok_or_else(|| {
+            IntegrationError::ConfigError(format!("Repository {} is not in owner/name form", repo))
+        })?;
+        let number: u64 = id
+            .parse()
+            .map_err(|_| IntegrationError::NotFound(format!("PR {} not found", id)))?;
+
+        let response = self.query_graphql(&build_comments_query(owner, name, number)).await?;
+        let pr = response
+            .field("repository")
+            .and_then(|r| r.get("pullRequest"))
+            .filter(|pr| !pr.is_null())
+            .ok_or_else(|| field_error(response.error_at("repository"), format!("PR {} not found", id)))?;
+        let pr: GqlCommentsPr =
+            serde_json::from_value(pr.clone()).map_err(|e| IntegrationError::ParseError(e.to_string()))?;
+
+        Ok(self.map_github_review_comments(pr))
+    }
+
+    fn map_github_review_comments(&self, pr: GqlCommentsPr) -> Vec<ReviewComment> {
+        let mut comments = Vec::new();
+
+        for thread in pr.review_threads.nodes {
+            // Threads on lines that changed since keep only their original line
+            let line = thread.line.or(thread.original_line);
+            for comment in thread.comments.nodes {
+                comments.push(self.map_github_comment(comment, &thread.id, Some(thread.path.clone()), line, Some(thread.is_resolved)));
+            }
+        }
+
+        for comment in pr.comments.nodes {
+            let thread_id = comment.database_id.to_string();
+            comments.push(self.map_github_comment(comment, &thread_id, None, None, None));
+        }
+
+        comments
+    }
+
+    fn map_github_comment(
+        &self,
+        comment: GqlComment,
+        thread_id: &str,
+        path: Option<String>,
+        line: Option<u32>,
+        resolved: Option<bool>,
+    ) -> ReviewComment {
+        ReviewComment {
+            id: comment.database_id.to_string(),
+            thread_id: thread_id.to_string(),
+            author: self.map_graphql_actor(comment.author.as_ref()),
+            body: comment.body,
+            path,
+            line,
+            resolved,
+            created_at: comment.created_at,
+            updated_at: comment.updated_at,
+        }
+    }
+
+    /// Comments of a Bitbucket PR, threaded through their parents
+    pub(super) async fn fetch_bitbucket_review_comments(
+        &self,
+        repo: &str,
+        id: &str,
+    ) -> Result<Vec<ReviewComment>, IntegrationError> {
+        let workspace = self.config().workspace.as_deref()
+            .ok_or_else(|| IntegrationError::ConfigError("Workspace required for Bitbucket".to_string()))?;
+
+        let mut comments = Vec::new();
+        let mut next_url = Some(format!(
+            "{}/repositories/{}/{}/pullrequests/{}/comments?pagelen=100",
+            self.config().api_base_url(), workspace, repo, id
+        ));
+
+        while let Some(url) = next_url.take() {
+            let response = self.get(&url).await?;
+            if response.status != 200 {
+                return Err(comments_error(response.status, id));
+            }
+
+            let page: BitbucketCommentPage = response.json()?;
+            comments.extend(page.values);
+            if comments.len() < MAX_REVIEW_COMMENTS {
+                next_url = page.next;
+            }
+        }
+
+        Ok(self.map_bitbucket_review_comments(comments))
+    }
+
+    fn map_bitbucket_review_comments(&self, comments: Vec<BitbucketComment>) -> Vec<ReviewComment> {
+        let parents: HashMap<i64, i64> = comments
+            .iter()
+            .filter_map(|c| Some((c.id, c.parent.as_ref()?.id)))
+            .collect();
+        // Only inline threads can be resolved; the root comment carries the resolution
+        let resolved: HashMap<i64, bool> = comments
+            .iter()
+            .filter(|c| c.parent.is_none() && c.inline.is_some())
+            .map(|c| (c.id, c.resolution.is_some()))
+            .collect();
+
+        comments
+            .into_iter()
+            .filter(|c| !c.deleted)
+            .map(|c| {
+                let root = bitbucket_thread_root(c.id, &parents);
+                ReviewComment {
+                    id: c.id.to_string(),
+                    thread_id: root.to_string(),
+                    author: match c.user {
+                        Some(ref user) => self.map_bitbucket_user(user),
+                        None => User {
+                            id: "deleted".to_string(),
+                            name: "Deleted user".to_string(),
+                            email: None,
+                            avatar_url: None,
+                        },
+                    },
+                    body: c.content.raw,
+                    line: c.inline.as_ref().and_then(|i| i.to.or(i.from)),
+                    path: c.inline.map(|i| i.path),
+                    resolved: resolved.get(&root).copied(),
+                    updated_at: c.updated_on.unwrap_or(c.created_on),
+                    created_at: c.created_on,
+                }
+            })
+            .collect()
+    }
+
+    /// Discussions of a GitLab merge request, without system notes
+    pub(super) async fn fetch_gitlab_review_comments(
+        &self,
+        repo: &str,
+        id: &str,
+    ) -> Result<Vec<ReviewComment>, IntegrationError> {
+        let project = self.config().gitlab_project_path(repo);
+        let mut discussions = Vec::new();
+        let mut page = Some(1);
+
+        while let Some(current) = page {
+            let url = format!(
+                "{}/projects/{}/merge_requests/{}/discussions?per_page=100&page={}",
+                self.config().api_base_url(),
+                urlencoding::encode(&project),
+                id,
+                current
+            );
+
+            let response = self.get(&url).await?;
+            if response.status != 200 {
+                return Err(comments_error(response.status, id));
+            }
+
+            page = response
+                .header("x-next-page")
+                .and_then(|v| v.trim().parse::<usize>().ok());
+            let batch: Vec<GitLabDiscussion> = response.json()?;
+            discussions.extend(batch);
+
+            let loaded: usize = discussions.iter().map(|d| d.notes.len()).sum();
+            if loaded >= MAX_REVIEW_COMMENTS {
+                break;
+            }
+        }
+
+        Ok(self.map_gitlab_review_comments(discussions))
+    }
+
+    fn map_gitlab_review_comments(&self, discussions: Vec<GitLabDiscussion>) -> Vec<ReviewComment> {
+        let mut comments = Vec::new();
+
+        for discussion in discussions {
+            let notes: Vec<GitLabReviewNote> = discussion.notes.into_iter().filter(|n| !n.system).collect();
+            let resolvable = notes.iter().any(|n| n.resolvable);
+            let resolved = resolvable.then(|| notes.iter().filter(|n| n.resolvable).all(|n| n.resolved));
+
+            for note in notes {
+                let position = note.position.as_ref();
+                comments.push(ReviewComment {
+                    id: note.id.to_string(),
+                    thread_id: discussion.id.clone(),
+                    author: self.map_gitlab_user(&note.author),
+                    body: note.body,
+                    path: position.and_then(|p| p.new_path.clone().or_else(|| p.old_path.clone())),
+                    line: position.and_then(|p| p.new_line.or(p.old_line)),
+                    resolved,
+                    created_at: note.created_at,
+                    updated_at: note.updated_at,
+                });
+            }
+        }
+
+        comments
+    }
+}
+
+// ===== Review Comment API Types =====
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GqlCommentsPr {
+    #[serde(default)]
+    review_threads: GqlNodes<GqlReviewThread>,
+    #[serde(default)]
+    comments: GqlNodes<GqlComment>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GqlReviewThread {
+    id: String,
+    #[serde(default)]
+    is_resolved: bool,
+    path: String,
+    line: Option<u32>,
+    original_line: Option<u32>,
+    #[serde(default)]
+    comments: GqlNodes<GqlComment>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GqlComment {
+    database_id: i64,
+    #[serde(default)]
+    body: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    /// Null for deleted accounts
+    author: Option<GqlActor>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketCommentPage {
+    values: Vec<BitbucketComment>,
+    next: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketComment {
+    id: i64,
+    content: BitbucketCommentContent,
+    /// Null for deleted accounts
+    user: Option<BitbucketUser>,
+    created_on: DateTime<Utc>,
+    updated_on: Option<DateTime<Utc>>,
+    #[serde(default)]
+    deleted: bool,
+    inline: Option<BitbucketInline>,
+    parent: Option<BitbucketCommentRef>,
+    /// Present once the thread is resolved
+    resolution: Option<IgnoredAny>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketCommentContent {
+    #[serde(default)]
+    raw: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketInline {
+    path: String,
+    /// Line in the old version, for comments on removed lines
+    from: Option<u32>,
+    /// Line in the new version
+    to: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketCommentRef {
+    id: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabDiscussion {
+    id: String,
+    #[serde(default)]
+    notes: Vec<GitLabReviewNote>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabReviewNote {
+    id: i64,
+    body: String,
+    author: GitLabUser,
+    #[serde(default)]
+    system: bool,
+    #[serde(default)]
+    resolvable: bool,
+    #[serde(default)]
+    resolved: bool,
+    position: Option<GitLabPosition>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabPosition {
+    new_path: Option<String>,
+    old_path: Option<String>,
+    new_line: Option<u32>,
+    old_line: Option<u32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::git::GitConfig;
+
+    fn provider(config: GitConfig) -> GitProvider {
+        GitProvider::new(config.with_token("t")).unwrap()
+    }
+
+    fn github_comment(id: i64, login: &str, at: &str) -> serde_json::Value {
+        serde_json::json!({
+            "databaseId": id,
+            "body": format!("Comment {}", id),
+            "createdAt": at,
+            "updatedAt": at,
+            "author": {"login": login, "avatarUrl": "https://avatars.example.com/u", "databaseId": id * 10}
+        })
+    }
+
+    #[test]
+    fn test_map_github_review_comments() {
+        let pr: GqlCommentsPr = serde_json::from_value(serde_json::json!({
+            "reviewThreads": {"nodes": [
+                {"id": "T1", "isResolved": false, "path": "src/lib.rs", "line": null, "originalLine": 12,
+                 "comments": {"nodes": [
+                     github_comment(1, "bob", "2024-03-01T09:00:00Z"),
+                     github_comment(2, "alice", "2024-03-01T10:00:00Z")
+                 ]}},
+                {"id": "T2", "isResolved": true, "path": "src/main.rs", "line": 3, "originalLine": 3,
+                 "comments": {"nodes": [github_comment(3, "carol", "2024-03-01T08:00:00Z")]}}
+            ]},
+            "comments": {"nodes": [github_comment(4, "bob", "2024-03-01T11:00:00Z")]}
+        }))
+        .unwrap();
+
+        let comments = provider(GitConfig::github("alice")).map_github_review_comments(pr);
+
+        assert_eq!(comments.len(), 4);
+        assert_eq!(comments[0].thread_id, "T1");
+        assert_eq!(comments[0].line, Some(12));
+        assert_eq!(comments[0].resolved, Some(false));
+        assert_eq!(comments[2].resolved, Some(true));
+        // Conversation comments are their own thread and cannot be resolved
+        assert_eq!((comments[3].path.as_deref(), comments[3].resolved), (None, None));
+        assert_eq!(comments[3].thread_id, "4");
+        assert_eq!(comments[3].author.id, "40");
+    }
+
+    #[test]
+    fn test_map_bitbucket_review_comments() {
+        let user = |name: &str| serde_json::json!({"uuid": format!("{{{}}}", name), "display_name": name, "links": {}});
+        let page: BitbucketCommentPage = serde_json::from_value(serde_json::json!({
+            "values": [
+                {"id": 10, "content": {"raw": "Rename this"}, "user": user("bob"),
+                 "created_on": "2024-03-01T09:00:00Z", "updated_on": "2024-03-01T09:00:00Z",
+                 "inline": {"path": "src/a.rs", "from": null, "to": 7}},
+                {"id": 11, "content": {"raw": "Done"}, "user": user("alice"),
+                 "created_on": "2024-03-01T10:00:00Z", "parent": {"id": 10},
+                 "inline": {"path": "src/a.rs", "from": null, "to": 7}},
+                {"id": 12, "content": {"raw": "Nice"}, "user": null, "created_on": "2024-03-01T11:00:00Z",
+                 "parent": {"id": 11}},
+                {"id": 13, "content": {"raw": "Gone"}, "user": user("bob"), "deleted": true,
+                 "created_on": "2024-03-01T12:00:00Z"},
+                {"id": 14, "content": {"raw": "Resolved"}, "user": user("carol"),
+                 "created_on": "2024-03-01T12:00:00Z", "inline": {"path": "src/b.rs", "from": 3, "to": null},
+                 "resolution": {"type": "comment_resolution"}}
+            ],
+            "next": null
+        }))
+        .unwrap();
+
+        let comments = provider(GitConfig::bitbucket("acme", "alice")).map_bitbucket_review_comments(page.values);
+
+        assert_eq!(comments.len(), 4);
+        assert!(comments[..3].iter().all(|c| c.thread_id == "10" && c.resolved == Some(false)));
+        assert_eq!(comments[1].updated_at, comments[1].created_at);
+        assert_eq!(comments[2].author.name, "Deleted user");
+        assert_eq!((comments[3].line, comments[3].resolved), (Some(3), Some(true)));
+    }
+
+    #[test]
+    fn test_map_gitlab_review_comments() {
+        let author = |id: i64, name: &str| serde_json::json!({"id": id, "username": name, "avatar_url": null});
+        let discussions: Vec<GitLabDiscussion> = serde_json::from_value(serde_json::json!([
+            {"id": "d1", "notes": [
+                {"id": 1, "body": "Why?", "author": author(2, "bob"), "resolvable": true, "resolved": false,
+                 "position": {"new_path": "app.rb", "old_path": "app.rb", "new_line": 4, "old_line": null},
+                 "created_at": "2024-03-01T09:00:00Z", "updated_at": "2024-03-01T09:00:00Z"},
+                {"id": 2, "body": "Because", "author": author(1, "alice"), "resolvable": true, "resolved": false,
+                 "created_at": "2024-03-01T10:00:00Z", "updated_at": "2024-03-01T10:00:00Z"}
+            ]},
+            {"id": "d2", "notes": [
+                {"id": 3, "body": "added 1 commit", "author": author(1, "alice"), "system": true,
+                 "created_at": "2024-03-01T11:00:00Z", "updated_at": "2024-03-01T11:00:00Z"}
+            ]},
+            {"id": "d3", "notes": [
+                {"id": 4, "body": "LGTM", "author": author(3, "carol"),
+                 "created_at": "2024-03-01T12:00:00Z", "updated_at": "2024-03-01T12:00:00Z"}
+            ]}
+        ]))
+        .unwrap();
+
+        let comments = provider(GitConfig::gitlab("alice")).map_gitlab_review_comments(discussions);
+
+        assert_eq!(comments.len(), 3);
+        assert_eq!((comments[0].path.as_deref(), comments[0].line), (Some("app.rb"), Some(4)));
+        assert_eq!(comments[1].resolved, Some(false));
+        assert_eq!((comments[2].thread_id.as_str(), comments[2].resolved), ("d3", None));
+    }
+
+    #[test]
+    fn test_bitbucket_thread_root() {
+        let parents = HashMap::from([(11, 10), (12, 11), (20, 99)]);
+        assert_eq!(bitbucket_thread_root(12, &parents), 10);
+        assert_eq!(bitbucket_thread_root(10, &parents), 10);
+        // Parent not listed: the reply starts its own thread at the missing parent
+        assert_eq!(bitbucket_thread_root(20, &parents), 99);
+    }
+
+    #[test]
+    fn test_comments_query_escapes_repository() {
+        let query = build_comments_query("acme", "api\"x", 7);
+        assert!(query.contains("repository(owner: \"acme\", name: \"api\\\"x\")"));
+        assert!(query.contains("pullRequest(number: 7)"));
+        assert!(query.contains("reviewThreads(last: 100)"));
+    }
+}
@@ -0,0 +1,696 @@
+//! Composite Pull Request Repository
+//!
+//! Combines several git connections (e.g. GitHub and Bitbucket) into one
+//! PullRequestRepository. Every call fans out across the connections, tags each
+//! PR with the id of the connection it came from and merges the results into a
+//! single list, so services like the PR aggregator need not know how many hosts
+//! are configured. Lookups of a single PR go to the connection named in a
+//! qualified repository (`work:acme/api`), or to every connection otherwise.
+//! Likewise listings pass each connection only the repositories qualified
+//! with its id, alongside the unqualified ones.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use super::provider::fan_out;
+use crate::integrations::traits::{
+    ClosedPrFetchResult, ClosedPullRequest, IntegrationError, PrFetchResult, PrFilter, PullRequest,
+    PullRequestRepository, RepositoryError, ReviewComments,
+};
+
+/// Time budget for a whole connection, which may span many repositories
+const DEFAULT_CONNECTION_TIMEOUT_SECS: u64 = 120;
+
+/// Separates the connection from the repository in a qualified repository
+const CONNECTION_SEPARATOR: char = ':';
+
+/// Repository qualified with the connection a PR came from, as read by `find_by_id`
+pub fn qualified_repository(connection: &str, repo: &str) -> String {
+    format!("{}{}{}", connection, CONNECTION_SEPARATOR, repo)
+}
+
+/// A git connection taking part in the composite
+#[derive(Clone)]
+struct Connection {
+    id: String,
+    repo: Arc<dyn PullRequestRepository>,
+}
+
+/// PullRequestRepository spanning several git connections
+#[derive(Clone)]
+pub struct CompositePullRequestRepository {
+    connections: Vec<Connection>,
+    timeout_secs: u64,
+}
+
+impl CompositePullRequestRepository {
+    pub fn new() -> Self {
+        Self {
+            connections: Vec::new(),
+            timeout_secs: DEFAULT_CONNECTION_TIMEOUT_SECS,
+        }
+    }
+
+    /// Add a connection, replacing an earlier one with the same id
+    pub fn with_connection(mut self, id: &str, repo: Arc<dyn PullRequestRepository>) -> Self {
+        self.connections.retain(|c| c.id != id);
+        self.connections.push(Connection {
+            id: id.to_string(),
+            repo,
+        });
+        self
+    }
+
+    /// Time budget for fetching from a single connection, in seconds
+    pub fn with_timeout(mut self, secs: u64) -> Self {
+        self.timeout_secs = secs;
+        self
+    }
+
+    /// Ids of the connections, in the order results are merged
+    pub fn connection_ids(&self) -> Vec<String> {
+        self.connections.iter().map(|c| c.id.clone()).collect()
+    }
+
+    fn connection(&self, id: &str) -> Option<Arc<dyn PullRequestRepository>> {
+        self.connections.iter().find(|c| c.id == id).map(|c| c.repo.clone())
+    }
+
+    /// Connection named by a qualified repository, with the bare repository;
+    /// `None` for an unqualified one. Naming an unknown connection is NotFound.
+    fn route<'a>(&self, repo: &'a str) -> Result<Option<(&Connection, &'a str)>, IntegrationError> {
+        let Some((id, bare)) = repo.split_once(CONNECTION_SEPARATOR) else { return Ok(None) };
+        match self.connections.iter().find(|c| c.id == id) {
+            Some(connection) => Ok(Some((connection, bare))),
+            None => Err(IntegrationError::NotFound(format!("Unknown connection {} in {}", id, repo))),
+        }
+    }
+
+    /// The filter each connection is queried with. Qualified repositories go
+    /// to their own connection and unqualified ones to all of them; connections
+    /// left without a repository are not queried. No repositories means all.
+    fn filters_by_connection(&self, filter: &PrFilter) -> HashMap<String, PrFilter> {
+        if filter.repositories.is_empty() {
+            return self.connections.iter().map(|c| (c.id.clone(), filter.clone())).collect();
+        }
+
+        let mut repositories: HashMap<&str, Vec<String>> = HashMap::new();
+        for entry in &filter.repositories {
+            match self.route(entry) {
+                Ok(Some((connection, repo))) => {
+                    repositories.entry(connection.id.as_str()).or_default().push(repo.to_string());
+                }
+                Ok(None) => {
+                    for connection in &self.connections {
+                        repositories.entry(connection.id.as_str()).or_default().push(entry.clone());
+                    }
+                }
+                Err(e) => log::warn!("CompositePullRequestRepository: skipping repository: {}", e),
+            }
+        }
+
+        repositories
+            .into_iter()
+            .map(|(id, repos)| {
+                let mut filter = filter.clone();
+                filter.repositories = repos;
+                (id.to_string(), filter)
+            })
+            .collect()
+    }
+
+    /// First result found by any connection. Without one, a real failure is
+    /// reported in preference to a connection not knowing the PR.
+    fn first_found<T>(results: Vec<(String, Result<T, IntegrationError>)>, id: &str) -> Result<(String, T), IntegrationError> {
+        let mut not_found = None;
+        let mut failure = None;
+
+        for (connection, result) in results {
+            match result {
+                Ok(found) => return Ok((connection, found)),
+                Err(e @ IntegrationError::NotFound(_)) => {
+                    not_found.get_or_insert(e);
+                }
+                Err(e) => {
+                    log::warn!("CompositePullRequestRepository: {} failed to look up PR {}: {}", connection, id, e);
+                    failure.get_or_insert(e);
+                }
+            }
+        }
+
+        Err(failure
+            .or(not_found)
+            .unwrap_or_else(|| IntegrationError::NotFound(format!("PR {} not found", id))))
+    }
+
+    /// Ids of the connections having a filter, in the order results are merged
+    fn queried_connections(&self, filters: &HashMap<String, PrFilter>) -> Vec<String> {
+        self.connection_ids().into_iter().filter(|id| filters.contains_key(id)).collect()
+    }
+
+    /// Run `fetch` against the given connections concurrently, results in the order of `ids`
+    async fn fan_out_connections<T, F, Fut>(&self, ids: Vec<String>, fetch: F) -> Vec<(String, Result<T, IntegrationError>)>
+    where
+        T: Send + 'static,
+        F: Fn(&str, Arc<dyn PullRequestRepository>) -> Fut,
+        Fut: std::future::Future<Output = Result<T, IntegrationError>> + Send + 'static,
+    {
+        let results = fan_out(
+            &ids,
+            ids.len(),
+            std::time::Duration::from_secs(self.timeout_secs),
+            |id| {
+                let repo = self.connection(&id);
+                let future = repo.map(|repo| fetch(&id, repo));
+                async move {
+                    match future {
+                        Some(future) => future.await,
+                        None => Err(IntegrationError::ConfigError(format!("Unknown connection {}", id))),
+                    }
+                }
+            },
+        )
+        .await;
+
+        ids.into_iter().zip(results).collect()
+    }
+
+    /// Merge per-connection PRs, tagging each with its connection and dropping
+    /// PRs already seen through an earlier connection
+    fn merge(prs_by_connection: Vec<(String, Vec<PullRequest>)>, limit: usize) -> Vec<PullRequest> {
+        let mut seen = HashSet::new();
+        let mut merged: Vec<PullRequest> = prs_by_connection
+            .into_iter()
+            .flat_map(|(id, prs)| {
+                prs.into_iter().map(move |mut pr| {
+                    pr.connection.get_or_insert_with(|| id.clone());
+                    pr
+                })
+            })
+            .filter(|pr| seen.insert(Self::identity(pr)))
+            .collect();
+
+        merged.sort_by_key(|pr| std::cmp::Reverse(pr.updated_at));
+        merged.truncate(limit);
+        merged
+    }
+
+    /// Merge per-connection closed PRs like `merge`, most recently closed first
+    fn merge_closed(prs_by_connection: Vec<(String, Vec<ClosedPullRequest>)>, limit: usize) -> Vec<ClosedPullRequest> {
+        let mut seen = HashSet::new();
+        let mut merged: Vec<ClosedPullRequest> = prs_by_connection
+            .into_iter()
+            .flat_map(|(id, prs)| {
+                prs.into_iter().map(move |mut closed| {
+                    closed.pr.connection.get_or_insert_with(|| id.clone());
+                    closed
+                })
+            })
+            .filter(|closed| seen.insert(Self::identity(&closed.pr)))
+            .collect();
+
+        merged.sort_by_key(|closed| std::cmp::Reverse(closed.closed_at));
+        merged.truncate(limit);
+        merged
+    }
+
+    /// What makes two PRs the same: the web URL, which is unique across hosts
+    fn identity(pr: &PullRequest) -> String {
+        if pr.url.is_empty() {
+            format!("{}#{}", pr.repository, pr.id)
+        } else {
+            pr.url.trim_end_matches('/').to_string()
+        }
+    }
+}
+
+impl Default for CompositePullRequestRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl PullRequestRepository for CompositePullRequestRepository {
+    /// Look the PR up in the connection of a qualified repository, otherwise in all of them
+    async fn find_by_id(&self, repo: &str, id: &str) -> Result<PullRequest, IntegrationError> {
+        if let Some((connection, repo)) = self.route(repo)? {
+            let mut pr = connection.repo.find_by_id(repo, id).await?;
+            pr.connection.get_or_insert_with(|| connection.id.clone());
+            return Ok(pr);
+        }
+
+        let results = self
+            .fan_out_connections(self.connection_ids(), |_, connection| {
+                let (repo, id) = (repo.to_string(), id.to_string());
+                async move { connection.find_by_id(&repo, &id).await }
+            })
+            .await;
+
+        let (connection, mut pr) = Self::first_found(results, id)?;
+        pr.connection.get_or_insert(connection);
+        Ok(pr)
+    }
+
+    /// Comments from the connection of a qualified repository, otherwise from the one having the PR
+    async fn get_review_comments(&self, repo: &str, id: &str, limit: usize) -> Result<ReviewComments, IntegrationError> {
+        if let Some((connection, repo)) = self.route(repo)? {
+            return connection.repo.get_review_comments(repo, id, limit).await;
+        }
+
+        let results = self
+            .fan_out_connections(self.connection_ids(), |_, connection| {
+                let (repo, id) = (repo.to_string(), id.to_string());
+                async move { connection.get_review_comments(&repo, &id, limit).await }
+            })
+            .await;
+
+        Ok(Self::first_found(results, id)?.1)
+    }
+
+    async fn find_by_reviewer(&self, user_id: &str, filter: &PrFilter) -> Result<Vec<PullRequest>, IntegrationError> {
+        let filters = self.filters_by_connection(filter);
+        let results = self
+            .fan_out_connections(self.queried_connections(&filters), |id, repo| {
+                let user_id = user_id.to_string();
+                let filter = filters[id].clone();
+                async move { repo.find_by_reviewer(&user_id, &filter).await }
+            })
+            .await;
+
+        let mut found = Vec::new();
+        let mut first_error = None;
+        for (id, result) in results {
+            match result {
+                Ok(prs) => found.push((id, prs)),
+                Err(e) => {
+                    log::warn!("CompositePullRequestRepository: {} failed: {}", id, e);
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        if found.is_empty() {
+            if let Some(e) = first_error {
+                return Err(e);
+            }
+        }
+        Ok(Self::merge(found, filter.limit))
+    }
+
+    async fn get_open_prs(&self, filter: &PrFilter) -> Result<Vec<PullRequest>, IntegrationError> {
+        Ok(self.fetch_open_prs(filter).await?.prs)
+    }
+
+    /// Open PRs of all connections. A failing connection is reported as a
+    /// failed repository named after the connection; if every connection
+    /// fails, the first error is returned instead.
+    async fn fetch_open_prs(&self, filter: &PrFilter) -> Result<PrFetchResult, IntegrationError> {
+        let filters = self.filters_by_connection(filter);
+        let results = self
+            .fan_out_connections(self.queried_connections(&filters), |id, repo| {
+                let filter = filters[id].clone();
+                async move { repo.fetch_open_prs(&filter).await }
+            })
+            .await;
+
+        let mut found = Vec::new();
+        let mut errors = Vec::new();
+        let mut first_error = None;
+
+        for (id, result) in results {
+            match result {
+                Ok(fetched) => {
+                    errors.extend(fetched.errors);
+                    found.push((id, fetched.prs));
+                }
+                Err(e) => {
+                    log::warn!("CompositePullRequestRepository: {} failed: {}", id, e);
+                    errors.push(RepositoryError::new(&id, &e));
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        if found.is_empty() {
+            if let Some(e) = first_error {
+                return Err(e);
+            }
+        }
+
+        Ok(PrFetchResult {
+            prs: Self::merge(found, filter.limit),
+            errors,
+        })
+    }
+
+    /// Closed PRs of all connections, with failures reported like `fetch_open_prs`
+    async fn get_closed_prs(
+        &self,
+        since: DateTime<Utc>,
+        filter: &PrFilter,
+    ) -> Result<ClosedPrFetchResult, IntegrationError> {
+        let filters = self.filters_by_connection(filter);
+        let results = self
+            .fan_out_connections(self.queried_connections(&filters), |id, repo| {
+                let filter = filters[id].clone();
+                async move { repo.get_closed_prs(since, &filter).await }
+            })
+            .await;
+
+        let mut found = Vec::new();
+        let mut errors = Vec::new();
+        let mut first_error = None;
+
+        for (id, result) in results {
+            match result {
+                Ok(fetched) => {
+                    errors.extend(fetched.errors);
+                    found.push((id, fetched.prs));
+                }
+                Err(e) => {
+                    log::warn!("CompositePullRequestRepository: {} failed: {}", id, e);
+                    errors.push(RepositoryError::new(&id, &e));
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        if found.is_empty() {
+            if let Some(e) = first_error {
+                return Err(e);
+            }
+        }
+
+        Ok(ClosedPrFetchResult {
+            prs: Self::merge_closed(found, filter.limit),
+            errors,
+        })
+    }
+}
+
+// Debug implementation
+impl std::fmt::Debug for CompositePullRequestRepository {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CompositePullRequestRepository")
+            .field("connections", &self.connection_ids())
+            .field("timeout_secs", &self.timeout_secs)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::traits::{ChecksStatus, PrState, ReviewState, Reviewer, User};
+    use chrono::Duration;
+
+    struct StaticRepo {
+        prs: Vec<PullRequest>,
+        fail: bool,
+    }
+
+    /// Repository recording the repositories it was asked for
+    #[derive(Default)]
+    struct RecordingRepo {
+        asked: std::sync::Mutex<Vec<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl PullRequestRepository for RecordingRepo {
+        async fn find_by_id(&self, _repo: &str, id: &str) -> Result<PullRequest, IntegrationError> {
+            Err(IntegrationError::NotFound(format!("PR {} not found", id)))
+        }
+
+        async fn find_by_reviewer(&self, _user_id: &str, filter: &PrFilter) -> Result<Vec<PullRequest>, IntegrationError> {
+            self.get_open_prs(filter).await
+        }
+
+        async fn get_open_prs(&self, filter: &PrFilter) -> Result<Vec<PullRequest>, IntegrationError> {
+            self.asked.lock().unwrap().push(filter.repositories.clone());
+            Ok(vec![])
+        }
+    }
+
+    #[async_trait]
+    impl PullRequestRepository for StaticRepo {
+        async fn find_by_id(&self, repo: &str, id: &str) -> Result<PullRequest, IntegrationError> {
+            if self.fail {
+                return Err(IntegrationError::Auth("Invalid credentials".to_string()));
+            }
+            self.prs
+                .iter()
+                .find(|pr| pr.repository == repo && pr.id == id)
+                .cloned()
+                .ok_or_else(|| IntegrationError::NotFound(format!("PR {} not found", id)))
+        }
+
+        async fn find_by_reviewer(&self, user_id: &str, filter: &PrFilter) -> Result<Vec<PullRequest>, IntegrationError> {
+            Ok(self
+                .get_open_prs(filter)
+                .await?
+                .into_iter()
+                .filter(|pr| pr.reviewers.iter().any(|r| r.is_user(user_id)))
+                .collect())
+        }
+
+        async fn get_open_prs(&self, _filter: &PrFilter) -> Result<Vec<PullRequest>, IntegrationError> {
+            if self.fail {
+                return Err(IntegrationError::Auth("Invalid credentials".to_string()));
+            }
+            Ok(self.prs.clone())
+        }
+
+        /// Every PR as merged at its last update
+        async fn get_closed_prs(
+            &self,
+            since: DateTime<Utc>,
+            _filter: &PrFilter,
+        ) -> Result<ClosedPrFetchResult, IntegrationError> {
+            if self.fail {
+                return Err(IntegrationError::Auth("Invalid credentials".to_string()));
+            }
+            let prs = self
+                .prs
+                .iter()
+                .filter(|pr| pr.updated_at >= since)
+                .map(|pr| ClosedPullRequest {
+                    pr: PullRequest { state: PrState::Merged, ..pr.clone() },
+                    closed_at: pr.updated_at,
+                    merged_at: Some(pr.updated_at),
+                    merged_by: None,
+                    first_review_at: None,
+                })
+                .collect();
+            Ok(ClosedPrFetchResult { prs, errors: vec![] })
+        }
+    }
+
+    fn pr(id: &str, repo: &str, url: &str, age_hours: i64) -> PullRequest {
+        PullRequest {
+            id: id.to_string(),
+            repository: repo.to_string(),
+            title: format!("PR {}", id),
+            description: None,
+            state: PrState::Open,
+            author: User {
+                id: "author".to_string(),
+                name: "author".to_string(),
+                email: None,
+                avatar_url: None,
+            },
+            reviewers: vec![],
+            source_branch: "feature".to_string(),
+            target_branch: "main".to_string(),
+            checks_status: ChecksStatus::None,
+            is_stale: false,
+            updated_at: Utc::now() - Duration::hours(age_hours),
+            created_at: Utc::now() - Duration::hours(age_hours + 1),
+            url: url.to_string(),
+            labels: vec![],
+            diff_stats: None,
+            connection: None,
+        }
+    }
+
+    fn repo(prs: Vec<PullRequest>) -> Arc<dyn PullRequestRepository> {
+        Arc::new(StaticRepo { prs, fail: false })
+    }
+
+    fn failing() -> Arc<dyn PullRequestRepository> {
+        Arc::new(StaticRepo { prs: vec![], fail: true })
+    }
+
+    #[tokio::test]
+    async fn test_merges_and_tags_connections() {
+        let composite = CompositePullRequestRepository::new()
+            .with_connection("github", repo(vec![pr("1", "acme/api", "https://github.com/acme/api/pull/1", 5)]))
+            .with_connection("bitbucket", repo(vec![pr("7", "web", "https://bitbucket.org/acme/web/pull-requests/7", 1)]));
+
+        let prs = composite.get_open_prs(&PrFilter::new()).await.unwrap();
+
+        assert_eq!(prs.len(), 2);
+        // Most recently updated first, regardless of connection
+        assert_eq!(prs[0].id, "7");
+        assert_eq!(prs[0].connection.as_deref(), Some("bitbucket"));
+        assert_eq!(prs[1].connection.as_deref(), Some("github"));
+    }
+
+    #[tokio::test]
+    async fn test_deduplicates_prs_seen_twice() {
+        let shared = pr("1", "acme/api", "https://github.com/acme/api/pull/1", 5);
+        let composite = CompositePullRequestRepository::new()
+            .with_connection("work", repo(vec![shared.clone()]))
+            .with_connection("personal", repo(vec![shared, pr("2", "me/dotfiles", "https://github.com/me/dotfiles/pull/2", 3)]));
+
+        let prs = composite.get_open_prs(&PrFilter::new()).await.unwrap();
+
+        assert_eq!(prs.len(), 2);
+        let api = prs.iter().find(|pr| pr.id == "1").unwrap();
+        assert_eq!(api.connection.as_deref(), Some("work"));
+    }
+
+    #[tokio::test]
+    async fn test_failed_connection_is_reported() {
+        let composite = CompositePullRequestRepository::new()
+            .with_connection("github", repo(vec![pr("1", "acme/api", "https://github.com/acme/api/pull/1", 5)]))
+            .with_connection("gitlab", failing());
+
+        let fetched = composite.fetch_open_prs(&PrFilter::new()).await.unwrap();
+
+        assert_eq!(fetched.prs.len(), 1);
+        assert_eq!(fetched.errors.len(), 1);
+        assert_eq!(fetched.errors[0].repository, "gitlab");
+
+        let all_failing = CompositePullRequestRepository::new().with_connection("gitlab", failing());
+        assert!(matches!(
+            all_failing.fetch_open_prs(&PrFilter::new()).await,
+            Err(IntegrationError::Auth(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_closed_prs_across_connections() {
+        let composite = CompositePullRequestRepository::new()
+            .with_connection("github", repo(vec![
+                pr("1", "acme/api", "https://github.com/acme/api/pull/1", 30),
+                pr("2", "acme/api", "https://github.com/acme/api/pull/2", 5),
+            ]))
+            .with_connection("bitbucket", repo(vec![pr("7", "web", "https://bitbucket.org/acme/web/pull-requests/7", 1)]))
+            .with_connection("gitlab", failing());
+
+        let since = Utc::now() - Duration::hours(24);
+        let fetched = composite.get_closed_prs(since, &PrFilter::new()).await.unwrap();
+
+        // Most recently closed first, PRs closed before `since` left out
+        let ids: Vec<&str> = fetched.prs.iter().map(|c| c.pr.id.as_str()).collect();
+        assert_eq!(ids, vec!["7", "2"]);
+        assert_eq!(fetched.prs[0].pr.connection.as_deref(), Some("bitbucket"));
+        assert_eq!(fetched.errors.len(), 1);
+        assert_eq!(fetched.errors[0].repository, "gitlab");
+    }
+
+    #[tokio::test]
+    async fn test_find_by_reviewer_across_connections() {
+        let mut reviewed = pr("3", "web", "https://bitbucket.org/acme/web/pull-requests/3", 2);
+        reviewed.reviewers = vec![Reviewer::new(
+            User { id: "jane".to_string(), name: "jane".to_string(), email: None, avatar_url: None },
+            ReviewState::Pending,
+        )];
+        let composite = CompositePullRequestRepository::new()
+            .with_connection("github", repo(vec![pr("1", "acme/api", "https://github.com/acme/api/pull/1", 5)]))
+            .with_connection("bitbucket", repo(vec![reviewed]));
+
+        let prs = composite.find_by_reviewer("jane", &PrFilter::new()).await.unwrap();
+
+        assert_eq!(prs.len(), 1);
+        assert_eq!(prs[0].connection.as_deref(), Some("bitbucket"));
+    }
+
+    #[tokio::test]
+    async fn test_find_by_id_tries_each_connection() {
+        let composite = CompositePullRequestRepository::new()
+            .with_connection("github", repo(vec![]))
+            .with_connection("bitbucket", repo(vec![pr("7", "web", "https://bitbucket.org/acme/web/pull-requests/7", 1)]));
+
+        let found = composite.find_by_id("web", "7").await.unwrap();
+        assert_eq!(found.connection.as_deref(), Some("bitbucket"));
+
+        assert!(matches!(composite.find_by_id("web", "8").await, Err(IntegrationError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_find_by_id_routes_qualified_repository() {
+        let composite = CompositePullRequestRepository::new()
+            .with_connection("github", failing())
+            .with_connection("bitbucket", repo(vec![pr("7", "web", "https://bitbucket.org/acme/web/pull-requests/7", 1)]));
+
+        let found = composite.find_by_id(&qualified_repository("bitbucket", "web"), "7").await.unwrap();
+        assert_eq!(found.repository, "web");
+        assert_eq!(found.connection.as_deref(), Some("bitbucket"));
+
+        let routed = composite.find_by_id(&qualified_repository("github", "web"), "7").await;
+        assert!(matches!(routed, Err(IntegrationError::Auth(_))));
+    }
+
+    #[tokio::test]
+    async fn test_find_by_id_prefers_failure_over_not_found() {
+        let composite = CompositePullRequestRepository::new()
+            .with_connection("github", repo(vec![]))
+            .with_connection("bitbucket", failing());
+
+        assert!(matches!(composite.find_by_id("web", "7").await, Err(IntegrationError::Auth(_))));
+    }
+
+    #[tokio::test]
+    async fn test_listings_pass_each_connection_its_repositories() {
+        let github = Arc::new(RecordingRepo::default());
+        let bitbucket = Arc::new(RecordingRepo::default());
+        let gitlab = Arc::new(RecordingRepo::default());
+        let composite = CompositePullRequestRepository::new()
+            .with_connection("github", github.clone())
+            .with_connection("bitbucket", bitbucket.clone())
+            .with_connection("gitlab", gitlab.clone());
+
+        let filter = PrFilter::new().with_repositories(vec![
+            qualified_repository("github", "acme/api"),
+            qualified_repository("bitbucket", "web"),
+            qualified_repository("missing", "acme/ops"),
+            "shared".to_string(),
+        ]);
+        let fetched = composite.fetch_open_prs(&filter).await.unwrap();
+        assert!(fetched.errors.is_empty());
+
+        assert_eq!(*github.asked.lock().unwrap(), vec![vec!["acme/api".to_string(), "shared".to_string()]]);
+        assert_eq!(*bitbucket.asked.lock().unwrap(), vec![vec!["web".to_string(), "shared".to_string()]]);
+        assert_eq!(*gitlab.asked.lock().unwrap(), vec![vec!["shared".to_string()]]);
+
+        // A connection the filter names none of is not queried
+        let filter = PrFilter::new().with_repositories(vec![qualified_repository("github", "acme/api")]);
+        composite.find_by_reviewer("jane", &filter).await.unwrap();
+        assert_eq!(github.asked.lock().unwrap().len(), 2);
+        assert_eq!(bitbucket.asked.lock().unwrap().len(), 1);
+        assert_eq!(gitlab.asked.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_find_by_id_rejects_unknown_connection() {
+        let composite = CompositePullRequestRepository::new()
+            .with_connection("bitbucket", repo(vec![pr("7", "missing:web", "https://bitbucket.org/acme/web/pull-requests/7", 1)]));
+
+        let found = composite.find_by_id(&qualified_repository("missing", "web"), "7").await;
+        assert!(matches!(found, Err(IntegrationError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_with_connection_replaces_same_id() {
+        let composite = CompositePullRequestRepository::new()
+            .with_connection("github", repo(vec![]))
+            .with_connection("gitlab", repo(vec![]))
+            .with_connection("github", failing());
+
+        assert_eq!(composite.connection_ids(), vec!["gitlab".to_string(), "github".to_string()]);
+    }
+}
@@ -0,0 +1,446 @@
+//! Pull Request Diff Stats
+//!
+//! Fetches the files a single PR changes, with lines added and removed per
+//! file, so PRs can be sized. Bitbucket reports a diffstat and GitHub a file
+//! list; GitLab only returns the diffs themselves, whose lines are counted.
+//!
+//! Totals come from the provider's own counts where it reports them. Without
+//! them they are summed from the file list, but only when the list is complete.
+//! Listings fetch only the totals: GitHub's single PR, which the HTTP cache
+//! mostly answers with a 304, Bitbucket's diffstat lines and GitLab's diffs.
+
+use serde::Deserialize;
+
+use super::provider::{parse_next_link, GitHubPr, GitProvider, GitProviderType};
+use crate::integrations::http_cache::HttpResponse;
+use crate::integrations::traits::{ChangedFile, DiffStats, IntegrationError, PullRequest};
+
+/// Upper bound of files listed per PR, which bounds the requests spent on huge PRs
+const MAX_DIFF_FILES: usize = 300;
+
+/// Files fetched for a PR; incomplete when cut short or when some diffs were omitted
+struct FileList {
+    files: Vec<ChangedFile>,
+    complete: bool,
+}
+
+/// Diff stats from the provider's totals and the fetched files.
+///
+/// A partial list would undercount the PR, so without totals it only sizes
+/// the PR when it is complete and matches the file count the provider reported.
+fn combine_diff_stats(totals: Option<DiffStats>, list: FileList, reported_files: Option<u64>) -> Option<DiffStats> {
+    match totals {
+        Some(totals) => Some(DiffStats { files: list.files, ..totals }),
+        None if list.complete && reported_files.is_none_or(|n| n == list.files.len() as u64) => {
+            Some(DiffStats::from_files(list.files))
+        }
+        None => None,
+    }
+}
+
+/// File list of GitLab diffs; `too_large` and collapsed diffs carry no lines
+fn gitlab_file_list(diffs: Vec<GitLabDiff>, more_pages: bool) -> FileList {
+    let complete = !more_pages
+        && diffs.len() <= MAX_DIFF_FILES
+        && diffs.iter().all(|diff| !diff.too_large && !diff.collapsed);
+
+    let files = diffs
+        .into_iter()
+        .take(MAX_DIFF_FILES)
+        .map(|diff| {
+            let (additions, deletions) = count_diff_lines(&diff.diff);
+            ChangedFile {
+                path: if diff.deleted_file { diff.old_path } else { diff.new_path },
+                additions,
+                deletions,
+            }
+        })
+        .collect();
+
+    FileList { files, complete }
+}
+
+/// Add the lines and files of a diffstat page to `totals`
+fn add_diffstat_page(totals: &mut DiffStats, page: &BitbucketDiffstatPage) {
+    for entry in &page.values {
+        totals.additions += entry.lines_added;
+        totals.deletions += entry.lines_removed;
+        totals.changed_files += 1;
+    }
+}
+
+/// Lines added and removed by a GitLab diff, which starts at the first hunk
+/// without `---`/`+++` file headers
+fn count_diff_lines(diff: &str) -> (u64, u64) {
+    diff.lines().fold((0, 0), |(added, removed), line| match line.as_bytes().first() {
+        Some(b'+') => (added + 1, removed),
+        Some(b'-') => (added, removed + 1),
+        _ => (added, removed),
+    })
+}
+
+impl GitProvider {
+    /// Attach the changed files to a PR, keeping totals it already carries.
+    /// `reported_files` is the provider's file count when it has no line totals.
+    /// Left as is when the files are missing.
+    pub(super) async fn attach_diff_stats(
+        &self,
+        pr: &mut PullRequest,
+        reported_files: Option<u64>,
+    ) -> Result<(), IntegrationError> {
+        let list = match self.config().provider {
+            GitProviderType::Bitbucket => self.fetch_bitbucket_diffstat(&pr.repository, &pr.id).await?,
+            GitProviderType::GitHub => self.fetch_github_files(&pr.repository, &pr.id).await?,
+            GitProviderType::GitLab => self.fetch_gitlab_diffs(&pr.repository, &pr.id).await?,
+        };
+        let Some(list) = list else { return Ok(()) };
+
+        pr.diff_stats = combine_diff_stats(pr.diff_stats.take(), list, reported_files);
+        Ok(())
+    }
+
+    /// Line and file totals of a PR without its file list, for listings
+    pub(super) async fn fetch_diff_totals(&self, repo: &str, id: &str) -> Result<Option<DiffStats>, IntegrationError> {
+        match self.config().provider {
+            GitProviderType::GitHub => {
+                // Only the single pull request endpoint reports the totals
+                let url = format!("{}/repos/{}/pulls/{}", self.config().api_base_url(), repo, id);
+                Ok(self.get_optional::<GitHubPr>(&url).await?.and_then(|pr| pr.diff_stats()))
+            }
+            GitProviderType::Bitbucket => self.fetch_bitbucket_totals(repo, id).await,
+            GitProviderType::GitLab => Ok(self
+                .fetch_gitlab_diffs(repo, id)
+                .await?
+                .and_then(|list| combine_diff_stats(None, list, None))
+                .map(|stats| DiffStats { files: Vec::new(), ..stats })),
+        }
+    }
+
+    /// Totals of every diffstat page, which unlike the file list are not cut short
+    async fn fetch_bitbucket_totals(&self, repo: &str, id: &str) -> Result<Option<DiffStats>, IntegrationError> {
+        let Some(workspace) = self.config().workspace.as_deref() else { return Ok(None) };
+        let mut totals = DiffStats::default();
+        let mut next_url = Some(format!(
+            "{}/repositories/{}/{}/pullrequests/{}/diffstat?pagelen=500&fields=next,values.lines_added,values.lines_removed",
+            self.config().api_base_url(), workspace, repo, id
+        ));
+
+        while let Some(url) = next_url.take() {
+            let Some(response) = self.get_diff_page(&url).await? else { return Ok(None) };
+            let Ok(page) = response.json::<BitbucketDiffstatPage>() else { return Ok(None) };
+            add_diffstat_page(&mut totals, &page);
+            next_url = page.next;
+        }
+
+        Ok(Some(totals))
+    }
+
+    /// GET a page of a diff resource, `None` when the resource does not exist
+    async fn get_diff_page(&self, url: &str) -> Result<Option<HttpResponse>, IntegrationError> {
+        let response = self.get(url).await?;
+        match response.status {
+            200 => Ok(Some(response)),
+            404 => Ok(None),
+            status => Err(self.enrichment_error(status, url)),
+        }
+    }
+
+    async fn fetch_github_files(&self, repo: &str, number: &str) -> Result<Option<FileList>, IntegrationError> {
+        let mut files = Vec::new();
+        let mut next_url = Some(format!(
+            "{}/repos/{}/pulls/{}/files?per_page=100",
+            self.config().api_base_url(), repo, number
+        ));
+
+        while let Some(url) = next_url.take() {
+            let Some(response) = self.get_diff_page(&url).await? else { return Ok(None) };
+            let Ok(page) = response.json::<Vec<GitHubPrFile>>() else { return Ok(None) };
+            files.extend(page.into_iter().map(|f| ChangedFile {
+                path: f.filename,
+                additions: f.additions,
+                deletions: f.deletions,
+            }));
+
+            next_url = response.header("link").and_then(parse_next_link);
+            if files.len() >= MAX_DIFF_FILES {
+                break;
+            }
+        }
+
+        let complete = next_url.is_none() && files.len() <= MAX_DIFF_FILES;
+        files.truncate(MAX_DIFF_FILES);
+        Ok(Some(FileList { files, complete }))
+    }
+
+    async fn fetch_bitbucket_diffstat(&self, repo: &str, id: &str) -> Result<Option<FileList>, IntegrationError> {
+        let Some(workspace) = self.config().workspace.as_deref() else { return Ok(None) };
+        let mut files = Vec::new();
+        let mut next_url = Some(format!(
+            "{}/repositories/{}/{}/pullrequests/{}/diffstat?pagelen=100",
+            self.config().api_base_url(), workspace, repo, id
+        ));
+
+        while let Some(url) = next_url.take() {
+            let Some(response) = self.get_diff_page(&url).await? else { return Ok(None) };
+            let Ok(page) = response.json::<BitbucketDiffstatPage>() else { return Ok(None) };
+            files.extend(page.values.into_iter().filter_map(|entry| {
+                // Deleted files only have the old side
+                let path = entry.new.or(entry.old)?.path;
+                Some(ChangedFile {
+                    path,
+                    additions: entry.lines_added,
+                    deletions: entry.lines_removed,
+                })
+            }));
+
+            next_url = page.next;
+            if files.len() >= MAX_DIFF_FILES {
+                break;
+            }
+        }
+
+        let complete = next_url.is_none() && files.len() <= MAX_DIFF_FILES;
+        files.truncate(MAX_DIFF_FILES);
+        Ok(Some(FileList { files, complete }))
+    }
+
+    /// Diffs of a merge request, through `/diffs` (GitLab 15.7+) or the older `/changes`
+    async fn fetch_gitlab_diffs(&self, repo: &str, iid: &str) -> Result<Option<FileList>, IntegrationError> {
+        let base = format!(
+            "{}/projects/{}/merge_requests/{}",
+            self.config().api_base_url(),
+            urlencoding::encode(&self.config().gitlab_project_path(repo)),
+            iid
+        );
+
+        if let Some(list) = self.fetch_gitlab_diff_pages(&base).await? {
+            return Ok(Some(list));
+        }
+
+        let Some(changes) = self.get_optional::<GitLabChanges>(&format!("{}/changes", base)).await? else {
+            return Ok(None);
+        };
+        Ok(Some(gitlab_file_list(changes.changes, changes.overflow)))
+    }
+
+    /// Pages of `/diffs`, `None` on GitLab versions without it
+    async fn fetch_gitlab_diff_pages(&self, base: &str) -> Result<Option<FileList>, IntegrationError> {
+        let mut diffs = Vec::new();
+        let mut page = Some(1);
+
+        while let Some(current) = page {
+            let url = format!("{}/diffs?per_page=100&page={}", base, current);
+            let Some(response) = self.get_diff_page(&url).await? else { return Ok(None) };
+
+            page = response
+                .header("x-next-page")
+                .and_then(|v| v.trim().parse::<usize>().ok());
+            let Ok(batch) = response.json::<Vec<GitLabDiff>>() else { return Ok(None) };
+            diffs.extend(batch);
+
+            if diffs.len() >= MAX_DIFF_FILES {
+                break;
+            }
+        }
+
+        Ok(Some(gitlab_file_list(diffs, page.is_some())))
+    }
+}
+
+// ===== Diff API Types =====
+
+#[derive(Debug, Deserialize)]
+struct GitHubPrFile {
+    filename: String,
+    #[serde(default)]
+    additions: u64,
+    #[serde(default)]
+    deletions: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketDiffstatPage {
+    values: Vec<BitbucketDiffstat>,
+    next: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketDiffstat {
+    #[serde(default)]
+    lines_added: u64,
+    #[serde(default)]
+    lines_removed: u64,
+    old: Option<BitbucketDiffPath>,
+    new: Option<BitbucketDiffPath>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketDiffPath {
+    path: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabChanges {
+    #[serde(default)]
+    changes: Vec<GitLabDiff>,
+    /// Set when GitLab left out changes beyond its size limits
+    #[serde(default)]
+    overflow: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabDiff {
+    old_path: String,
+    new_path: String,
+    #[serde(default)]
+    diff: String,
+    #[serde(default)]
+    deleted_file: bool,
+    #[serde(default)]
+    too_large: bool,
+    #[serde(default)]
+    collapsed: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::git::GitConfig;
+    use crate::integrations::traits::{ChecksStatus, PrFilter};
+    use crate::services::PrAggregator;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_count_diff_lines() {
+        let diff = "@@ -1,4 +1,4 @@\n fn main() {\n-    old();\n+    new();\n+    more();\n--- SQL comment\n }\n";
+        assert_eq!(count_diff_lines(diff), (2, 2));
+        assert_eq!(count_diff_lines(""), (0, 0));
+    }
+
+    fn file(path: &str, additions: u64, deletions: u64) -> ChangedFile {
+        ChangedFile { path: path.to_string(), additions, deletions }
+    }
+
+    #[test]
+    fn test_combine_diff_stats_prefers_provider_totals() {
+        let totals = DiffStats { additions: 5000, deletions: 200, changed_files: 420, files: vec![] };
+        let list = FileList { files: vec![file("a.rs", 3, 1)], complete: false };
+
+        let stats = combine_diff_stats(Some(totals), list, None).unwrap();
+        assert_eq!(stats.lines_changed(), 5200);
+        assert_eq!(stats.changed_files, 420);
+        assert_eq!(stats.files.len(), 1);
+    }
+
+    #[test]
+    fn test_combine_diff_stats_without_totals() {
+        let complete = || FileList { files: vec![file("a.rs", 3, 1), file("b.rs", 2, 0)], complete: true };
+
+        assert_eq!(combine_diff_stats(None, complete(), None).map(|s| s.lines_changed()), Some(6));
+        assert_eq!(combine_diff_stats(None, complete(), Some(2)).map(|s| s.changed_files), Some(2));
+        // The provider counts more files than were listed
+        assert_eq!(combine_diff_stats(None, complete(), Some(3)), None);
+
+        let partial = FileList { files: vec![file("a.rs", 3, 1)], complete: false };
+        assert_eq!(combine_diff_stats(None, partial, None), None);
+    }
+
+    #[test]
+    fn test_gitlab_file_list_incomplete_with_too_large_diff() {
+        let diffs: Vec<GitLabDiff> = serde_json::from_value(serde_json::json!([
+            {"old_path": "a.rs", "new_path": "a.rs", "diff": "@@ -1 +1 @@\n-a\n+b\n"},
+            {"old_path": "dump.sql", "new_path": "dump.sql", "diff": "", "too_large": true}
+        ]))
+        .unwrap();
+
+        let list = gitlab_file_list(diffs, false);
+        assert!(!list.complete);
+        assert_eq!(list.files.len(), 2);
+        assert_eq!((list.files[0].additions, list.files[0].deletions), (1, 1));
+    }
+
+    #[test]
+    fn test_add_diffstat_page() {
+        let page: BitbucketDiffstatPage = serde_json::from_value(serde_json::json!({
+            "values": [{"lines_added": 400, "lines_removed": 20}, {"lines_added": 0, "lines_removed": 3}],
+            "next": "https://api.bitbucket.org/2.0/repositories/acme/web/pullrequests/7/diffstat?page=2"
+        }))
+        .unwrap();
+
+        let mut totals = DiffStats::default();
+        add_diffstat_page(&mut totals, &page);
+        add_diffstat_page(&mut totals, &page);
+
+        assert_eq!((totals.additions, totals.deletions, totals.changed_files), (800, 46, 4));
+        assert!(totals.files.is_empty());
+    }
+
+    /// PRs as listed, for the aggregator
+    struct ListedPrs(Vec<PullRequest>);
+
+    #[async_trait::async_trait]
+    impl crate::integrations::traits::PullRequestRepository for ListedPrs {
+        async fn find_by_id(&self, _repo: &str, id: &str) -> Result<PullRequest, IntegrationError> {
+            Err(IntegrationError::NotFound(id.to_string()))
+        }
+
+        async fn find_by_reviewer(&self, _user_id: &str, _filter: &PrFilter) -> Result<Vec<PullRequest>, IntegrationError> {
+            Ok(vec![])
+        }
+
+        async fn get_open_prs(&self, _filter: &PrFilter) -> Result<Vec<PullRequest>, IntegrationError> {
+            Ok(self.0.clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_listed_prs_are_sized() {
+        let provider = GitProvider::new(GitConfig::github("user").with_token("t")).unwrap();
+        let mut json = serde_json::json!({
+            "number": 7,
+            "title": "Rewrite billing",
+            "body": null,
+            "user": {"id": 1, "login": "alice", "avatar_url": "https://avatars.example.com/u"},
+            "head": {"ref": "feature/billing", "sha": "abc123"},
+            "base": {"ref": "main", "sha": "def456"},
+            "created_at": "2024-01-01T00:00:00Z",
+            "updated_at": "2024-01-02T00:00:00Z",
+            "html_url": "https://github.com/acme/api/pull/7"
+        });
+        let listed: GitHubPr = serde_json::from_value(json.clone()).unwrap();
+
+        // What `fetch_diff_totals` reads from `pulls/7`
+        json["additions"] = serde_json::json!(820);
+        json["deletions"] = serde_json::json!(140);
+        json["changed_files"] = serde_json::json!(31);
+        let single: GitHubPr = serde_json::from_value(json).unwrap();
+
+        let mut pr = provider.map_github_pr(&listed, "acme/api", &[], ChecksStatus::None);
+        assert_eq!(pr.diff_stats, None);
+        pr.diff_stats = single.diff_stats();
+
+        let aggregator = PrAggregator::new(Arc::new(ListedPrs(vec![pr])), Default::default());
+        assert_eq!(aggregator.get_summary().await.unwrap().large_count, 1);
+    }
+
+    #[test]
+    fn test_bitbucket_diffstat_paths() {
+        let page: BitbucketDiffstatPage = serde_json::from_value(serde_json::json!({
+            "values": [
+                {"status": "modified", "lines_added": 4, "lines_removed": 1,
+                 "old": {"path": "src/a.rs"}, "new": {"path": "src/a.rs"}},
+                {"status": "removed", "lines_added": 0, "lines_removed": 30,
+                 "old": {"path": "src/gone.rs"}, "new": null}
+            ],
+            "next": null
+        }))
+        .unwrap();
+
+        let paths: Vec<String> = page
+            .values
+            .into_iter()
+            .filter_map(|entry| entry.new.or(entry.old).map(|p| p.path))
+            .collect();
+        assert_eq!(paths, vec!["src/a.rs", "src/gone.rs"]);
+    }
+}
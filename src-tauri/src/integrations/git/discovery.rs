@@ -0,0 +1,502 @@
+//! Repository Discovery
+//!
+//! Lists the repositories of a GitHub org or user, a Bitbucket workspace or
+//! project, or a GitLab group, so new services show up without being added to
+//! `GitConfig.repositories` by hand. The selection is narrowed with glob
+//! patterns and an archived filter, and cached until the refresh interval passes.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::provider::{parse_next_link, GitProvider, GitProviderType};
+use crate::integrations::traits::IntegrationError;
+
+/// Largest page the repository listings return
+const REPO_PAGE_SIZE: usize = 100;
+
+/// Repository discovery settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepoDiscovery {
+    /// GitHub org or user, Bitbucket workspace or GitLab group; defaults to the workspace
+    #[serde(default)]
+    pub owner: Option<String>,
+    /// Bitbucket project key to restrict the workspace to
+    #[serde(default)]
+    pub project: Option<String>,
+    /// Glob patterns of repositories to select; empty selects all
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// Glob patterns of repositories to leave out, applied after `include`
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    #[serde(default)]
+    pub include_archived: bool,
+    /// How long a discovered list is reused, in seconds
+    #[serde(default = "default_refresh_interval_secs")]
+    pub refresh_interval_secs: u64,
+}
+
+fn default_refresh_interval_secs() -> u64 {
+    3600
+}
+
+impl Default for RepoDiscovery {
+    fn default() -> Self {
+        Self {
+            owner: None,
+            project: None,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            include_archived: false,
+            refresh_interval_secs: default_refresh_interval_secs(),
+        }
+    }
+}
+
+impl RepoDiscovery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_owner(mut self, owner: &str) -> Self {
+        self.owner = Some(owner.to_string());
+        self
+    }
+
+    pub fn with_project(mut self, project: &str) -> Self {
+        self.project = Some(project.to_string());
+        self
+    }
+
+    pub fn with_include(mut self, pattern: &str) -> Self {
+        self.include.push(pattern.to_string());
+        self
+    }
+
+    pub fn with_exclude(mut self, pattern: &str) -> Self {
+        self.exclude.push(pattern.to_string());
+        self
+    }
+
+    pub fn include_archived(mut self) -> Self {
+        self.include_archived = true;
+        self
+    }
+
+    pub fn with_refresh_interval(mut self, secs: u64) -> Self {
+        self.refresh_interval_secs = secs;
+        self
+    }
+
+    /// Whether a discovered repository is selected.
+    ///
+    /// Patterns match either the full name (`acme/payments-api`) or its last
+    /// segment (`payments-api`).
+    pub fn selects(&self, name: &str, archived: bool) -> bool {
+        if archived && !self.include_archived {
+            return false;
+        }
+
+        let short = name.rsplit('/').next().unwrap_or(name);
+        let matches = |pattern: &String| glob_match(pattern, name) || glob_match(pattern, short);
+
+        (self.include.is_empty() || self.include.iter().any(matches)) && !self.exclude.iter().any(matches)
+    }
+}
+
+/// Case-insensitive glob match supporting `*` and `?`
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` and the text position it was tried at
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            // Let the last `*` swallow one more character
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, t));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Repositories found by the last discovery
+#[derive(Debug, Clone)]
+pub(super) struct DiscoveredRepos {
+    repos: Vec<String>,
+    /// Time of the last attempt, successful or not
+    fetched_at: DateTime<Utc>,
+    /// Why the last attempt failed; `repos` then holds the previous list
+    error: Option<IntegrationError>,
+}
+
+impl GitProvider {
+    /// Repositories to watch: the configured ones followed by the discovered ones.
+    ///
+    /// Discovered repositories are cached for the refresh interval. When a
+    /// refresh fails the previous list is kept, and without one only the
+    /// configured repositories are used, unless there are none. Failures are
+    /// cached too, so discovery is attempted at most once per interval.
+    pub async fn repositories(&self) -> Result<Vec<String>, IntegrationError> {
+        let Some(ref discovery) = self.config().discovery else {
+            return Ok(self.config().repositories.clone());
+        };
+
+        let mut cached = self.discovery_cache().lock().await;
+        let ttl = Duration::seconds(discovery.refresh_interval_secs as i64);
+        let fresh = cached
+            .as_ref()
+            .is_some_and(|d| Utc::now().signed_duration_since(d.fetched_at) < ttl);
+
+        if !fresh {
+            let previous = cached.take().map(|d| d.repos).unwrap_or_default();
+            *cached = Some(match self.discover_repositories(discovery).await {
+                Ok(repos) => {
+                    log::info!("GitProvider: discovered {} repositories", repos.len());
+                    DiscoveredRepos { repos, fetched_at: Utc::now(), error: None }
+                }
+                Err(e) => {
+                    log::warn!("GitProvider: repository discovery failed, keeping the previous list: {}", e);
+                    DiscoveredRepos { repos: previous, fetched_at: Utc::now(), error: Some(e) }
+                }
+            });
+        }
+
+        let Some(discovered) = cached.as_ref() else { return Ok(self.config().repositories.clone()) };
+        if let Some(ref e) = discovered.error {
+            if discovered.repos.is_empty() && self.config().repositories.is_empty() {
+                return Err(e.clone());
+            }
+        }
+        Ok(self.merge_repositories(&discovered.repos))
+    }
+
+    /// Discover repositories now instead of waiting for the refresh interval
+    pub async fn refresh_repositories(&self) -> Result<Vec<String>, IntegrationError> {
+        self.discovery_cache().lock().await.take();
+        self.repositories().await
+    }
+
+    /// Configured repositories followed by the discovered ones not yet listed
+    fn merge_repositories(&self, discovered: &[String]) -> Vec<String> {
+        let mut repos = self.config().repositories.clone();
+        for repo in discovered {
+            if !repos.iter().any(|r| r.eq_ignore_ascii_case(repo)) {
+                repos.push(repo.clone());
+            }
+        }
+        repos
+    }
+
+    /// Selected repositories of the discovery owner, sorted by name
+    async fn discover_repositories(&self, discovery: &RepoDiscovery) -> Result<Vec<String>, IntegrationError> {
+        let owner = discovery
+            .owner
+            .as_deref()
+            .or(self.config().workspace.as_deref())
+            .ok_or_else(|| IntegrationError::ConfigError("Repository discovery needs an owner".to_string()))?;
+
+        let listed = match self.config().provider {
+            GitProviderType::GitHub => self.list_github_repos(owner).await?,
+            GitProviderType::Bitbucket => self.list_bitbucket_repos(owner, discovery.project.as_deref()).await?,
+            GitProviderType::GitLab => self.list_gitlab_repos(owner).await?,
+        };
+
+        let mut repos: Vec<String> = listed
+            .into_iter()
+            .filter(|(name, archived)| discovery.selects(name, *archived))
+            .map(|(name, _)| name)
+            .collect();
+        repos.sort();
+        Ok(repos)
+    }
+
+    fn discovery_error(status: u16, owner: &str) -> IntegrationError {
+        match status {
+            401 | 403 => IntegrationError::Auth(format!("Access denied to {}", owner)),
+            404 => IntegrationError::NotFound(format!("Owner {} not found", owner)),
+            429 => IntegrationError::RateLimit { reset_at: None },
+            status => IntegrationError::ApiError(format!("Status {} for {}", status, owner)),
+        }
+    }
+
+    /// `owner/name` and archived flag of every repository of a GitHub org or user
+    async fn list_github_repos(&self, owner: &str) -> Result<Vec<(String, bool)>, IntegrationError> {
+        let base = self.config().api_base_url();
+        // Only the authenticated user's own listing includes their private repositories
+        let mut next_url = Some(if owner.eq_ignore_ascii_case(&self.config().username) {
+            format!("{}/user/repos?affiliation=owner&per_page={}", base, REPO_PAGE_SIZE)
+        } else {
+            format!("{}/orgs/{}/repos?type=all&per_page={}", base, owner, REPO_PAGE_SIZE)
+        });
+        let mut tried_user = false;
+        let mut repos = Vec::new();
+
+        while let Some(url) = next_url.take() {
+            let response = self.get(&url).await?;
+
+            // Not an organization, so list the user's public repositories instead
+            if response.status == 404 && repos.is_empty() && !tried_user {
+                tried_user = true;
+                next_url = Some(format!("{}/users/{}/repos?type=owner&per_page={}", base, owner, REPO_PAGE_SIZE));
+                continue;
+            }
+            if response.status != 200 {
+                return Err(Self::discovery_error(response.status, owner));
+            }
+
+            next_url = response.header("link").and_then(parse_next_link);
+
+            let page: Vec<GitHubRepo> = response.json()?;
+            repos.extend(page.into_iter().map(|r| (r.full_name, r.archived)));
+        }
+
+        Ok(repos)
+    }
+
+    /// Slugs of the repositories of a Bitbucket workspace, optionally one project.
+    ///
+    /// Bitbucket has no archived repositories, so none are reported as such.
+    async fn list_bitbucket_repos(&self, workspace: &str, project: Option<&str>) -> Result<Vec<(String, bool)>, IntegrationError> {
+        let mut url = format!(
+            "{}/repositories/{}?pagelen={}",
+            self.config().api_base_url(), workspace, REPO_PAGE_SIZE
+        );
+        if let Some(project) = project {
+            url.push_str(&format!("&q={}", urlencoding::encode(&format!("project.key=\"{}\"", project))));
+        }
+
+        let mut repos = Vec::new();
+        let mut next_url = Some(url);
+
+        while let Some(url) = next_url.take() {
+            let response = self.get(&url).await?;
+            if response.status != 200 {
+                return Err(Self::discovery_error(response.status, workspace));
+            }
+
+            let page: BitbucketRepoPage = response.json()?;
+            repos.extend(page.values.into_iter().map(|r| (r.slug, false)));
+            next_url = page.next;
+        }
+
+        Ok(repos)
+    }
+
+    /// Full paths and archived flags of the projects of a GitLab group and its subgroups
+    async fn list_gitlab_repos(&self, group: &str) -> Result<Vec<(String, bool)>, IntegrationError> {
+        let mut repos = Vec::new();
+        let mut page = Some(1);
+
+        while let Some(current) = page {
+            let url = format!(
+                "{}/groups/{}/projects?include_subgroups=true&per_page={}&page={}",
+                self.config().api_base_url(),
+                urlencoding::encode(group),
+                REPO_PAGE_SIZE,
+                current
+            );
+
+            let response = self.get(&url).await?;
+            if response.status != 200 {
+                return Err(Self::discovery_error(response.status, group));
+            }
+
+            // GitLab sends an empty X-Next-Page header on the last page
+            page = response
+                .header("x-next-page")
+                .and_then(|v| v.trim().parse::<usize>().ok());
+
+            let projects: Vec<GitLabProject> = response.json()?;
+            repos.extend(projects.into_iter().map(|p| (p.path_with_namespace, p.archived)));
+        }
+
+        Ok(repos)
+    }
+}
+
+// ===== Repository Listing API Types =====
+
+#[derive(Debug, Deserialize)]
+struct GitHubRepo {
+    full_name: String,
+    #[serde(default)]
+    archived: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketRepoPage {
+    #[serde(default)]
+    values: Vec<BitbucketRepo>,
+    next: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketRepo {
+    slug: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabProject {
+    path_with_namespace: String,
+    #[serde(default)]
+    archived: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::git::GitConfig;
+    use serde_json::json;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("payments-*", "payments-api"));
+        assert!(glob_match("*-api", "Payments-API"));
+        assert!(glob_match("acme/*", "acme/web"));
+        assert!(glob_match("svc-??", "svc-eu"));
+        assert!(glob_match("*a*b*", "xxaxxbxx"));
+        assert!(glob_match("*", ""));
+        assert!(!glob_match("svc-??", "svc-eu1"));
+        assert!(!glob_match("payments-*", "billing-api"));
+        assert!(!glob_match("", "api"));
+    }
+
+    #[test]
+    fn test_discovery_selection() {
+        let discovery = RepoDiscovery::new()
+            .with_include("payments-*")
+            .with_include("acme/billing")
+            .with_exclude("*-sandbox");
+
+        assert!(discovery.selects("acme/payments-api", false));
+        assert!(discovery.selects("acme/billing", false));
+        assert!(!discovery.selects("acme/payments-sandbox", false));
+        assert!(!discovery.selects("acme/web", false));
+        // Archived repositories are left out unless asked for
+        assert!(!discovery.selects("acme/payments-legacy", true));
+        assert!(discovery.clone().include_archived().selects("acme/payments-legacy", true));
+        assert!(RepoDiscovery::new().selects("acme/anything", false));
+    }
+
+    #[test]
+    fn test_discovery_config_defaults() {
+        let discovery: RepoDiscovery = serde_json::from_value(json!({ "include": ["svc-*"] })).unwrap();
+        assert_eq!(discovery.refresh_interval_secs, 3600);
+        assert!(!discovery.include_archived);
+        assert!(discovery.owner.is_none());
+    }
+
+    #[test]
+    fn test_repository_listing_types() {
+        let github: Vec<GitHubRepo> = serde_json::from_value(json!([
+            { "id": 1, "full_name": "acme/api", "archived": false, "fork": false },
+            { "id": 2, "full_name": "acme/legacy", "archived": true }
+        ]))
+        .unwrap();
+        assert_eq!(github[1].full_name, "acme/legacy");
+        assert!(github[1].archived);
+
+        let bitbucket: BitbucketRepoPage = serde_json::from_value(json!({
+            "values": [{ "slug": "web", "full_name": "acme/web" }],
+            "next": "https://api.bitbucket.org/2.0/repositories/acme?page=2"
+        }))
+        .unwrap();
+        assert_eq!(bitbucket.values[0].slug, "web");
+        assert!(bitbucket.next.is_some());
+
+        let gitlab: Vec<GitLabProject> = serde_json::from_value(json!([
+            { "id": 1, "path_with_namespace": "acme/platform/api", "archived": false }
+        ]))
+        .unwrap();
+        assert_eq!(gitlab[0].path_with_namespace, "acme/platform/api");
+    }
+
+    #[test]
+    fn test_merge_keeps_configured_repositories_first() {
+        let provider = GitProvider::new(
+            GitConfig::github("octocat")
+                .with_token("t")
+                .with_repositories(vec!["acme/api".to_string(), "other/tool".to_string()]),
+        )
+        .unwrap();
+
+        let merged = provider.merge_repositories(&["acme/API".to_string(), "acme/web".to_string()]);
+
+        assert_eq!(merged, vec!["acme/api", "other/tool", "acme/web"]);
+    }
+
+    #[tokio::test]
+    async fn test_repositories_without_discovery() {
+        let provider = GitProvider::new(
+            GitConfig::github("octocat").with_token("t").with_repositories(vec!["acme/api".to_string()]),
+        )
+        .unwrap();
+
+        assert_eq!(provider.repositories().await.unwrap(), vec!["acme/api"]);
+    }
+
+    #[tokio::test]
+    async fn test_discovery_requires_owner() {
+        let provider = GitProvider::new(
+            GitConfig::github("octocat").with_token("t").with_discovery(RepoDiscovery::new()),
+        )
+        .unwrap();
+
+        assert!(matches!(provider.repositories().await, Err(IntegrationError::ConfigError(_))));
+        // The failure is cached until the next refresh
+        let fetched_at = provider.discovery_cache().lock().await.as_ref().map(|d| d.fetched_at);
+        assert!(matches!(provider.repositories().await, Err(IntegrationError::ConfigError(_))));
+        assert_eq!(provider.discovery_cache().lock().await.as_ref().map(|d| d.fetched_at), fetched_at);
+    }
+
+    #[tokio::test]
+    async fn test_failed_discovery_falls_back_to_configured_repositories() {
+        let provider = GitProvider::new(
+            GitConfig::github("octocat")
+                .with_token("t")
+                .with_repositories(vec!["acme/api".to_string()])
+                .with_discovery(RepoDiscovery::new()),
+        )
+        .unwrap();
+
+        assert_eq!(provider.repositories().await.unwrap(), vec!["acme/api"]);
+        let cache = provider.discovery_cache().lock().await.clone().unwrap();
+        assert!(cache.error.is_some());
+        assert!(cache.repos.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_cached_discovery_is_reused() {
+        let provider = GitProvider::new(
+            GitConfig::github("octocat")
+                .with_token("t")
+                .with_discovery(RepoDiscovery::new().with_owner("acme")),
+        )
+        .unwrap();
+
+        *provider.discovery_cache().lock().await = Some(DiscoveredRepos {
+            repos: vec!["acme/api".to_string()],
+            fetched_at: Utc::now(),
+            error: None,
+        });
+
+        // A fresh cache is served without calling the API
+        assert_eq!(provider.repositories().await.unwrap(), vec!["acme/api"]);
+        // Clones share the cache, as used by concurrent fetches
+        assert_eq!(provider.clone().repositories().await.unwrap(), vec!["acme/api"]);
+    }
+}
@@ -0,0 +1,677 @@
+//! GitHub GraphQL Backend
+//!
+//! Alternative to the REST listing for GitHub. REST needs a request per PR for
+//! reviews and two more for checks, while one GraphQL query returns the open
+//! PRs of several repositories with their reviews, status rollup, diff size and
+//! labels. Selected with `GitConfig::github_api`, it maps into the same
+//! PullRequest type as the REST backend.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::VecDeque;
+
+use super::provider::{GitConfig, GitHubLabel, GitHubReview, GitHubUser, GitProvider, GitProviderType};
+use crate::integrations::traits::{
+    ChangedFile, ChecksStatus, DiffStats, IntegrationError, PrFetchResult, PrFilter, PrState, PullRequest, User,
+};
+
+/// Repositories queried together in one request
+const GRAPHQL_BATCH_SIZE: usize = 10;
+
+/// PRs per repository and page; the nested reviews and labels make large pages slow to resolve
+const GRAPHQL_MAX_PAGE_SIZE: usize = 50;
+
+/// Fields of a PR, shared by the listing and the single PR query
+const PR_FIELDS: &str = "fragment PrFields on PullRequest { \
+number title body isDraft url createdAt updatedAt headRefName baseRefName \
+additions deletions changedFiles \
+author { login avatarUrl ... on User { databaseId } ... on Bot { databaseId } } \
+labels(first: 20) { nodes { name } } \
+files(first: 100) { nodes { path additions deletions } } \
+reviewRequests(first: 20) { nodes { requestedReviewer { ... on User { login avatarUrl databaseId } } } } \
+reviews(last: 50) { nodes { state author { login avatarUrl ... on User { databaseId } } } } \
+commits(last: 1) { nodes { commit { statusCheckRollup { state } } } } \
+}";
+
+/// API used to fetch GitHub pull requests
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GitHubApi {
+    #[default]
+    Rest,
+    #[serde(rename = "graphql")]
+    GraphQl,
+}
+
+impl GitConfig {
+    /// Whether PRs are fetched through the GitHub GraphQL API
+    pub(super) fn uses_graphql(&self) -> bool {
+        self.provider == GitProviderType::GitHub && self.github_api == GitHubApi::GraphQl
+    }
+
+    /// GraphQL endpoint: `/graphql` on github.com, `/api/graphql` on GitHub Enterprise Server
+    pub(super) fn graphql_url(&self) -> String {
+        let base = self.api_base_url();
+        match base.strip_suffix("/api/v3") {
+            Some(host) => format!("{}/api/graphql", host),
+            None => format!("{}/graphql", base),
+        }
+    }
+}
+
+/// Split an `owner/name` repository
+pub(super) fn split_repo(repo: &str) -> Option<(&str, &str)> {
+    let (owner, name) = repo.split_once('/')?;
+    (!owner.is_empty() && !name.is_empty() && !name.contains('/')).then_some((owner, name))
+}
+
+/// GraphQL string literal; JSON escaping is valid GraphQL syntax
+pub(super) fn literal(value: &str) -> String {
+    Value::from(value).to_string()
+}
+
+/// Field alias of the repository at `index`
+fn alias(index: usize) -> String {
+    format!("r{}", index)
+}
+
+/// A repository with more PRs to list
+#[derive(Debug, Clone)]
+struct PendingRepo {
+    index: usize,
+    after: Option<String>,
+}
+
+/// Query for the next page of open PRs of each pending repository, aliased by its index
+fn build_open_prs_query(repos: &[String], batch: &[PendingRepo], page_size: usize) -> String {
+    let selections: Vec<String> = batch
+        .iter()
+        .filter_map(|pending| {
+            let (owner, name) = split_repo(&repos[pending.index])?;
+            Some(format!(
+                "{}: repository(owner: {}, name: {}) {{ pullRequests(states: OPEN, first: {}, after: {}, \
+                 orderBy: {{field: UPDATED_AT, direction: DESC}}) {{ pageInfo {{ hasNextPage endCursor }} \
+                 nodes {{ ...PrFields }} }} }}",
+                alias(pending.index),
+                literal(owner),
+                literal(name),
+                page_size,
+                pending.after.as_deref().map(literal).unwrap_or_else(|| "null".to_string()),
+            ))
+        })
+        .collect();
+
+    format!("query {{ {} }} {}", selections.join(" "), PR_FIELDS)
+}
+
+fn build_pr_query(owner: &str, name: &str, number: u64) -> String {
+    format!(
+        "query {{ repository(owner: {}, name: {}) {{ pullRequest(number: {}) {{ ...PrFields }} }} }} {}",
+        literal(owner),
+        literal(name),
+        number,
+        PR_FIELDS
+    )
+}
+
+/// Checks status from the state of the head commit's status check rollup
+fn map_status_rollup(state: Option<&str>) -> ChecksStatus {
+    match state {
+        Some("SUCCESS") => ChecksStatus::Pass,
+        Some("FAILURE") | Some("ERROR") => ChecksStatus::Fail,
+        Some("PENDING") | Some("EXPECTED") => ChecksStatus::Running,
+        _ => ChecksStatus::None,
+    }
+}
+
+/// Error for a field that came back null, `not_found` describing what is missing
+pub(super) fn field_error(error: Option<&GraphQlError>, not_found: String) -> IntegrationError {
+    let Some(error) = error else {
+        return IntegrationError::NotFound(not_found);
+    };
+    match error.kind.as_deref() {
+        Some("NOT_FOUND") => IntegrationError::NotFound(not_found),
+        Some("FORBIDDEN") => IntegrationError::PermissionDenied(error.message.clone()),
+        _ => IntegrationError::ApiError(error.message.clone()),
+    }
+}
+
+impl GitProvider {
+    /// Open PRs of `repos`, several repositories per GraphQL request.
+    ///
+    /// Each repository is paged on its own, so a repository that is missing or
+    /// forbidden fails without affecting the others in its request.
+    pub(super) async fn fetch_graphql_prs(
+        &self,
+        repos: &[String],
+        filter: &PrFilter,
+    ) -> Result<PrFetchResult, IntegrationError> {
+        let page_size = self.config().page_size.clamp(1, GRAPHQL_MAX_PAGE_SIZE);
+
+        let mut results: Vec<Result<Vec<PullRequest>, IntegrationError>> = Vec::with_capacity(repos.len());
+        let mut pending = VecDeque::new();
+        for (index, repo) in repos.iter().enumerate() {
+            if split_repo(repo).is_some() {
+                results.push(Ok(Vec::new()));
+                pending.push_back(PendingRepo { index, after: None });
+            } else {
+                results.push(Err(IntegrationError::ConfigError(format!(
+                    "Repository {} is not in owner/name form",
+                    repo
+                ))));
+            }
+        }
+
+        while !pending.is_empty() {
+            let batch: Vec<PendingRepo> = pending.drain(..pending.len().min(GRAPHQL_BATCH_SIZE)).collect();
+            let query = build_open_prs_query(repos, &batch, page_size);
+
+            let response = match self.query_graphql(&query).await {
+                Ok(response) => response,
+                Err(e) => {
+                    for repo in &batch {
+                        results[repo.index] = Err(e.clone());
+                    }
+                    continue;
+                }
+            };
+
+            for repo in batch {
+                let name = &repos[repo.index];
+                let field = alias(repo.index);
+                let page = match response.field(&field).and_then(|r| r.get("pullRequests")) {
+                    Some(connection) => serde_json::from_value::<GqlPrConnection>(connection.clone())
+                        .map_err(|e| IntegrationError::ParseError(e.to_string())),
+                    None => Err(field_error(
+                        response.error_at(&field),
+                        format!("Repository {} not found", name),
+                    )),
+                };
+
+                let page = match page {
+                    Ok(page) => page,
+                    Err(e) => {
+                        results[repo.index] = Err(e);
+                        continue;
+                    }
+                };
+
+                let Ok(prs) = &mut results[repo.index] else { continue };
+                for node in &page.nodes {
+                    if prs.len() >= filter.limit {
+                        break;
+                    }
+                    if filter.stale_only && !self.is_stale(&node.updated_at) {
+                        continue;
+                    }
+                    prs.push(self.map_graphql_pr(node, name));
+                }
+
+                if prs.len() < filter.limit && page.page_info.has_next_page {
+                    pending.push_back(PendingRepo {
+                        index: repo.index,
+                        after: page.page_info.end_cursor,
+                    });
+                }
+            }
+        }
+
+        Self::merge_repo_results(repos, results, filter.limit)
+    }
+
+    /// A single PR through GraphQL
+    pub(super) async fn find_graphql_pr(&self, repo: &str, id: &str) -> Result<PullRequest, IntegrationError> {
+        let (owner, name) = split_repo(repo).ok_or_else(|| {
+            IntegrationError::ConfigError(format!("Repository {} is not in owner/name form", repo))
+        })?;
+        let number: u64 = id
+            .parse()
+            .map_err(|_| IntegrationError::NotFound(format!("PR {} not found", id)))?;
+
+        let response = self.query_graphql(&build_pr_query(owner, name, number)).await?;
+        match response.field("repository").and_then(|r| r.get("pullRequest")).filter(|pr| !pr.is_null()) {
+            Some(node) => {
+                let pr: GqlPr = serde_json::from_value(node.clone())
+                    .map_err(|e| IntegrationError::ParseError(e.to_string()))?;
+                Ok(self.map_graphql_pr(&pr, repo))
+            }
+            None => Err(field_error(response.error_at("repository"), format!("PR {} not found", id))),
+        }
+    }
+
+    /// Run a query, failing when it produced no data at all.
+    ///
+    /// Partial data is returned with its errors, which belong to the fields that
+    /// came back null.
+    pub(super) async fn query_graphql(&self, query: &str) -> Result<GraphQlResponse, IntegrationError> {
+        let url = self.config().graphql_url();
+        let (status, body) = self.post_json(&url, &serde_json::json!({ "query": query })).await?;
+
+        match status {
+            200 => {}
+            401 => return Err(IntegrationError::Auth("Invalid credentials".to_string())),
+            // Secondary limits without a Retry-After are only told apart by their message
+            403 if body.to_lowercase().contains("rate limit") => {
+                return Err(IntegrationError::RateLimit { reset_at: None })
+            }
+            403 => return Err(IntegrationError::PermissionDenied("GraphQL API access denied".to_string())),
+            status => return Err(IntegrationError::ApiError(format!("GraphQL status {}", status))),
+        }
+
+        let response: GraphQlResponse =
+            serde_json::from_str(&body).map_err(|e| IntegrationError::ParseError(e.to_string()))?;
+
+        // The GraphQL rate limit is reported in the body, not through the status
+        if response.errors.iter().any(|e| e.kind.as_deref() == Some("RATE_LIMITED")) {
+            return Err(IntegrationError::RateLimit { reset_at: None });
+        }
+        if response.data.as_ref().is_none_or(Value::is_null) {
+            let messages: Vec<&str> = response.errors.iter().map(|e| e.message.as_str()).collect();
+            return Err(IntegrationError::ApiError(messages.join("; ")));
+        }
+
+        Ok(response)
+    }
+
+    fn map_graphql_pr(&self, pr: &GqlPr, repo: &str) -> PullRequest {
+        let author = pr.author.as_ref().and_then(GqlActor::to_github_user);
+        let requested: Vec<GitHubUser> = pr
+            .review_requests
+            .nodes
+            .iter()
+            .filter_map(|request| request.requested_reviewer.as_ref()?.to_github_user())
+            .collect();
+        let reviews: Vec<GitHubReview> = pr
+            .reviews
+            .nodes
+            .iter()
+            .map(|review| GitHubReview {
+                user: review.author.as_ref().and_then(GqlActor::to_github_user),
+                state: review.state.clone(),
+                submitted_at: None,
+            })
+            .collect();
+        let rollup = pr
+            .commits
+            .nodes
+            .last()
+            .and_then(|node| node.commit.status_check_rollup.as_ref())
+            .map(|rollup| rollup.state.as_str());
+
+        PullRequest {
+            id: pr.number.to_string(),
+            repository: repo.to_string(),
+            title: pr.title.clone(),
+            description: Some(pr.body.clone()).filter(|body| !body.is_empty()),
+            state: if pr.is_draft { PrState::Draft } else { PrState::Open },
+            author: self.map_graphql_actor(pr.author.as_ref()),
+            reviewers: self.map_github_reviewers(author.map_or(0, |u| u.id), &requested, &reviews),
+            source_branch: pr.head_ref_name.clone(),
+            target_branch: pr.base_ref_name.clone(),
+            checks_status: map_status_rollup(rollup),
+            is_stale: self.is_stale(&pr.updated_at),
+            updated_at: pr.updated_at,
+            created_at: pr.created_at,
+            url: pr.url.clone(),
+            labels: pr.labels.nodes.iter().map(|l| l.name.clone()).collect(),
+            // The file list stops at 100 files, the totals do not
+            diff_stats: Some(DiffStats {
+                additions: pr.additions,
+                deletions: pr.deletions,
+                changed_files: pr.changed_files,
+                files: pr
+                    .files
+                    .nodes
+                    .iter()
+                    .map(|f| ChangedFile {
+                        path: f.path.clone(),
+                        additions: f.additions,
+                        deletions: f.deletions,
+                    })
+                    .collect(),
+            }),
+            connection: None,
+        }
+    }
+
+    /// User of an actor. Deleted accounts come back null and mannequins have
+    /// no id, so both are keyed by their login instead.
+    pub(super) fn map_graphql_actor(&self, actor: Option<&GqlActor>) -> User {
+        if let Some(user) = actor.and_then(GqlActor::to_github_user) {
+            return self.map_github_user(&user);
+        }
+
+        let login = actor.and_then(|a| a.login.clone()).unwrap_or_else(|| "ghost".to_string());
+        User {
+            id: login.clone(),
+            name: login,
+            email: None,
+            avatar_url: actor.and_then(|a| a.avatar_url.clone()),
+        }
+    }
+}
+
+// ===== GitHub GraphQL API Types =====
+
+#[derive(Debug, Deserialize)]
+pub(super) struct GraphQlResponse {
+    data: Option<Value>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+}
+
+impl GraphQlResponse {
+    /// Non-null top-level field of the data
+    pub(super) fn field(&self, name: &str) -> Option<&Value> {
+        self.data.as_ref()?.get(name).filter(|v| !v.is_null())
+    }
+
+    /// First error reported for a top-level field
+    pub(super) fn error_at(&self, name: &str) -> Option<&GraphQlError> {
+        self.errors
+            .iter()
+            .find(|e| e.path.first().and_then(Value::as_str) == Some(name))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct GraphQlError {
+    message: String,
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    #[serde(default)]
+    path: Vec<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct GqlNodes<T> {
+    #[serde(default = "Vec::new")]
+    pub(super) nodes: Vec<T>,
+}
+
+impl<T> Default for GqlNodes<T> {
+    fn default() -> Self {
+        Self { nodes: Vec::new() }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GqlPrConnection {
+    page_info: GqlPageInfo,
+    #[serde(default)]
+    nodes: Vec<GqlPr>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GqlPageInfo {
+    has_next_page: bool,
+    end_cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GqlPr {
+    number: u64,
+    title: String,
+    #[serde(default)]
+    body: String,
+    #[serde(default)]
+    is_draft: bool,
+    url: String,
+    created_at: chrono::DateTime<chrono::Utc>,
+    updated_at: chrono::DateTime<chrono::Utc>,
+    head_ref_name: String,
+    base_ref_name: String,
+    /// Null for deleted accounts
+    author: Option<GqlActor>,
+    #[serde(default)]
+    additions: u64,
+    #[serde(default)]
+    deletions: u64,
+    #[serde(default)]
+    changed_files: u64,
+    #[serde(default)]
+    labels: GqlNodes<GitHubLabel>,
+    #[serde(default)]
+    files: GqlNodes<GqlFile>,
+    #[serde(default)]
+    review_requests: GqlNodes<GqlReviewRequest>,
+    #[serde(default)]
+    reviews: GqlNodes<GqlReview>,
+    #[serde(default)]
+    commits: GqlNodes<GqlCommitNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GqlFile {
+    path: String,
+    additions: u64,
+    deletions: u64,
+}
+
+/// A user, bot or mannequin; fields are missing for actor types the query does not select
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct GqlActor {
+    login: Option<String>,
+    avatar_url: Option<String>,
+    database_id: Option<i64>,
+}
+
+impl GqlActor {
+    /// The REST shape of the actor, which needs the numeric id REST reports
+    fn to_github_user(&self) -> Option<GitHubUser> {
+        Some(GitHubUser {
+            id: self.database_id?,
+            login: self.login.clone()?,
+            avatar_url: self.avatar_url.clone().unwrap_or_default(),
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GqlReviewRequest {
+    /// Teams select no fields and come back empty
+    requested_reviewer: Option<GqlActor>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GqlReview {
+    state: String,
+    author: Option<GqlActor>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GqlCommitNode {
+    commit: GqlCommit,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GqlCommit {
+    status_check_rollup: Option<GqlStatusRollup>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GqlStatusRollup {
+    state: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::traits::ReviewState;
+
+    fn provider() -> GitProvider {
+        GitProvider::new(
+            GitConfig::github("alice")
+                .with_token("t")
+                .with_github_api(GitHubApi::GraphQl),
+        )
+        .unwrap()
+    }
+
+    fn actor(id: i64, login: &str) -> Value {
+        serde_json::json!({"login": login, "avatarUrl": "https://avatars.example.com/u", "databaseId": id})
+    }
+
+    fn pr_json() -> Value {
+        serde_json::json!({
+            "number": 42,
+            "title": "Add billing",
+            "body": "",
+            "isDraft": false,
+            "url": "https://github.com/acme/api/pull/42",
+            "createdAt": "2024-01-01T00:00:00Z",
+            "updatedAt": "2024-01-02T00:00:00Z",
+            "headRefName": "feature/billing",
+            "baseRefName": "main",
+            "author": actor(1, "alice"),
+            "additions": 120,
+            "deletions": 30,
+            "changedFiles": 4,
+            "labels": {"nodes": [{"name": "backend"}, {"name": "P1"}]},
+            "files": {"nodes": [{"path": "src/billing.rs", "additions": 120, "deletions": 30}]},
+            "reviewRequests": {"nodes": [
+                {"requestedReviewer": actor(4, "dave")},
+                {"requestedReviewer": {}}
+            ]},
+            "reviews": {"nodes": [
+                {"state": "APPROVED", "author": actor(2, "bob")},
+                {"state": "COMMENTED", "author": actor(2, "bob")},
+                {"state": "CHANGES_REQUESTED", "author": actor(3, "carol")},
+                {"state": "APPROVED", "author": null}
+            ]},
+            "commits": {"nodes": [{"commit": {"statusCheckRollup": {"state": "FAILURE"}}}]}
+        })
+    }
+
+    #[test]
+    fn test_github_api_config() {
+        let config: GitConfig = serde_json::from_value(serde_json::json!({
+            "provider": "github",
+            "base_url": null,
+            "workspace": null,
+            "username": "alice",
+            "repositories": []
+        }))
+        .unwrap();
+        assert_eq!(config.github_api, GitHubApi::Rest);
+        assert!(!config.uses_graphql());
+
+        let config = config.with_github_api(GitHubApi::GraphQl);
+        assert!(config.uses_graphql());
+        assert_eq!(serde_json::to_value(config.github_api).unwrap(), "graphql");
+
+        // Only GitHub has a GraphQL backend
+        assert!(!GitConfig::gitlab("alice").with_github_api(GitHubApi::GraphQl).uses_graphql());
+    }
+
+    #[test]
+    fn test_graphql_url() {
+        assert_eq!(GitConfig::github("alice").graphql_url(), "https://api.github.com/graphql");
+        assert_eq!(
+            GitConfig::github("alice").with_base_url("https://ghe.acme.io/api/v3/").graphql_url(),
+            "https://ghe.acme.io/api/graphql"
+        );
+    }
+
+    #[test]
+    fn test_build_open_prs_query() {
+        let repos = vec!["acme/api".to_string(), "acme/web".to_string()];
+        let batch = vec![
+            PendingRepo { index: 0, after: None },
+            PendingRepo { index: 1, after: Some("Y3Vy\"c29y".to_string()) },
+        ];
+
+        let query = build_open_prs_query(&repos, &batch, 25);
+
+        assert!(query.contains("r0: repository(owner: \"acme\", name: \"api\")"));
+        assert!(query.contains("r1: repository(owner: \"acme\", name: \"web\")"));
+        assert!(query.contains("first: 25, after: null"));
+        assert!(query.contains("after: \"Y3Vy\\\"c29y\""));
+        assert!(query.ends_with(PR_FIELDS));
+    }
+
+    #[test]
+    fn test_split_repo() {
+        assert_eq!(split_repo("acme/api"), Some(("acme", "api")));
+        assert_eq!(split_repo("api"), None);
+        assert_eq!(split_repo("acme/"), None);
+        assert_eq!(split_repo("acme/group/api"), None);
+    }
+
+    #[test]
+    fn test_map_graphql_pr() {
+        let provider = provider();
+        let node: GqlPr = serde_json::from_value(pr_json()).unwrap();
+
+        let pr = provider.map_graphql_pr(&node, "acme/api");
+
+        assert_eq!(pr.id, "42");
+        assert_eq!(pr.description, None);
+        assert_eq!(pr.state, PrState::Open);
+        assert_eq!(pr.author.id, "1");
+        assert_eq!(pr.checks_status, ChecksStatus::Fail);
+        assert_eq!(pr.labels, vec!["backend", "P1"]);
+        let stats = pr.diff_stats.unwrap();
+        assert_eq!((stats.additions, stats.deletions, stats.changed_files), (120, 30, 4));
+        assert_eq!(stats.files[0].path, "src/billing.rs");
+
+        let state_of = |name: &str| pr.reviewers.iter().find(|r| r.user.name == name).map(|r| r.state);
+        assert_eq!(state_of("bob"), Some(ReviewState::Approved));
+        assert_eq!(state_of("carol"), Some(ReviewState::ChangesRequested));
+        assert_eq!(state_of("dave"), Some(ReviewState::Pending));
+        // Team requests and deleted reviewers have no user to show
+        assert_eq!(pr.reviewers.len(), 3);
+    }
+
+    #[test]
+    fn test_map_graphql_pr_without_author() {
+        let mut json = pr_json();
+        json["author"] = Value::Null;
+        json["isDraft"] = Value::Bool(true);
+        json["commits"] = serde_json::json!({"nodes": [{"commit": {"statusCheckRollup": null}}]});
+
+        let pr = provider().map_graphql_pr(&serde_json::from_value(json).unwrap(), "acme/api");
+
+        assert_eq!(pr.author.name, "ghost");
+        assert_eq!(pr.state, PrState::Draft);
+        assert_eq!(pr.checks_status, ChecksStatus::None);
+    }
+
+    #[test]
+    fn test_map_status_rollup() {
+        assert_eq!(map_status_rollup(Some("SUCCESS")), ChecksStatus::Pass);
+        assert_eq!(map_status_rollup(Some("ERROR")), ChecksStatus::Fail);
+        assert_eq!(map_status_rollup(Some("EXPECTED")), ChecksStatus::Running);
+        assert_eq!(map_status_rollup(None), ChecksStatus::None);
+    }
+
+    #[test]
+    fn test_partial_response_errors() {
+        let response: GraphQlResponse = serde_json::from_value(serde_json::json!({
+            "data": {
+                "r0": {"pullRequests": {"pageInfo": {"hasNextPage": false, "endCursor": null}, "nodes": []}},
+                "r1": null
+            },
+            "errors": [{
+                "type": "NOT_FOUND",
+                "path": ["r1"],
+                "message": "Could not resolve to a Repository with the name 'acme/gone'."
+            }]
+        }))
+        .unwrap();
+
+        assert!(response.field("r0").is_some());
+        assert!(response.field("r1").is_none());
+        assert!(response.error_at("r0").is_none());
+        assert!(matches!(
+            field_error(response.error_at("r1"), "Repository acme/gone not found".to_string()),
+            IntegrationError::NotFound(_)
+        ));
+    }
+}
@@ -0,0 +1,896 @@
+//! Git Hosting Issue Trackers
+//!
+//! Implements TicketRepository for GitHub Issues and GitLab Issues with the
+//! credentials of the git connection. Issues are keyed `owner/repo#123`; a bare
+//! `#123` refers to the first configured repository.
+//!
+//! Issue trackers have no workflow, so tickets are mapped by convention: closed
+//! issues are done, open issues with an "in progress" label are in progress,
+//! priority comes from labels such as `P1` or `priority: high`, and the
+//! milestone stands in for the sprint. Issue types and `any_of` groups of a
+//! search are ignored.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::de::IgnoredAny;
+use serde::Deserialize;
+
+use super::provider::{
+    fan_out, parse_next_link, GitHubLabel, GitHubUser, GitLabNote, GitLabUser, GitProvider, GitProviderType,
+};
+use crate::integrations::traits::{
+    Comment, IntegrationError, Priority, SprintFilter, StatusCategory, Ticket, TicketRepository,
+    TicketSearchQuery, TicketStatus,
+};
+
+/// Largest page GitHub and GitLab return
+const ISSUE_PAGE_SIZE: usize = 100;
+
+/// Longest query GitHub's search accepts before answering 422
+const GITHUB_MAX_QUERY_LEN: usize = 256;
+
+/// Split an issue reference into its repository, if given, and number
+fn parse_issue_ref(id: &str) -> Option<(Option<&str>, u64)> {
+    let (repo, number) = id.trim().rsplit_once('#')?;
+    let number = number.parse().ok()?;
+
+    let repo = repo.trim();
+    if repo.is_empty() {
+        return Some((None, number));
+    }
+    if !repo.contains('/') || repo.split('/').any(str::is_empty) {
+        return None;
+    }
+    Some((Some(repo), number))
+}
+
+/// Lower-cased label with scope separators and dashes normalised to spaces
+fn normalize_label(label: &str) -> String {
+    label
+        .to_lowercase()
+        .replace("::", ":")
+        .replace(['-', '_'], " ")
+        .trim()
+        .to_string()
+}
+
+/// Priority of an issue from its labels, the highest one winning.
+///
+/// Bare `P0`–`P4` labels are recognised as well as words behind a `priority` or
+/// `prio` scope, e.g. `priority: high` or GitLab's scoped `priority::critical`.
+fn priority_from_labels(labels: &[String]) -> Option<Priority> {
+    let rank = |priority: &Priority| match priority {
+        Priority::Highest => 0,
+        Priority::High => 1,
+        Priority::Medium => 2,
+        Priority::Low => 3,
+        Priority::Lowest => 4,
+    };
+
+    labels
+        .iter()
+        .filter_map(|label| {
+            let label = normalize_label(label);
+            let value = ["priority", "prio"]
+                .iter()
+                .find_map(|scope| label.strip_prefix(scope))
+                .map(|rest| rest.trim_start_matches([':', '/', ' ']).to_string());
+            let scoped = value.is_some();
+
+            match value.as_deref().unwrap_or(&label) {
+                "p0" => Some(Priority::Highest),
+                "p1" => Some(Priority::High),
+                "p2" => Some(Priority::Medium),
+                "p3" => Some(Priority::Low),
+                "p4" => Some(Priority::Lowest),
+                "critical" | "highest" | "urgent" | "blocker" if scoped => Some(Priority::Highest),
+                "high" if scoped => Some(Priority::High),
+                "medium" | "normal" if scoped => Some(Priority::Medium),
+                "low" if scoped => Some(Priority::Low),
+                "lowest" | "trivial" if scoped => Some(Priority::Lowest),
+                _ => None,
+            }
+        })
+        .min_by_key(rank)
+}
+
+/// Status of an issue from its state and labels
+fn issue_status(open: bool, labels: &[String]) -> TicketStatus {
+    let in_progress = labels.iter().any(|label| {
+        let label = normalize_label(label);
+        let label = label
+            .strip_prefix("status")
+            .map(|rest| rest.trim_start_matches([':', '/', ' ']))
+            .unwrap_or(&label);
+        matches!(label, "in progress" | "doing" | "wip" | "in review")
+    });
+
+    let (name, category) = match (open, in_progress) {
+        (false, _) => ("Closed", StatusCategory::Done),
+        (true, true) => ("In Progress", StatusCategory::InProgress),
+        (true, false) => ("Open", StatusCategory::Todo),
+    };
+    TicketStatus {
+        name: name.to_string(),
+        category,
+    }
+}
+
+/// Whether the search asks for open (`true`) or closed (`false`) issues only
+fn requested_state(query: &TicketSearchQuery) -> Option<bool> {
+    if let Some(category) = query.status_category {
+        return Some(category != StatusCategory::Done);
+    }
+    match query.status.as_deref().map(str::to_lowercase).as_deref() {
+        Some("closed" | "done") => Some(false),
+        Some("open" | "opened" | "in progress") => Some(true),
+        _ => None,
+    }
+}
+
+/// Filters the APIs cannot express, applied to the mapped tickets
+fn matches_query(ticket: &Ticket, query: &TicketSearchQuery) -> bool {
+    (query.priorities.is_empty() || ticket.priority.is_some_and(|p| query.priorities.contains(&p)))
+        && query.status_category.is_none_or(|c| ticket.status.category == c)
+}
+
+/// `owner/repo` of a GitHub API repository URL
+fn repo_from_url(url: &str) -> String {
+    let mut segments = url.trim_end_matches('/').rsplit('/');
+    let name = segments.next().unwrap_or_default();
+    let owner = segments.next().unwrap_or_default();
+    format!("{}/{}", owner, name)
+}
+
+fn issue_error(status: u16, body: &str, what: &str) -> IntegrationError {
+    match status {
+        401 | 403 => IntegrationError::Auth(format!("Access denied to {}", what)),
+        404 => IntegrationError::NotFound(format!("{} not found", what)),
+        429 => IntegrationError::RateLimit { reset_at: None },
+        status => IntegrationError::ApiError(format!("Status {}: {}", status, body)),
+    }
+}
+
+impl GitProvider {
+    /// Repository and number of an issue reference
+    fn resolve_issue_ref(&self, id: &str) -> Result<(String, u64), IntegrationError> {
+        let (repo, number) = parse_issue_ref(id)
+            .ok_or_else(|| IntegrationError::NotFound(format!("{} is not an issue reference", id)))?;
+
+        match repo {
+            Some(repo) => Ok((repo.to_string(), number)),
+            None => self
+                .config()
+                .repositories
+                .first()
+                .map(|repo| (repo.clone(), number))
+                .ok_or_else(|| {
+                    IntegrationError::ConfigError(format!("No repository configured for {}", id))
+                }),
+        }
+    }
+
+    /// Repositories a search covers: the query's project or all watched ones
+    async fn issue_search_repos(&self, query: &TicketSearchQuery) -> Result<Vec<String>, IntegrationError> {
+        match query.project {
+            Some(ref project) => Ok(vec![project.clone()]),
+            None => self.repositories().await,
+        }
+    }
+
+    /// Username for a user filter, resolving Jira's `currentUser()`
+    fn issue_username<'a>(&'a self, user: &'a str) -> &'a str {
+        if user.eq_ignore_ascii_case("currentUser()") {
+            &self.config().username
+        } else {
+            user
+        }
+    }
+
+    fn issue_tracker_unsupported(&self) -> IntegrationError {
+        IntegrationError::ConfigError(format!(
+            "{} issues are not supported",
+            self.config().provider.as_str()
+        ))
+    }
+
+    // ----- GitHub -----
+
+    fn map_github_issue(&self, issue: &GitHubIssue, repo: &str) -> Ticket {
+        let labels: Vec<String> = issue.labels.iter().map(|l| l.name.clone()).collect();
+
+        Ticket {
+            id: issue.id.to_string(),
+            key: format!("{}#{}", repo, issue.number),
+            summary: issue.title.clone(),
+            description: issue.body.clone(),
+            description_markdown: issue.body.clone(),
+            status: issue_status(issue.state == "open", &labels),
+            assignee: issue.assignee.as_ref().map(|u| self.map_github_user(u)),
+            reporter: issue.user.as_ref().map(|u| self.map_github_user(u)),
+            priority: priority_from_labels(&labels),
+            sprint: issue.milestone.as_ref().map(|m| m.title.clone()),
+            story_points: None,
+            epic: None,
+            team: None,
+            labels,
+            updated_at: issue.updated_at,
+            created_at: issue.created_at,
+        }
+    }
+
+    /// Search terms of the GitHub issue search for a query
+    fn github_search_terms(&self, query: &TicketSearchQuery, repos: &[String]) -> String {
+        let mut terms = vec!["is:issue".to_string()];
+
+        if let Some(ref text) = query.text {
+            terms.push(text.trim().to_string());
+        }
+        terms.extend(repos.iter().map(|repo| format!("repo:{}", repo)));
+        if let Some(ref assignee) = query.assignee {
+            terms.push(format!("assignee:{}", self.issue_username(assignee)));
+        }
+        if let Some(ref reporter) = query.reporter {
+            terms.push(format!("author:{}", self.issue_username(reporter)));
+        }
+        match requested_state(query) {
+            Some(true) => terms.push("is:open".to_string()),
+            Some(false) => terms.push("is:closed".to_string()),
+            None => {}
+        }
+        terms.extend(query.labels.iter().map(|label| format!("label:\"{}\"", label)));
+        if let Some(SprintFilter::Specific(ref milestone)) = query.sprint {
+            terms.push(format!("milestone:\"{}\"", milestone));
+        }
+        if let Some(since) = query.updated_since {
+            terms.push(format!("updated:>={}", since.format("%Y-%m-%dT%H:%M:%SZ")));
+        }
+
+        terms.join(" ")
+    }
+
+    /// Queries covering `repos`, split so each stays within GitHub's length limit
+    fn github_search_queries(&self, query: &TicketSearchQuery, repos: &[String]) -> Vec<String> {
+        let mut batches: Vec<Vec<String>> = Vec::new();
+        let mut batch: Vec<String> = Vec::new();
+
+        for repo in repos {
+            batch.push(repo.clone());
+            if batch.len() > 1 && self.github_search_terms(query, &batch).len() > GITHUB_MAX_QUERY_LEN {
+                let overflow = batch.pop().into_iter().collect();
+                batches.push(std::mem::replace(&mut batch, overflow));
+            }
+        }
+        batches.push(batch);
+
+        batches.iter().map(|batch| self.github_search_terms(query, batch)).collect()
+    }
+
+    async fn find_github_issue(&self, repo: &str, number: u64) -> Result<Ticket, IntegrationError> {
+        let url = format!("{}/repos/{}/issues/{}", self.config().api_base_url(), repo, number);
+        let response = self.get(&url).await?;
+        let reference = format!("Issue {}#{}", repo, number);
+
+        match response.status {
+            200 => {
+                let issue: GitHubIssue = response.json()?;
+                // The issues API serves pull requests as well
+                if issue.pull_request.is_some() {
+                    return Err(IntegrationError::NotFound(format!("{} is a pull request", reference)));
+                }
+                Ok(self.map_github_issue(&issue, repo))
+            }
+            status => Err(issue_error(status, &response.body, &reference)),
+        }
+    }
+
+    async fn search_github_issues(&self, query: &TicketSearchQuery) -> Result<Vec<Ticket>, IntegrationError> {
+        let repos = self.issue_search_repos(query).await?;
+        if repos.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut tickets = Vec::new();
+        for terms in self.github_search_queries(query, &repos) {
+            tickets.extend(self.search_github_issue_batch(&terms, query).await?);
+        }
+
+        // Each batch is sorted on its own
+        tickets.sort_by_key(|ticket| std::cmp::Reverse(ticket.updated_at));
+        tickets.truncate(query.limit);
+        Ok(tickets)
+    }
+
+    /// Run one search query, up to `query.limit` matches
+    async fn search_github_issue_batch(
+        &self,
+        terms: &str,
+        query: &TicketSearchQuery,
+    ) -> Result<Vec<Ticket>, IntegrationError> {
+        let mut tickets = Vec::new();
+        let mut next_url = Some(format!(
+            "{}/search/issues?q={}&sort=updated&order=desc&per_page={}",
+            self.config().api_base_url(),
+            urlencoding::encode(terms),
+            query.limit.clamp(1, ISSUE_PAGE_SIZE)
+        ));
+
+        while let Some(url) = next_url.take() {
+            let response = self.get(&url).await?;
+            if response.status != 200 {
+                return Err(issue_error(response.status, &response.body, "issue search"));
+            }
+
+            next_url = response.header("link").and_then(parse_next_link);
+
+            let result: GitHubIssueSearch = response.json()?;
+            tickets.extend(
+                result
+                    .items
+                    .iter()
+                    .map(|issue| self.map_github_issue(issue, &repo_from_url(&issue.repository_url)))
+                    .filter(|ticket| matches_query(ticket, query)),
+            );
+
+            if tickets.len() >= query.limit {
+                break;
+            }
+        }
+
+        Ok(tickets)
+    }
+
+    async fn get_github_comments(&self, repo: &str, number: u64, limit: usize) -> Result<Vec<Comment>, IntegrationError> {
+        let mut comments: Vec<GitHubComment> = Vec::new();
+        let mut next_url = Some(format!(
+            "{}/repos/{}/issues/{}/comments?per_page={}",
+            self.config().api_base_url(), repo, number, ISSUE_PAGE_SIZE
+        ));
+
+        // Comments are listed oldest first, so the newest are on the last page
+        while let Some(url) = next_url.take() {
+            let response = self.get(&url).await?;
+            if response.status != 200 {
+                let reference = format!("Issue {}#{}", repo, number);
+                return Err(issue_error(response.status, &response.body, &reference));
+            }
+
+            next_url = response.header("link").and_then(parse_next_link);
+            comments.extend(response.json::<Vec<GitHubComment>>()?);
+        }
+
+        Ok(comments
+            .iter()
+            .rev()
+            .take(limit)
+            .map(|comment| {
+                let body = comment.body.clone().unwrap_or_default();
+                Comment {
+                    id: comment.id.to_string(),
+                    author: comment.user.as_ref().map(|u| self.map_github_user(u)),
+                    body: body.clone(),
+                    body_markdown: body,
+                    created_at: comment.created_at,
+                    updated_at: comment.updated_at,
+                }
+            })
+            .collect())
+    }
+
+    // ----- GitLab -----
+
+    fn map_gitlab_issue(&self, issue: &GitLabIssue, project: &str) -> Ticket {
+        let key = match issue.references {
+            Some(ref references) => references.full.clone(),
+            None => format!("{}#{}", project, issue.iid),
+        };
+
+        Ticket {
+            id: issue.id.to_string(),
+            key,
+            summary: issue.title.clone(),
+            description: issue.description.clone(),
+            description_markdown: issue.description.clone(),
+            status: issue_status(issue.state == "opened", &issue.labels),
+            assignee: issue.assignees.first().map(|u| self.map_gitlab_user(u)),
+            reporter: issue.author.as_ref().map(|u| self.map_gitlab_user(u)),
+            priority: priority_from_labels(&issue.labels),
+            sprint: issue.milestone.as_ref().map(|m| m.title.clone()),
+            story_points: issue.weight,
+            epic: None,
+            team: None,
+            labels: issue.labels.clone(),
+            updated_at: issue.updated_at,
+            created_at: issue.created_at,
+        }
+    }
+
+    /// Query parameters of the GitLab project issues API for a query
+    fn gitlab_issue_params(&self, query: &TicketSearchQuery) -> Vec<(&'static str, String)> {
+        let mut params = vec![
+            ("order_by", "updated_at".to_string()),
+            ("sort", "desc".to_string()),
+        ];
+
+        if let Some(ref text) = query.text {
+            params.push(("search", text.trim().to_string()));
+        }
+        if let Some(ref assignee) = query.assignee {
+            params.push(("assignee_username", self.issue_username(assignee).to_string()));
+        }
+        if let Some(ref reporter) = query.reporter {
+            params.push(("author_username", self.issue_username(reporter).to_string()));
+        }
+        match requested_state(query) {
+            Some(true) => params.push(("state", "opened".to_string())),
+            Some(false) => params.push(("state", "closed".to_string())),
+            None => {}
+        }
+        if !query.labels.is_empty() {
+            params.push(("labels", query.labels.join(",")));
+        }
+        match query.sprint {
+            Some(SprintFilter::Open) => params.push(("milestone", "Started".to_string())),
+            Some(SprintFilter::Specific(ref milestone)) => params.push(("milestone", milestone.clone())),
+            Some(SprintFilter::Closed) | None => {}
+        }
+        if let Some(since) = query.updated_since {
+            params.push(("updated_after", since.to_rfc3339()));
+        }
+
+        params
+    }
+
+    async fn find_gitlab_issue(&self, repo: &str, iid: u64) -> Result<Ticket, IntegrationError> {
+        let project = self.config().gitlab_project_path(repo);
+        let url = format!(
+            "{}/projects/{}/issues/{}",
+            self.config().api_base_url(), urlencoding::encode(&project), iid
+        );
+        let response = self.get(&url).await?;
+
+        match response.status {
+            200 => Ok(self.map_gitlab_issue(&response.json()?, &project)),
+            status => Err(issue_error(status, &response.body, &format!("Issue {}#{}", project, iid))),
+        }
+    }
+
+    async fn fetch_gitlab_issues(&self, repo: &str, query: &TicketSearchQuery) -> Result<Vec<Ticket>, IntegrationError> {
+        let project = self.config().gitlab_project_path(repo);
+        let params: String = self
+            .gitlab_issue_params(query)
+            .iter()
+            .map(|(name, value)| format!("&{}={}", name, urlencoding::encode(value)))
+            .collect();
+
+        let mut tickets = Vec::new();
+        let mut page = Some(1);
+
+        while let Some(current) = page {
+            let url = format!(
+                "{}/projects/{}/issues?per_page={}&page={}{}",
+                self.config().api_base_url(),
+                urlencoding::encode(&project),
+                query.limit.clamp(1, ISSUE_PAGE_SIZE),
+                current,
+                params
+            );
+
+            let response = self.get(&url).await?;
+            if response.status != 200 {
+                return Err(issue_error(response.status, &response.body, &format!("Project {}", project)));
+            }
+
+            // GitLab sends an empty X-Next-Page header on the last page
+            page = response
+                .header("x-next-page")
+                .and_then(|v| v.trim().parse::<usize>().ok());
+
+            let issues: Vec<GitLabIssue> = response.json()?;
+            tickets.extend(
+                issues
+                    .iter()
+                    .map(|issue| self.map_gitlab_issue(issue, &project))
+                    .filter(|ticket| matches_query(ticket, query)),
+            );
+
+            if tickets.len() >= query.limit {
+                break;
+            }
+        }
+
+        Ok(tickets)
+    }
+
+    /// Search the issues of every project concurrently, newest updates first.
+    ///
+    /// Projects that fail are skipped unless all of them do.
+    async fn search_gitlab_issues(&self, query: &TicketSearchQuery) -> Result<Vec<Ticket>, IntegrationError> {
+        let repos = self.issue_search_repos(query).await?;
+
+        let results = fan_out(
+            &repos,
+            self.config().max_concurrency,
+            std::time::Duration::from_secs(self.config().repo_timeout_secs),
+            |repo| {
+                let provider = self.clone();
+                let query = query.clone();
+                async move { provider.fetch_gitlab_issues(&repo, &query).await }
+            },
+        )
+        .await;
+
+        let mut tickets = Vec::new();
+        let mut first_error = None;
+        let mut succeeded = 0;
+
+        for (repo, result) in repos.iter().zip(results) {
+            match result {
+                Ok(found) => {
+                    succeeded += 1;
+                    tickets.extend(found);
+                }
+                Err(e) => {
+                    log::warn!("GitProvider: failed to search issues of {}: {}", repo, e);
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        if succeeded == 0 {
+            if let Some(e) = first_error {
+                return Err(e);
+            }
+        }
+
+        tickets.sort_by_key(|ticket| std::cmp::Reverse(ticket.updated_at));
+        tickets.truncate(query.limit);
+        Ok(tickets)
+    }
+
+    async fn get_gitlab_comments(&self, repo: &str, iid: u64, limit: usize) -> Result<Vec<Comment>, IntegrationError> {
+        let project = self.config().gitlab_project_path(repo);
+        let url = format!(
+            "{}/projects/{}/issues/{}/notes?sort=desc&order_by=created_at&per_page={}",
+            self.config().api_base_url(), urlencoding::encode(&project), iid, ISSUE_PAGE_SIZE
+        );
+        let response = self.get(&url).await?;
+
+        let notes: Vec<GitLabNote> = match response.status {
+            200 => response.json()?,
+            status => return Err(issue_error(status, &response.body, &format!("Issue {}#{}", project, iid))),
+        };
+
+        // System notes record label and assignee changes rather than discussion
+        Ok(notes
+            .iter()
+            .filter(|note| !note.system)
+            .take(limit)
+            .map(|note| Comment {
+                id: note.id.to_string(),
+                author: note.author.as_ref().map(|u| self.map_gitlab_user(u)),
+                body: note.body.clone(),
+                body_markdown: note.body.clone(),
+                created_at: note.created_at,
+                updated_at: note.updated_at,
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl TicketRepository for GitProvider {
+    async fn find_by_id(&self, id: &str) -> Result<Ticket, IntegrationError> {
+        let (repo, number) = self.resolve_issue_ref(id)?;
+
+        match self.config().provider {
+            GitProviderType::GitHub => self.find_github_issue(&repo, number).await,
+            GitProviderType::GitLab => self.find_gitlab_issue(&repo, number).await,
+            GitProviderType::Bitbucket => Err(self.issue_tracker_unsupported()),
+        }
+    }
+
+    async fn search(&self, query: &TicketSearchQuery) -> Result<Vec<Ticket>, IntegrationError> {
+        match self.config().provider {
+            GitProviderType::GitHub => self.search_github_issues(query).await,
+            GitProviderType::GitLab => self.search_gitlab_issues(query).await,
+            GitProviderType::Bitbucket => Err(self.issue_tracker_unsupported()),
+        }
+    }
+
+    async fn get_comments(&self, key: &str, limit: usize) -> Result<Vec<Comment>, IntegrationError> {
+        let (repo, number) = self.resolve_issue_ref(key)?;
+
+        match self.config().provider {
+            GitProviderType::GitHub => self.get_github_comments(&repo, number, limit).await,
+            GitProviderType::GitLab => self.get_gitlab_comments(&repo, number, limit).await,
+            GitProviderType::Bitbucket => Err(self.issue_tracker_unsupported()),
+        }
+    }
+}
+
+// ===== GitHub Issues API Types =====
+
+#[derive(Debug, Deserialize)]
+struct GitHubIssue {
+    id: i64,
+    number: i64,
+    title: String,
+    body: Option<String>,
+    state: String,
+    user: Option<GitHubUser>,
+    assignee: Option<GitHubUser>,
+    #[serde(default)]
+    labels: Vec<GitHubLabel>,
+    milestone: Option<GitHubMilestone>,
+    /// Present when the issue is a pull request
+    pull_request: Option<IgnoredAny>,
+    repository_url: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubMilestone {
+    title: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubIssueSearch {
+    #[serde(default)]
+    items: Vec<GitHubIssue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubComment {
+    id: i64,
+    user: Option<GitHubUser>,
+    body: Option<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+// ===== GitLab Issues API Types =====
+
+#[derive(Debug, Deserialize)]
+struct GitLabIssue {
+    id: i64,
+    iid: i64,
+    title: String,
+    description: Option<String>,
+    state: String,
+    author: Option<GitLabUser>,
+    #[serde(default)]
+    assignees: Vec<GitLabUser>,
+    #[serde(default)]
+    labels: Vec<String>,
+    milestone: Option<GitLabMilestone>,
+    weight: Option<f64>,
+    references: Option<GitLabReferences>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabMilestone {
+    title: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabReferences {
+    full: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::git::GitConfig;
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    fn github() -> GitProvider {
+        GitProvider::new(
+            GitConfig::github("octocat")
+                .with_token("t")
+                .with_repositories(vec!["acme/api".to_string(), "acme/web".to_string()]),
+        )
+        .unwrap()
+    }
+
+    fn gitlab() -> GitProvider {
+        GitProvider::new(
+            GitConfig::gitlab("jane")
+                .with_token("t")
+                .with_workspace("acme")
+                .with_repositories(vec!["api".to_string()]),
+        )
+        .unwrap()
+    }
+
+    fn labels(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_issue_ref() {
+        assert_eq!(parse_issue_ref("#42"), Some((None, 42)));
+        assert_eq!(parse_issue_ref("acme/api#42"), Some((Some("acme/api"), 42)));
+        assert_eq!(parse_issue_ref("group/sub/project#7"), Some((Some("group/sub/project"), 7)));
+        assert_eq!(parse_issue_ref("api#42"), None);
+        assert_eq!(parse_issue_ref("acme/#42"), None);
+        assert_eq!(parse_issue_ref("#abc"), None);
+        assert_eq!(parse_issue_ref("PROJ-42"), None);
+    }
+
+    #[test]
+    fn test_resolve_bare_reference_to_first_repository() {
+        assert_eq!(github().resolve_issue_ref("#42").unwrap(), ("acme/api".to_string(), 42));
+        assert_eq!(github().resolve_issue_ref("acme/web#3").unwrap(), ("acme/web".to_string(), 3));
+
+        let unscoped = GitProvider::new(GitConfig::github("octocat").with_token("t")).unwrap();
+        assert!(matches!(unscoped.resolve_issue_ref("#42"), Err(IntegrationError::ConfigError(_))));
+        assert!(matches!(unscoped.resolve_issue_ref("PROJ-42"), Err(IntegrationError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_priority_from_labels() {
+        assert_eq!(priority_from_labels(&labels(&["bug", "P1"])), Some(Priority::High));
+        assert_eq!(priority_from_labels(&labels(&["priority: low"])), Some(Priority::Low));
+        assert_eq!(priority_from_labels(&labels(&["priority::critical"])), Some(Priority::Highest));
+        assert_eq!(priority_from_labels(&labels(&["Priority/Medium"])), Some(Priority::Medium));
+        // The highest priority wins
+        assert_eq!(priority_from_labels(&labels(&["p3", "priority-high"])), Some(Priority::High));
+        // Bare words are too ambiguous
+        assert_eq!(priority_from_labels(&labels(&["high", "low-hanging fruit"])), None);
+    }
+
+    #[test]
+    fn test_issue_status() {
+        assert_eq!(issue_status(false, &labels(&["in progress"])).category, StatusCategory::Done);
+        assert_eq!(issue_status(true, &labels(&["bug"])).category, StatusCategory::Todo);
+
+        let doing = issue_status(true, &labels(&["status::in-progress"]));
+        assert_eq!(doing.name, "In Progress");
+        assert_eq!(doing.category, StatusCategory::InProgress);
+    }
+
+    #[test]
+    fn test_map_github_issue() {
+        let issue: GitHubIssue = serde_json::from_value(json!({
+            "id": 9001, "number": 42, "title": "Refunds fail for EUR",
+            "body": "Steps to **reproduce**", "state": "open",
+            "user": { "id": 1, "login": "jane", "avatar_url": "https://a/1" },
+            "assignee": { "id": 2, "login": "bob", "avatar_url": "https://a/2" },
+            "labels": [{ "id": 1, "name": "bug" }, { "id": 2, "name": "P0" }],
+            "milestone": { "id": 3, "title": "2024.03" },
+            "repository_url": "https://api.github.com/repos/acme/api",
+            "created_at": "2024-03-01T09:00:00Z", "updated_at": "2024-03-02T09:00:00Z"
+        }))
+        .unwrap();
+
+        let ticket = github().map_github_issue(&issue, &repo_from_url(&issue.repository_url));
+
+        assert_eq!(ticket.key, "acme/api#42");
+        assert_eq!(ticket.status.category, StatusCategory::Todo);
+        assert_eq!(ticket.priority, Some(Priority::Highest));
+        assert_eq!(ticket.sprint.as_deref(), Some("2024.03"));
+        assert_eq!(ticket.assignee.unwrap().name, "bob");
+        assert_eq!(ticket.reporter.unwrap().name, "jane");
+        assert_eq!(ticket.description_markdown.as_deref(), Some("Steps to **reproduce**"));
+        assert!(issue.pull_request.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_github_search_terms() {
+        let query = TicketSearchQuery::new()
+            .with_text("refund")
+            .with_assignee("currentUser()")
+            .with_status_category(StatusCategory::Done)
+            .with_label("bug")
+            .with_sprint(SprintFilter::Specific("2024.03".to_string()))
+            .updated_since(Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap());
+
+        let provider = github();
+        let repos = provider.issue_search_repos(&query).await.unwrap();
+        let terms = provider.github_search_terms(&query, &repos);
+
+        assert_eq!(
+            terms,
+            "is:issue refund repo:acme/api repo:acme/web assignee:octocat is:closed label:\"bug\" \
+             milestone:\"2024.03\" updated:>=2024-03-01T00:00:00Z"
+        );
+
+        assert_eq!(provider.github_search_queries(&query, &repos), vec![terms]);
+
+        let scoped = TicketSearchQuery::new().with_project("acme/infra");
+        assert_eq!(provider.issue_search_repos(&scoped).await.unwrap(), vec!["acme/infra".to_string()]);
+    }
+
+    #[test]
+    fn test_github_search_queries_stay_under_length_limit() {
+        let repos: Vec<String> = (0..30).map(|i| format!("acme-corporation/service-{:02}", i)).collect();
+        let query = TicketSearchQuery::new().with_text("refund");
+
+        let queries = github().github_search_queries(&query, &repos);
+
+        assert!(queries.len() > 1);
+        assert!(queries.iter().all(|q| q.len() <= GITHUB_MAX_QUERY_LEN));
+        assert!(queries.iter().all(|q| q.starts_with("is:issue refund repo:")));
+        let covered = queries.iter().map(|q| q.matches("repo:").count()).sum::<usize>();
+        assert_eq!(covered, repos.len());
+    }
+
+    #[test]
+    fn test_map_gitlab_issue() {
+        let issue: GitLabIssue = serde_json::from_value(json!({
+            "id": 501, "iid": 7, "title": "Flaky checkout test", "description": null,
+            "state": "closed",
+            "author": { "id": 1, "username": "jane", "avatar_url": null },
+            "assignees": [{ "id": 2, "username": "bob", "avatar_url": null }],
+            "labels": ["priority::high", "flaky"],
+            "milestone": { "id": 3, "title": "Sprint 12" },
+            "weight": 3,
+            "references": { "short": "#7", "relative": "#7", "full": "acme/api#7" },
+            "created_at": "2024-03-01T09:00:00.000Z", "updated_at": "2024-03-04T09:00:00.000Z"
+        }))
+        .unwrap();
+
+        let ticket = gitlab().map_gitlab_issue(&issue, "acme/api");
+
+        assert_eq!(ticket.key, "acme/api#7");
+        assert_eq!(ticket.status.name, "Closed");
+        assert_eq!(ticket.status.category, StatusCategory::Done);
+        assert_eq!(ticket.priority, Some(Priority::High));
+        assert_eq!(ticket.sprint.as_deref(), Some("Sprint 12"));
+        assert_eq!(ticket.story_points, Some(3.0));
+        assert_eq!(ticket.assignee.unwrap().name, "bob");
+    }
+
+    #[test]
+    fn test_gitlab_issue_params() {
+        let query = TicketSearchQuery::new()
+            .with_text("checkout")
+            .with_reporter("jane")
+            .with_status("Open")
+            .with_label("flaky")
+            .with_label("ci")
+            .with_sprint(SprintFilter::Open);
+
+        let params = gitlab().gitlab_issue_params(&query);
+
+        assert!(params.contains(&("search", "checkout".to_string())));
+        assert!(params.contains(&("author_username", "jane".to_string())));
+        assert!(params.contains(&("state", "opened".to_string())));
+        assert!(params.contains(&("labels", "flaky,ci".to_string())));
+        assert!(params.contains(&("milestone", "Started".to_string())));
+    }
+
+    #[test]
+    fn test_matches_query_filters_priority_and_category() {
+        let issue: GitLabIssue = serde_json::from_value(json!({
+            "id": 1, "iid": 1, "title": "t", "state": "opened", "labels": ["P2", "doing"],
+            "created_at": "2024-03-01T09:00:00Z", "updated_at": "2024-03-01T09:00:00Z"
+        }))
+        .unwrap();
+        let ticket = gitlab().map_gitlab_issue(&issue, "acme/api");
+
+        assert_eq!(ticket.key, "acme/api#1");
+        assert!(matches_query(&ticket, &TicketSearchQuery::new().with_priority(Priority::Medium)));
+        assert!(!matches_query(&ticket, &TicketSearchQuery::new().with_priority(Priority::High)));
+        assert!(matches_query(&ticket, &TicketSearchQuery::new().with_status_category(StatusCategory::InProgress)));
+        assert!(!matches_query(&ticket, &TicketSearchQuery::new().with_status_category(StatusCategory::Todo)));
+    }
+
+    #[tokio::test]
+    async fn test_bitbucket_has_no_issues() {
+        let provider = GitProvider::new(GitConfig::bitbucket("acme", "user").with_token("t")).unwrap();
+        let result = provider.search(&TicketSearchQuery::new()).await;
+        assert!(matches!(result, Err(IntegrationError::ConfigError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_search_without_repositories() {
+        let provider = GitProvider::new(GitConfig::github("octocat").with_token("t")).unwrap();
+        assert!(provider.search(&TicketSearchQuery::new()).await.unwrap().is_empty());
+    }
+}
@@ -1,8 +1,22 @@
 //! Git Hosting Integration
 //!
 //! Provides Git hosting API clients (Bitbucket, GitHub, GitLab)
-//! implementing the PullRequestRepository trait with Strategy Pattern.
+//! implementing the PullRequestRepository trait with Strategy Pattern,
+//! and TicketRepository for GitHub Issues and GitLab Issues. Several
+//! connections are combined with CompositePullRequestRepository, and
+//! repositories can be discovered from an org, workspace or group. GitHub
+//! PRs can also be fetched through the GraphQL API.
 
+mod closed;
+mod comments;
+mod composite;
+mod diffstat;
+mod discovery;
+mod graphql;
+mod issues;
 mod provider;
 
+pub use composite::{qualified_repository, CompositePullRequestRepository};
+pub use discovery::RepoDiscovery;
+pub use graphql::GitHubApi;
 pub use provider::{GitProvider, GitConfig, GitProviderType};
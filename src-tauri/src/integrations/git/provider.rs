@@ -3,13 +3,21 @@
 //! Implements PullRequestRepository using Strategy Pattern for multiple providers.
 
 use async_trait::async_trait;
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-
+use std::future::Future;
+use std::sync::Arc;
+use tokio::sync::{Mutex, Semaphore};
+use tokio::task::JoinSet;
+
+use super::discovery::{DiscoveredRepos, RepoDiscovery};
+use super::graphql::GitHubApi;
+use crate::integrations::http_cache::{send_get, HttpCache, HttpResponse};
+use crate::integrations::rate_limit::{RateLimitTracker, CORE_RESOURCE, GRAPHQL_RESOURCE, SEARCH_RESOURCE};
 use crate::integrations::traits::{
-    ChecksStatus, IntegrationError, PrFilter, PrState, PullRequest, PullRequestRepository,
-    Reviewer, User,
+    ChecksStatus, ClosedPrFetchResult, DiffStats, IntegrationError, PrFetchResult, PrFilter, PrState, PullRequest,
+    PullRequestRepository, RepositoryError, ReviewComments, ReviewState, Reviewer, User,
 };
 
 /// Git provider type
@@ -21,6 +29,16 @@ pub enum GitProviderType {
     GitLab,
 }
 
+impl GitProviderType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GitProviderType::Bitbucket => "bitbucket",
+            GitProviderType::GitHub => "github",
+            GitProviderType::GitLab => "gitlab",
+        }
+    }
+}
+
 /// Git hosting configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GitConfig {
@@ -31,6 +49,33 @@ pub struct GitConfig {
     #[serde(skip)]
     pub token: Option<String>,
     pub repositories: Vec<String>,
+    /// Items requested per page when listing PRs (capped per provider)
+    #[serde(default = "default_page_size")]
+    pub page_size: usize,
+    /// Maximum number of repositories fetched at the same time
+    #[serde(default = "default_max_concurrency")]
+    pub max_concurrency: usize,
+    /// Time budget for fetching a single repository, in seconds
+    #[serde(default = "default_repo_timeout_secs")]
+    pub repo_timeout_secs: u64,
+    /// Discover repositories of an org, workspace or group in addition to `repositories`
+    #[serde(default)]
+    pub discovery: Option<RepoDiscovery>,
+    /// API used to fetch GitHub PRs; ignored by the other providers
+    #[serde(default)]
+    pub github_api: GitHubApi,
+}
+
+fn default_page_size() -> usize {
+    50
+}
+
+fn default_max_concurrency() -> usize {
+    8
+}
+
+fn default_repo_timeout_secs() -> u64 {
+    30
 }
 
 impl GitConfig {
@@ -42,6 +87,11 @@ impl GitConfig {
             username: username.to_string(),
             token: None,
             repositories: Vec::new(),
+            page_size: default_page_size(),
+            max_concurrency: default_max_concurrency(),
+            repo_timeout_secs: default_repo_timeout_secs(),
+            discovery: None,
+            github_api: GitHubApi::default(),
         }
     }
 
@@ -53,9 +103,40 @@ impl GitConfig {
             username: username.to_string(),
             token: None,
             repositories: Vec::new(),
+            page_size: default_page_size(),
+            max_concurrency: default_max_concurrency(),
+            repo_timeout_secs: default_repo_timeout_secs(),
+            discovery: None,
+            github_api: GitHubApi::default(),
+        }
+    }
+
+    pub fn gitlab(username: &str) -> Self {
+        Self {
+            provider: GitProviderType::GitLab,
+            base_url: None,
+            workspace: None,
+            username: username.to_string(),
+            token: None,
+            repositories: Vec::new(),
+            page_size: default_page_size(),
+            max_concurrency: default_max_concurrency(),
+            repo_timeout_secs: default_repo_timeout_secs(),
+            discovery: None,
+            github_api: GitHubApi::default(),
         }
     }
 
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = Some(base_url.to_string());
+        self
+    }
+
+    pub fn with_workspace(mut self, workspace: &str) -> Self {
+        self.workspace = Some(workspace.to_string());
+        self
+    }
+
     pub fn with_token(mut self, token: &str) -> Self {
         self.token = Some(token.to_string());
         self
@@ -66,9 +147,48 @@ impl GitConfig {
         self
     }
 
-    fn api_base_url(&self) -> String {
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = max_concurrency;
+        self
+    }
+
+    pub fn with_repo_timeout(mut self, secs: u64) -> Self {
+        self.repo_timeout_secs = secs;
+        self
+    }
+
+    pub fn with_discovery(mut self, discovery: RepoDiscovery) -> Self {
+        self.discovery = Some(discovery);
+        self
+    }
+
+    pub fn with_github_api(mut self, api: GitHubApi) -> Self {
+        self.github_api = api;
+        self
+    }
+
+    /// Page size clamped to what the provider accepts
+    pub(super) fn effective_page_size(&self) -> usize {
+        let max = match self.provider {
+            GitProviderType::Bitbucket => 50,
+            GitProviderType::GitHub | GitProviderType::GitLab => 100,
+        };
+        self.page_size.clamp(1, max)
+    }
+
+    pub(super) fn api_base_url(&self) -> String {
         if let Some(ref url) = self.base_url {
-            return url.trim_end_matches('/').to_string();
+            let url = url.trim_end_matches('/');
+            // Self-hosted GitLab is usually configured with the instance URL
+            if self.provider == GitProviderType::GitLab && !url.ends_with("/api/v4") {
+                return format!("{}/api/v4", url);
+            }
+            return url.to_string();
         }
         match self.provider {
             GitProviderType::Bitbucket => "https://api.bitbucket.org/2.0".to_string(),
@@ -76,14 +196,141 @@ impl GitConfig {
             GitProviderType::GitLab => "https://gitlab.com/api/v4".to_string(),
         }
     }
+
+    /// Full GitLab project path, prefixing the workspace (group) for bare names
+    pub(super) fn gitlab_project_path(&self, repo: &str) -> String {
+        match self.workspace {
+            Some(ref group) if !repo.contains('/') => format!("{}/{}", group.trim_matches('/'), repo),
+            _ => repo.to_string(),
+        }
+    }
+}
+
+/// Extract the `rel="next"` URL from a GitHub `Link` header
+pub(super) fn parse_next_link(link_header: &str) -> Option<String> {
+    link_header.split(',').find_map(|part| {
+        let mut segments = part.split(';');
+        let url = segments.next()?.trim();
+        let is_next = segments.any(|s| s.trim() == "rel=\"next\"");
+        if is_next {
+            Some(url.trim_start_matches('<').trim_end_matches('>').to_string())
+        } else {
+            None
+        }
+    })
+}
+
+/// Pick the pipeline that ran for the head commit, falling back to the latest one
+fn select_head_pipeline(pipelines: Vec<GitLabPipeline>, head_sha: Option<&str>) -> Option<GitLabPipeline> {
+    let position = head_sha
+        .and_then(|sha| pipelines.iter().position(|p| p.sha.as_deref() == Some(sha)))
+        .unwrap_or(0);
+    pipelines.into_iter().nth(position)
+}
+
+/// Run `fetch` for every repository with at most `max_concurrency` requests in flight.
+///
+/// Each repository gets its own `repo_timeout`. Results are returned in the order of
+/// `repos`, regardless of which fetch finishes first.
+pub(super) async fn fan_out<T, F, Fut>(
+    repos: &[String],
+    max_concurrency: usize,
+    repo_timeout: std::time::Duration,
+    fetch: F,
+) -> Vec<Result<T, IntegrationError>>
+where
+    T: Send + 'static,
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<T, IntegrationError>> + Send + 'static,
+{
+    let semaphore = Arc::new(Semaphore::new(max_concurrency.max(1)));
+    let mut tasks = JoinSet::new();
+
+    for (index, repo) in repos.iter().enumerate() {
+        let semaphore = semaphore.clone();
+        let future = fetch(repo.clone());
+        let repo = repo.clone();
+
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            let result = match tokio::time::timeout(repo_timeout, future).await {
+                Ok(result) => result,
+                Err(_) => Err(IntegrationError::Network(format!(
+                    "Timed out fetching {} after {}s", repo, repo_timeout.as_secs()
+                ))),
+            };
+            (index, result)
+        });
+    }
+
+    let mut results: Vec<Option<Result<T, IntegrationError>>> = repos.iter().map(|_| None).collect();
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok((index, result)) => results[index] = Some(result),
+            Err(e) => log::error!("GitProvider: repository fetch task failed: {}", e),
+        }
+    }
+
+    results
+        .into_iter()
+        .zip(repos)
+        .map(|(result, repo)| {
+            result.unwrap_or_else(|| {
+                Err(IntegrationError::ApiError(format!("Fetch for {} did not complete", repo)))
+            })
+        })
+        .collect()
+}
+
+/// Enrich `items` with at most `max_concurrency` in flight, keeping their order.
+///
+/// Stops at the first error, which aborts the enrichments still running.
+pub(super) async fn enrich_all<I, T, F, Fut>(
+    items: Vec<I>,
+    max_concurrency: usize,
+    enrich: F,
+) -> Result<Vec<T>, IntegrationError>
+where
+    T: Send + 'static,
+    F: Fn(I) -> Fut,
+    Fut: Future<Output = Result<T, IntegrationError>> + Send + 'static,
+{
+    let semaphore = Arc::new(Semaphore::new(max_concurrency.max(1)));
+    let mut tasks = JoinSet::new();
+    let count = items.len();
+
+    for (index, item) in items.into_iter().enumerate() {
+        let semaphore = semaphore.clone();
+        let future = enrich(item);
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            (index, future.await)
+        });
+    }
+
+    let mut results: Vec<Option<T>> = (0..count).map(|_| None).collect();
+    while let Some(joined) = tasks.join_next().await {
+        let (index, result) = joined
+            .map_err(|e| IntegrationError::ApiError(format!("PR enrichment task failed: {}", e)))?;
+        results[index] = Some(result?);
+    }
+
+    Ok(results.into_iter().flatten().collect())
 }
 
+/// PRs of one repository enriched at the same time; repositories already run in parallel
+const MAX_ENRICH_CONCURRENCY: usize = 4;
+
 /// Git provider client using Strategy Pattern
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct GitProvider {
     config: GitConfig,
     http_client: Client,
     stale_threshold: Duration,
+    rate_limiter: RateLimitTracker,
+    http_cache: Option<HttpCache>,
+    /// Shared between clones so concurrent fetches reuse one discovery
+    discovered: Arc<Mutex<Option<DiscoveredRepos>>>,
 }
 
 impl GitProvider {
@@ -99,10 +346,15 @@ impl GitProvider {
             .build()
             .map_err(|e| IntegrationError::Network(e.to_string()))?;
 
+        let rate_limiter = RateLimitTracker::new(config.provider.as_str());
+
         Ok(Self {
             config,
             http_client,
             stale_threshold: Duration::hours(48),
+            rate_limiter,
+            http_cache: None,
+            discovered: Arc::new(Mutex::new(None)),
         })
     }
 
@@ -111,6 +363,30 @@ impl GitProvider {
         self
     }
 
+    /// Share a rate limit tracker, e.g. with the background poller
+    pub fn with_rate_limiter(mut self, tracker: RateLimitTracker) -> Self {
+        self.rate_limiter = tracker;
+        self
+    }
+
+    pub fn rate_limiter(&self) -> &RateLimitTracker {
+        &self.rate_limiter
+    }
+
+    pub(super) fn config(&self) -> &GitConfig {
+        &self.config
+    }
+
+    pub(super) fn discovery_cache(&self) -> &Mutex<Option<DiscoveredRepos>> {
+        &self.discovered
+    }
+
+    /// Send conditional requests, replaying unchanged responses from the cache
+    pub fn with_http_cache(mut self, cache: HttpCache) -> Self {
+        self.http_cache = Some(cache);
+        self
+    }
+
     fn auth_header(&self) -> (&'static str, String) {
         let token = self.config.token.as_deref().unwrap_or("");
         match self.config.provider {
@@ -130,54 +406,236 @@ impl GitProvider {
         }
     }
 
-    fn is_stale(&self, updated_at: &chrono::DateTime<chrono::Utc>) -> bool {
+    /// Budget a GET is charged to; GitHub meters its search API separately
+    fn rate_limit_resource(&self, url: &str) -> &'static str {
+        if self.config.provider == GitProviderType::GitHub && url.contains("/search/") {
+            SEARCH_RESOURCE
+        } else {
+            CORE_RESOURCE
+        }
+    }
+
+    /// GET an API URL, failing fast while the rate limit is exhausted
+    pub(super) async fn get(&self, url: &str) -> Result<HttpResponse, IntegrationError> {
+        let resource = self.rate_limit_resource(url);
+        self.rate_limiter.check_resource(resource)?;
+
+        let (header_name, header_value) = self.auth_header();
+        let mut request = self.http_client
+            .get(url)
+            .header(header_name, header_value)
+            .header("User-Agent", "em-cockpit");
+        if self.config.provider == GitProviderType::GitHub {
+            request = request.header("Accept", "application/vnd.github+json");
+        }
+
+        let response = send_get(self.http_cache.as_ref(), url, request).await?;
+        if let Some(err) = self.rate_limiter.observe_resource(resource, response.status, &response.headers) {
+            return Err(err);
+        }
+
+        Ok(response)
+    }
+
+    /// POST a JSON body past the HTTP cache, e.g. a GraphQL query, charged to the GraphQL budget
+    pub(super) async fn post_json(&self, url: &str, body: &serde_json::Value) -> Result<(u16, String), IntegrationError> {
+        self.rate_limiter.check_resource(GRAPHQL_RESOURCE)?;
+
+        let (header_name, header_value) = self.auth_header();
+        let response = self.http_client
+            .post(url)
+            .header(header_name, header_value)
+            .header("User-Agent", "em-cockpit")
+            .json(body)
+            .send()
+            .await?;
+
+        let status = response.status().as_u16();
+        if let Some(err) = self.rate_limiter.observe_resource(GRAPHQL_RESOURCE, status, response.headers()) {
+            return Err(err);
+        }
+
+        Ok((status, response.text().await?))
+    }
+
+    /// GET and parse an enrichment resource.
+    ///
+    /// A missing resource or an unexpected body yields `None`, so the PR is
+    /// shown without it. Rate limits, auth failures and other errors are
+    /// returned, since empty data would look real.
+    pub(super) async fn get_optional<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<Option<T>, IntegrationError> {
+        let response = self.get(url).await?;
+        match response.status {
+            200 => Ok(response.json().ok()),
+            404 => Ok(None),
+            status => Err(self.enrichment_error(status, url)),
+        }
+    }
+
+    /// Error for an enrichment resource that answered neither 200 nor 404
+    pub(super) fn enrichment_error(&self, status: u16, url: &str) -> IntegrationError {
+        match status {
+            401 | 403 => IntegrationError::Auth(format!("Access denied to {}", url)),
+            status => IntegrationError::ApiError(format!("Status {} for {}", status, url)),
+        }
+    }
+
+    pub(super) fn is_stale(&self, updated_at: &chrono::DateTime<chrono::Utc>) -> bool {
         Utc::now().signed_duration_since(*updated_at) > self.stale_threshold
     }
 
-    async fn fetch_bitbucket_prs(&self, filter: &PrFilter) -> Result<Vec<PullRequest>, IntegrationError> {
+    /// Map a non-200 listing response to the error reported for that repository
+    pub(super) fn repo_status_error(&self, status: u16, repo: &str) -> IntegrationError {
+        match status {
+            401 | 403 => IntegrationError::Auth(format!("Access denied to {}", repo)),
+            404 => IntegrationError::NotFound(format!("Repository {} not found", repo)),
+            429 => IntegrationError::RateLimit { reset_at: None },
+            status => IntegrationError::ApiError(format!("Status {} for {}", status, repo)),
+        }
+    }
+
+    /// Fetch open PRs of all repositories concurrently, merged in the order of `repos`
+    async fn fetch_all_repos(&self, repos: &[String], filter: &PrFilter) -> Result<PrFetchResult, IntegrationError> {
+        let results = fan_out(
+            repos,
+            self.config.max_concurrency,
+            std::time::Duration::from_secs(self.config.repo_timeout_secs),
+            |repo| {
+                let provider = self.clone();
+                let filter = filter.clone();
+                async move { provider.fetch_repo_prs(&repo, &filter).await }
+            },
+        )
+        .await;
+
+        Self::merge_repo_results(repos, results, filter.limit)
+    }
+
+    /// Merge per-repository results in the order of `repos`.
+    ///
+    /// Failed repositories are reported alongside the PRs of the others. If every
+    /// repository fails, the first error is returned instead.
+    pub(super) fn merge_repo_results(
+        repos: &[String],
+        results: Vec<Result<Vec<PullRequest>, IntegrationError>>,
+        limit: usize,
+    ) -> Result<PrFetchResult, IntegrationError> {
+        let (mut prs, errors) = Self::collect_repo_results(repos, results)?;
+        prs.truncate(limit);
+        Ok(PrFetchResult { prs, errors })
+    }
+
+    /// Flatten per-repository results, with an error for each failed repository
+    pub(super) fn collect_repo_results<T>(
+        repos: &[String],
+        results: Vec<Result<Vec<T>, IntegrationError>>,
+    ) -> Result<(Vec<T>, Vec<RepositoryError>), IntegrationError> {
+        let mut items = Vec::new();
+        let mut errors = Vec::new();
+        let mut first_error = None;
+        let mut succeeded = 0;
+
+        for (repo, result) in repos.iter().zip(results) {
+            match result {
+                Ok(fetched) => {
+                    succeeded += 1;
+                    items.extend(fetched);
+                }
+                Err(e) => {
+                    log::warn!("GitProvider: failed to fetch {}: {}", repo, e);
+                    errors.push(RepositoryError::new(repo, &e));
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        if succeeded == 0 {
+            if let Some(e) = first_error {
+                return Err(e);
+            }
+        }
+
+        Ok((items, errors))
+    }
+
+    async fn fetch_repo_prs(&self, repo: &str, filter: &PrFilter) -> Result<Vec<PullRequest>, IntegrationError> {
+        match self.config.provider {
+            GitProviderType::Bitbucket => self.fetch_bitbucket_prs(repo, filter).await,
+            GitProviderType::GitHub => self.fetch_github_prs(repo, filter).await,
+            GitProviderType::GitLab => self.fetch_gitlab_prs(repo, filter).await,
+        }
+    }
+
+    async fn fetch_bitbucket_prs(&self, repo: &str, filter: &PrFilter) -> Result<Vec<PullRequest>, IntegrationError> {
         let workspace = self.config.workspace.as_ref()
             .ok_or_else(|| IntegrationError::ConfigError("Workspace required for Bitbucket".to_string()))?;
-        
-        let repos = if filter.repositories.is_empty() {
-            &self.config.repositories
-        } else {
-            &filter.repositories
-        };
 
-        let mut all_prs = Vec::new();
-        let (header_name, header_value) = self.auth_header();
+        let mut prs = Vec::new();
+        let mut next_url = Some(format!(
+            "{}/repositories/{}/{}/pullrequests?state=OPEN&pagelen={}&fields={}",
+            self.config.api_base_url(), workspace, repo, self.config.effective_page_size(),
+            BITBUCKET_LIST_FIELDS
+        ));
+
+        while let Some(url) = next_url.take() {
+            let response = self.get(&url).await?;
+            if response.status != 200 {
+                return Err(self.repo_status_error(response.status, repo));
+            }
 
-        for repo in repos {
-            let url = format!(
-                "{}/repositories/{}/{}/pullrequests?state=OPEN",
-                self.config.api_base_url(), workspace, repo
-            );
+            let result: BitbucketPrList = response.json()?;
+            prs.extend(result.values.into_iter().filter(|pr| !filter.stale_only || self.is_stale(&pr.updated_on)));
 
-            let response = self.http_client
-                .get(&url)
-                .header(header_name, &header_value)
-                .send()
-                .await?;
-
-            if response.status().as_u16() == 200 {
-                let result: BitbucketPrList = response
-                    .json()
-                    .await
-                    .map_err(|e| IntegrationError::ParseError(e.to_string()))?;
-
-                for pr in result.values {
-                    let mapped = self.map_bitbucket_pr(&pr, repo);
-                    if !filter.stale_only || mapped.is_stale {
-                        all_prs.push(mapped);
-                    }
-                }
+            if prs.len() >= filter.limit {
+                prs.truncate(filter.limit);
+                break;
             }
+            next_url = result.next;
         }
 
-        Ok(all_prs.into_iter().take(filter.limit).collect())
+        enrich_all(prs, MAX_ENRICH_CONCURRENCY, |pr| {
+            let provider = self.clone();
+            let workspace = workspace.clone();
+            let repo = repo.to_string();
+            async move {
+                let id = pr.id.to_string();
+                let (checks, totals) = tokio::try_join!(
+                    provider.fetch_bitbucket_checks(&workspace, &repo, pr.id),
+                    provider.fetch_diff_totals(&repo, &id),
+                )?;
+                let mut mapped = provider.map_bitbucket_pr(&pr, &repo, checks);
+                mapped.diff_stats = totals;
+                Ok(mapped)
+            }
+        })
+        .await
+    }
+
+    /// Combined commit statuses of the PR's head commit
+    async fn fetch_bitbucket_checks(&self, workspace: &str, repo: &str, id: i64) -> Result<ChecksStatus, IntegrationError> {
+        let url = format!(
+            "{}/repositories/{}/{}/pullrequests/{}/statuses?pagelen=100",
+            self.config.api_base_url(), workspace, repo, id
+        );
+
+        Ok(self.get_optional::<BitbucketStatusList>(&url)
+            .await?
+            .map(|list| ChecksStatus::combine(
+                list.values.iter().map(|s| self.map_bitbucket_status(&s.state))
+            ))
+            .unwrap_or(ChecksStatus::None))
+    }
+
+    fn map_bitbucket_status(&self, state: &str) -> ChecksStatus {
+        match state {
+            "SUCCESSFUL" => ChecksStatus::Pass,
+            "FAILED" | "STOPPED" => ChecksStatus::Fail,
+            "INPROGRESS" => ChecksStatus::Running,
+            _ => ChecksStatus::None,
+        }
     }
 
-    fn map_bitbucket_pr(&self, pr: &BitbucketPr, repo: &str) -> PullRequest {
+    pub(super) fn map_bitbucket_pr(&self, pr: &BitbucketPr, repo: &str, checks: ChecksStatus) -> PullRequest {
         let updated_at = pr.updated_on;
         PullRequest {
             id: pr.id.to_string(),
@@ -185,74 +643,225 @@ impl GitProvider {
             title: pr.title.clone(),
             description: pr.description.clone(),
             state: PrState::Open,
-            author: User {
-                id: pr.author.uuid.clone(),
-                name: pr.author.display_name.clone(),
-                email: None,
-                avatar_url: pr.author.links.avatar.as_ref().map(|l| l.href.clone()),
-            },
-            reviewers: pr.reviewers.iter().map(|r| Reviewer {
-                user: User {
-                    id: r.uuid.clone(),
-                    name: r.display_name.clone(),
-                    email: None,
-                    avatar_url: None,
-                },
-                approved: false,
-            }).collect(),
+            author: self.map_bitbucket_user(&pr.author),
+            reviewers: self.map_bitbucket_reviewers(pr),
             source_branch: pr.source.branch.name.clone(),
             target_branch: pr.destination.branch.name.clone(),
-            checks_status: ChecksStatus::None,
+            checks_status: checks,
             is_stale: self.is_stale(&updated_at),
             updated_at,
             created_at: pr.created_on,
             url: pr.links.html.href.clone(),
+            labels: vec![],
+            diff_stats: None,
+            connection: None,
         }
     }
 
-    async fn fetch_github_prs(&self, filter: &PrFilter) -> Result<Vec<PullRequest>, IntegrationError> {
-        let repos = if filter.repositories.is_empty() {
-            &self.config.repositories
-        } else {
-            &filter.repositories
-        };
+    /// Merge requested reviewers with the participants who already reviewed.
+    ///
+    /// Participants without a verdict are listed as commenters, except for
+    /// requested reviewers who stay pending.
+    fn map_bitbucket_reviewers(&self, pr: &BitbucketPr) -> Vec<Reviewer> {
+        let mut reviewers: Vec<Reviewer> = pr.reviewers.iter()
+            .filter(|r| r.uuid != pr.author.uuid)
+            .map(|r| Reviewer::new(self.map_bitbucket_user(r), ReviewState::Pending))
+            .collect();
+
+        for participant in &pr.participants {
+            if participant.user.uuid == pr.author.uuid {
+                continue;
+            }
 
-        let mut all_prs = Vec::new();
-        let (header_name, header_value) = self.auth_header();
+            let state = match participant.state.as_deref() {
+                Some("approved") => ReviewState::Approved,
+                Some("changes_requested") => ReviewState::ChangesRequested,
+                _ if participant.approved => ReviewState::Approved,
+                _ if participant.role == "REVIEWER" => ReviewState::Pending,
+                _ => ReviewState::Commented,
+            };
+
+            match reviewers.iter_mut().find(|r| r.user.id == participant.user.uuid) {
+                Some(existing) => existing.state = state,
+                None => reviewers.push(Reviewer::new(self.map_bitbucket_user(&participant.user), state)),
+            }
+        }
 
-        for repo in repos {
-            let url = format!(
-                "{}/repos/{}/pulls?state=open",
-                self.config.api_base_url(), repo
-            );
+        reviewers
+    }
+
+    pub(super) fn map_bitbucket_user(&self, user: &BitbucketUser) -> User {
+        User {
+            id: user.uuid.clone(),
+            name: user.display_name.clone(),
+            email: None,
+            avatar_url: user.links.avatar.as_ref().map(|l| l.href.clone()),
+        }
+    }
+
+    async fn fetch_github_prs(&self, repo: &str, filter: &PrFilter) -> Result<Vec<PullRequest>, IntegrationError> {
+        let mut prs = Vec::new();
+        let mut next_url = Some(format!(
+            "{}/repos/{}/pulls?state=open&per_page={}",
+            self.config.api_base_url(), repo, self.config.effective_page_size()
+        ));
+
+        while let Some(url) = next_url.take() {
+            let response = self.get(&url).await?;
+            if response.status != 200 {
+                return Err(self.repo_status_error(response.status, repo));
+            }
+
+            next_url = response.header("link").and_then(parse_next_link);
+
+            let page: Vec<GitHubPr> = response.json()?;
+            prs.extend(page.into_iter().filter(|pr| !filter.stale_only || self.is_stale(&pr.updated_at)));
+
+            if prs.len() >= filter.limit {
+                prs.truncate(filter.limit);
+                break;
+            }
+        }
+
+        enrich_all(prs, MAX_ENRICH_CONCURRENCY, |pr| {
+            let provider = self.clone();
+            let repo = repo.to_string();
+            async move {
+                let number = pr.number.to_string();
+                let (reviews, checks, totals) = tokio::try_join!(
+                    provider.fetch_github_reviews(&repo, pr.number),
+                    provider.fetch_github_checks(&repo, &pr.head.sha),
+                    provider.fetch_diff_totals(&repo, &number),
+                )?;
+                let mut mapped = provider.map_github_pr(&pr, &repo, &reviews, checks);
+                mapped.diff_stats = totals;
+                Ok(mapped)
+            }
+        })
+        .await
+    }
+
+    /// Submitted reviews of a PR in chronological order (empty when unavailable)
+    pub(super) async fn fetch_github_reviews(&self, repo: &str, number: i64) -> Result<Vec<GitHubReview>, IntegrationError> {
+        let url = format!(
+            "{}/repos/{}/pulls/{}/reviews?per_page=100",
+            self.config.api_base_url(), repo, number
+        );
+
+        Ok(self.get_optional(&url).await?.unwrap_or_default())
+    }
+
+    /// Check runs and legacy commit statuses of a commit, combined.
+    ///
+    /// Apps like GitHub Actions report check runs while older CI services still
+    /// post commit statuses, so both are needed for the full picture.
+    async fn fetch_github_checks(&self, repo: &str, sha: &str) -> Result<ChecksStatus, IntegrationError> {
+        let base = format!("{}/repos/{}/commits/{}", self.config.api_base_url(), repo, sha);
+
+        let mut statuses = Vec::new();
+
+        let runs_url = format!("{}/check-runs?per_page=100", base);
+        if let Some(runs) = self.get_optional::<GitHubCheckRuns>(&runs_url).await? {
+            statuses.extend(runs.check_runs.iter().map(|run| self.map_github_check_run(run)));
+        }
+
+        let status_url = format!("{}/status", base);
+        if let Some(combined) = self.get_optional::<GitHubCombinedStatus>(&status_url).await? {
+            statuses.push(self.map_github_combined_status(&combined));
+        }
+
+        Ok(ChecksStatus::combine(statuses))
+    }
+
+    fn map_github_check_run(&self, run: &GitHubCheckRun) -> ChecksStatus {
+        if run.status != "completed" {
+            return ChecksStatus::Running;
+        }
+
+        match run.conclusion.as_deref() {
+            Some("success") | Some("neutral") | Some("skipped") => ChecksStatus::Pass,
+            Some("failure") | Some("timed_out") | Some("cancelled") | Some("action_required")
+            | Some("startup_failure") => ChecksStatus::Fail,
+            _ => ChecksStatus::None,
+        }
+    }
 
-            let response = self.http_client
-                .get(&url)
-                .header(header_name, &header_value)
-                .header("Accept", "application/vnd.github+json")
-                .header("User-Agent", "em-cockpit")
-                .send()
-                .await?;
-
-            if response.status().as_u16() == 200 {
-                let prs: Vec<GitHubPr> = response
-                    .json()
-                    .await
-                    .map_err(|e| IntegrationError::ParseError(e.to_string()))?;
-
-                for pr in prs {
-                    let mapped = self.map_github_pr(&pr, repo);
-                    if !filter.stale_only || mapped.is_stale {
-                        all_prs.push(mapped);
+    fn map_github_combined_status(&self, combined: &GitHubCombinedStatus) -> ChecksStatus {
+        // The combined state is "pending" when no status was ever posted
+        if combined.total_count == 0 {
+            return ChecksStatus::None;
+        }
+
+        match combined.state.as_str() {
+            "success" => ChecksStatus::Pass,
+            "failure" | "error" => ChecksStatus::Fail,
+            "pending" => ChecksStatus::Running,
+            _ => ChecksStatus::None,
+        }
+    }
+
+    /// Reduce the review history to the current state of each reviewer.
+    ///
+    /// Comments do not override an earlier approval or change request, while a
+    /// dismissal does. Requested reviewers are pending even if they reviewed
+    /// before, since GitHub only lists them again after a re-request.
+    pub(super) fn map_github_reviewers(
+        &self,
+        author_id: i64,
+        requested_reviewers: &[GitHubUser],
+        reviews: &[GitHubReview],
+    ) -> Vec<Reviewer> {
+        let mut reviewers: Vec<Reviewer> = Vec::new();
+
+        for review in reviews {
+            let Some(ref user) = review.user else { continue };
+            if user.id == author_id {
+                continue;
+            }
+
+            let state = match review.state.as_str() {
+                "APPROVED" => ReviewState::Approved,
+                "CHANGES_REQUESTED" => ReviewState::ChangesRequested,
+                "COMMENTED" | "DISMISSED" => ReviewState::Commented,
+                _ => continue,
+            };
+
+            match reviewers.iter_mut().find(|r| r.user.id == user.id.to_string()) {
+                Some(existing) => {
+                    if review.state != "COMMENTED" || !existing.state.is_decisive() {
+                        existing.state = state;
                     }
                 }
+                None => reviewers.push(Reviewer::new(self.map_github_user(user), state)),
             }
         }
 
-        Ok(all_prs.into_iter().take(filter.limit).collect())
+        for requested in requested_reviewers {
+            match reviewers.iter_mut().find(|r| r.user.id == requested.id.to_string()) {
+                Some(existing) => existing.state = ReviewState::Pending,
+                None => reviewers.push(Reviewer::new(self.map_github_user(requested), ReviewState::Pending)),
+            }
+        }
+
+        reviewers
     }
 
-    fn map_github_pr(&self, pr: &GitHubPr, repo: &str) -> PullRequest {
+    pub(super) fn map_github_user(&self, user: &GitHubUser) -> User {
+        User {
+            id: user.id.to_string(),
+            name: user.login.clone(),
+            email: None,
+            avatar_url: Some(user.avatar_url.clone()),
+        }
+    }
+
+    pub(super) fn map_github_pr(
+        &self,
+        pr: &GitHubPr,
+        repo: &str,
+        reviews: &[GitHubReview],
+        checks: ChecksStatus,
+    ) -> PullRequest {
         let updated_at = pr.updated_at;
         PullRequest {
             id: pr.number.to_string(),
@@ -260,28 +869,166 @@ impl GitProvider {
             title: pr.title.clone(),
             description: pr.body.clone(),
             state: if pr.draft { PrState::Draft } else { PrState::Open },
-            author: User {
-                id: pr.user.id.to_string(),
-                name: pr.user.login.clone(),
-                email: None,
-                avatar_url: Some(pr.user.avatar_url.clone()),
-            },
-            reviewers: pr.requested_reviewers.iter().map(|r| Reviewer {
-                user: User {
-                    id: r.id.to_string(),
-                    name: r.login.clone(),
-                    email: None,
-                    avatar_url: Some(r.avatar_url.clone()),
-                },
-                approved: false,
-            }).collect(),
+            author: self.map_github_user(&pr.user),
+            reviewers: self.map_github_reviewers(pr.user.id, &pr.requested_reviewers, reviews),
             source_branch: pr.head.ref_name.clone(),
             target_branch: pr.base.ref_name.clone(),
-            checks_status: ChecksStatus::None,
+            checks_status: checks,
             is_stale: self.is_stale(&updated_at),
             updated_at,
             created_at: pr.created_at,
             url: pr.html_url.clone(),
+            labels: pr.labels.iter().map(|l| l.name.clone()).collect(),
+            diff_stats: pr.diff_stats(),
+            connection: None,
+        }
+    }
+
+    async fn fetch_gitlab_prs(&self, repo: &str, filter: &PrFilter) -> Result<Vec<PullRequest>, IntegrationError> {
+        let mut prs = Vec::new();
+        let project = self.config.gitlab_project_path(repo);
+        let mut page = Some(1);
+
+        while let Some(current) = page {
+            let url = format!(
+                "{}/projects/{}/merge_requests?state=opened&per_page={}&page={}",
+                self.config.api_base_url(),
+                urlencoding::encode(&project),
+                self.config.effective_page_size(),
+                current
+            );
+
+            let response = self.get(&url).await?;
+            if response.status != 200 {
+                return Err(self.repo_status_error(response.status, repo));
+            }
+
+            // GitLab sends an empty X-Next-Page header on the last page
+            page = response
+                .header("x-next-page")
+                .and_then(|v| v.trim().parse::<usize>().ok());
+
+            let mrs: Vec<GitLabMr> = response.json()?;
+            prs.extend(mrs.into_iter().filter(|mr| !filter.stale_only || self.is_stale(&mr.updated_at)));
+
+            if prs.len() >= filter.limit {
+                prs.truncate(filter.limit);
+                break;
+            }
+        }
+
+        enrich_all(prs, MAX_ENRICH_CONCURRENCY, |mr| {
+            let provider = self.clone();
+            let project = project.clone();
+            let repo = repo.to_string();
+            async move {
+                let iid = mr.iid.to_string();
+                let (approved_by, pipeline, totals) = tokio::try_join!(
+                    provider.fetch_gitlab_approvals(&project, mr.iid),
+                    provider.fetch_gitlab_pipeline(&project, &mr),
+                    provider.fetch_diff_totals(&repo, &iid),
+                )?;
+                let mut mapped = provider.map_gitlab_mr(&mr, &repo, &approved_by, pipeline.as_ref());
+                mapped.diff_stats = totals;
+                Ok(mapped)
+            }
+        })
+        .await
+    }
+
+    /// Users who approved a merge request (empty when approvals are unavailable)
+    pub(super) async fn fetch_gitlab_approvals(&self, project: &str, iid: i64) -> Result<Vec<GitLabUser>, IntegrationError> {
+        let url = format!(
+            "{}/projects/{}/merge_requests/{}/approvals",
+            self.config.api_base_url(), urlencoding::encode(project), iid
+        );
+
+        Ok(self.get_optional::<GitLabApprovals>(&url)
+            .await?
+            .map(|a| a.approved_by.into_iter().map(|entry| entry.user).collect())
+            .unwrap_or_default())
+    }
+
+    /// Latest pipeline for the head commit of a merge request, if any
+    async fn fetch_gitlab_pipeline(&self, project: &str, mr: &GitLabMr) -> Result<Option<GitLabPipeline>, IntegrationError> {
+        // Only the single merge request endpoint embeds the head pipeline
+        if let Some(ref pipeline) = mr.head_pipeline {
+            return Ok(Some(pipeline.clone()));
+        }
+
+        let url = format!(
+            "{}/projects/{}/merge_requests/{}/pipelines?per_page=20",
+            self.config.api_base_url(), urlencoding::encode(project), mr.iid
+        );
+
+        let pipelines: Vec<GitLabPipeline> = self.get_optional(&url).await?.unwrap_or_default();
+        Ok(select_head_pipeline(pipelines, mr.sha.as_deref()))
+    }
+
+    pub(super) fn map_gitlab_mr(
+        &self,
+        mr: &GitLabMr,
+        repo: &str,
+        approved_by: &[GitLabUser],
+        pipeline: Option<&GitLabPipeline>,
+    ) -> PullRequest {
+        let updated_at = mr.updated_at;
+
+        let mut reviewers: Vec<Reviewer> = mr.reviewers.iter().map(|r| {
+            let state = if approved_by.iter().any(|a| a.id == r.id) {
+                ReviewState::Approved
+            } else {
+                ReviewState::Pending
+            };
+            Reviewer::new(self.map_gitlab_user(r), state)
+        }).collect();
+
+        // Approvers are not always listed as reviewers
+        for approver in approved_by {
+            if !mr.reviewers.iter().any(|r| r.id == approver.id) {
+                reviewers.push(Reviewer::new(self.map_gitlab_user(approver), ReviewState::Approved));
+            }
+        }
+
+        PullRequest {
+            id: mr.iid.to_string(),
+            repository: repo.to_string(),
+            title: mr.title.clone(),
+            description: mr.description.clone().filter(|d| !d.is_empty()),
+            state: if mr.is_draft() { PrState::Draft } else { PrState::Open },
+            author: self.map_gitlab_user(&mr.author),
+            reviewers,
+            source_branch: mr.source_branch.clone(),
+            target_branch: mr.target_branch.clone(),
+            checks_status: pipeline
+                .map(|p| self.map_gitlab_pipeline_status(&p.status))
+                .unwrap_or(ChecksStatus::None),
+            is_stale: self.is_stale(&updated_at),
+            updated_at,
+            created_at: mr.created_at,
+            url: mr.web_url.clone(),
+            labels: mr.labels.clone(),
+            diff_stats: None,
+            connection: None,
+        }
+    }
+
+    pub(super) fn map_gitlab_user(&self, user: &GitLabUser) -> User {
+        User {
+            id: user.id.to_string(),
+            name: user.username.clone(),
+            email: None,
+            avatar_url: user.avatar_url.clone(),
+        }
+    }
+
+    fn map_gitlab_pipeline_status(&self, status: &str) -> ChecksStatus {
+        match status {
+            "success" => ChecksStatus::Pass,
+            "failed" => ChecksStatus::Fail,
+            "created" | "waiting_for_resource" | "preparing" | "pending" | "running"
+            | "scheduled" => ChecksStatus::Running,
+            _ => ChecksStatus::None,
         }
     }
 }
@@ -289,8 +1036,10 @@ impl GitProvider {
 #[async_trait]
 impl PullRequestRepository for GitProvider {
     async fn find_by_id(&self, repo: &str, id: &str) -> Result<PullRequest, IntegrationError> {
-        let (header_name, header_value) = self.auth_header();
-        
+        if self.config.uses_graphql() {
+            return self.find_graphql_pr(repo, id).await;
+        }
+
         let url = match self.config.provider {
             GitProviderType::Bitbucket => {
                 let workspace = self.config.workspace.as_ref()
@@ -303,93 +1052,154 @@ impl PullRequestRepository for GitProvider {
             }
             GitProviderType::GitLab => {
                 format!("{}/projects/{}/merge_requests/{}", 
-                    self.config.api_base_url(), urlencoding::encode(repo), id)
+                    self.config.api_base_url(),
+                    urlencoding::encode(&self.config.gitlab_project_path(repo)), id)
             }
         };
 
-        let response = self.http_client
-            .get(&url)
-            .header(header_name, header_value)
-            .header("User-Agent", "em-cockpit")
-            .send()
-            .await?;
+        let response = self.get(&url).await?;
 
-        match response.status().as_u16() {
+        match response.status {
             200 => {
-                match self.config.provider {
+                // GitHub reports line totals on the PR itself, GitLab only a file count
+                let (mut pr, reported_files) = match self.config.provider {
                     GitProviderType::Bitbucket => {
-                        let pr: BitbucketPr = response.json().await
-                            .map_err(|e| IntegrationError::ParseError(e.to_string()))?;
-                        Ok(self.map_bitbucket_pr(&pr, repo))
+                        let pr: BitbucketPr = response.json()?;
+                        let workspace = self.config.workspace.as_deref().unwrap_or_default();
+                        let checks = self.fetch_bitbucket_checks(workspace, repo, pr.id).await?;
+                        (self.map_bitbucket_pr(&pr, repo, checks), None)
                     }
                     GitProviderType::GitHub => {
-                        let pr: GitHubPr = response.json().await
-                            .map_err(|e| IntegrationError::ParseError(e.to_string()))?;
-                        Ok(self.map_github_pr(&pr, repo))
+                        let pr: GitHubPr = response.json()?;
+                        let reviews = self.fetch_github_reviews(repo, pr.number).await?;
+                        let checks = self.fetch_github_checks(repo, &pr.head.sha).await?;
+                        (self.map_github_pr(&pr, repo, &reviews, checks), None)
                     }
                     GitProviderType::GitLab => {
-                        Err(IntegrationError::ApiError("GitLab not fully implemented".to_string()))
+                        let mr: GitLabMr = response.json()?;
+                        let project = self.config.gitlab_project_path(repo);
+                        let approved_by = self.fetch_gitlab_approvals(&project, mr.iid).await?;
+                        let pipeline = self.fetch_gitlab_pipeline(&project, &mr).await?;
+                        (self.map_gitlab_mr(&mr, repo, &approved_by, pipeline.as_ref()), mr.changed_files())
                     }
-                }
+                };
+                self.attach_diff_stats(&mut pr, reported_files).await?;
+                Ok(pr)
             }
             401 => Err(IntegrationError::Auth("Invalid credentials".to_string())),
             404 => Err(IntegrationError::NotFound(format!("PR {} not found", id))),
-            429 => Err(IntegrationError::RateLimit),
             status => Err(IntegrationError::ApiError(format!("Status: {}", status))),
         }
     }
 
+    async fn get_review_comments(&self, repo: &str, id: &str, limit: usize) -> Result<ReviewComments, IntegrationError> {
+        let comments = match self.config.provider {
+            GitProviderType::Bitbucket => self.fetch_bitbucket_review_comments(repo, id).await?,
+            GitProviderType::GitHub => self.fetch_github_review_comments(repo, id).await?,
+            GitProviderType::GitLab => self.fetch_gitlab_review_comments(repo, id).await?,
+        };
+        Ok(ReviewComments::from_comments(comments, limit))
+    }
+
+    async fn get_closed_prs(
+        &self,
+        since: DateTime<Utc>,
+        filter: &PrFilter,
+    ) -> Result<ClosedPrFetchResult, IntegrationError> {
+        self.fetch_closed_prs(since, filter).await
+    }
+
     async fn find_by_reviewer(&self, user_id: &str, filter: &PrFilter) -> Result<Vec<PullRequest>, IntegrationError> {
         let all_prs = self.get_open_prs(filter).await?;
         
         Ok(all_prs
             .into_iter()
-            .filter(|pr| pr.reviewers.iter().any(|r| r.user.id == user_id || r.user.name == user_id))
+            .filter(|pr| pr.reviewers.iter().any(|r| r.is_user(user_id)))
             .collect())
     }
 
     async fn get_open_prs(&self, filter: &PrFilter) -> Result<Vec<PullRequest>, IntegrationError> {
-        match self.config.provider {
-            GitProviderType::Bitbucket => self.fetch_bitbucket_prs(filter).await,
-            GitProviderType::GitHub => self.fetch_github_prs(filter).await,
-            GitProviderType::GitLab => {
-                Err(IntegrationError::ApiError("GitLab not fully implemented".to_string()))
-            }
+        Ok(self.fetch_open_prs(filter).await?.prs)
+    }
+
+    async fn fetch_open_prs(&self, filter: &PrFilter) -> Result<PrFetchResult, IntegrationError> {
+        if self.config.provider == GitProviderType::Bitbucket && self.config.workspace.is_none() {
+            return Err(IntegrationError::ConfigError("Workspace required for Bitbucket".to_string()));
+        }
+
+        let repos = if filter.repositories.is_empty() {
+            self.repositories().await?
+        } else {
+            filter.repositories.clone()
+        };
+
+        if self.config.uses_graphql() {
+            return self.fetch_graphql_prs(&repos, filter).await;
         }
+        self.fetch_all_repos(&repos, filter).await
     }
 }
 
 // ===== Bitbucket API Types =====
 
+/// The listing endpoint omits reviewers and participants unless asked for
+pub(super) const BITBUCKET_LIST_FIELDS: &str = "%2Bvalues.reviewers,%2Bvalues.participants";
+
 #[derive(Debug, Deserialize)]
-struct BitbucketPrList {
-    values: Vec<BitbucketPr>,
+pub(super) struct BitbucketPrList {
+    pub(super) values: Vec<BitbucketPr>,
+    pub(super) next: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
-struct BitbucketPr {
-    id: i64,
+pub(super) struct BitbucketPr {
+    pub(super) id: i64,
     title: String,
     description: Option<String>,
-    author: BitbucketUser,
+    pub(super) author: BitbucketUser,
     #[serde(default)]
     reviewers: Vec<BitbucketUser>,
+    #[serde(default)]
+    participants: Vec<BitbucketParticipant>,
     source: BitbucketRef,
     destination: BitbucketRef,
     created_on: chrono::DateTime<chrono::Utc>,
-    updated_on: chrono::DateTime<chrono::Utc>,
+    pub(super) updated_on: chrono::DateTime<chrono::Utc>,
     links: BitbucketLinks,
+    /// OPEN, MERGED, DECLINED or SUPERSEDED
+    pub(super) state: Option<String>,
+    /// Who merged or declined the PR
+    pub(super) closed_by: Option<BitbucketUser>,
 }
 
 #[derive(Debug, Deserialize)]
-struct BitbucketUser {
+pub(super) struct BitbucketUser {
     uuid: String,
     display_name: String,
     links: BitbucketUserLinks,
 }
 
 #[derive(Debug, Deserialize)]
-struct BitbucketUserLinks {
+struct BitbucketParticipant {
+    user: BitbucketUser,
+    role: String,
+    #[serde(default)]
+    approved: bool,
+    state: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketStatusList {
+    values: Vec<BitbucketStatus>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketStatus {
+    state: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct BitbucketUserLinks {
     avatar: Option<BitbucketLink>,
 }
 
@@ -409,18 +1219,18 @@ struct BitbucketLinks {
 }
 
 #[derive(Debug, Deserialize)]
-struct BitbucketLink {
+pub(super) struct BitbucketLink {
     href: String,
 }
 
 // ===== GitHub API Types =====
 
 #[derive(Debug, Deserialize)]
-struct GitHubPr {
-    number: i64,
+pub(super) struct GitHubPr {
+    pub(super) number: i64,
     title: String,
     body: Option<String>,
-    user: GitHubUser,
+    pub(super) user: GitHubUser,
     #[serde(default)]
     requested_reviewers: Vec<GitHubUser>,
     head: GitHubRef,
@@ -428,21 +1238,163 @@ struct GitHubPr {
     #[serde(default)]
     draft: bool,
     created_at: chrono::DateTime<chrono::Utc>,
-    updated_at: chrono::DateTime<chrono::Utc>,
+    pub(super) updated_at: chrono::DateTime<chrono::Utc>,
+    pub(super) closed_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub(super) merged_at: Option<chrono::DateTime<chrono::Utc>>,
     html_url: String,
+    #[serde(default)]
+    labels: Vec<GitHubLabel>,
+    /// Only the single pull request endpoint reports the diff size and merger
+    additions: Option<u64>,
+    deletions: Option<u64>,
+    changed_files: Option<u64>,
+    pub(super) merged_by: Option<GitHubUser>,
+}
+
+impl GitHubPr {
+    pub(super) fn diff_stats(&self) -> Option<DiffStats> {
+        Some(DiffStats {
+            additions: self.additions?,
+            deletions: self.deletions?,
+            changed_files: self.changed_files?,
+            files: Vec::new(),
+        })
+    }
 }
 
 #[derive(Debug, Deserialize)]
-struct GitHubUser {
-    id: i64,
-    login: String,
-    avatar_url: String,
+pub(super) struct GitHubUser {
+    pub(super) id: i64,
+    pub(super) login: String,
+    pub(super) avatar_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct GitHubReview {
+    /// Null for deleted accounts
+    pub(super) user: Option<GitHubUser>,
+    pub(super) state: String,
+    /// Null while the review is pending
+    pub(super) submitted_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct GitHubLabel {
+    pub(super) name: String,
 }
 
 #[derive(Debug, Deserialize)]
 struct GitHubRef {
     #[serde(rename = "ref")]
     ref_name: String,
+    sha: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubCheckRuns {
+    #[serde(default)]
+    check_runs: Vec<GitHubCheckRun>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubCheckRun {
+    status: String,
+    conclusion: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubCombinedStatus {
+    state: String,
+    #[serde(default)]
+    total_count: usize,
+}
+
+// ===== GitLab API Types =====
+
+#[derive(Debug, Deserialize)]
+pub(super) struct GitLabMr {
+    pub(super) iid: i64,
+    title: String,
+    description: Option<String>,
+    pub(super) author: GitLabUser,
+    #[serde(default)]
+    reviewers: Vec<GitLabUser>,
+    #[serde(default)]
+    labels: Vec<String>,
+    source_branch: String,
+    target_branch: String,
+    sha: Option<String>,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    work_in_progress: bool,
+    head_pipeline: Option<GitLabPipeline>,
+    created_at: chrono::DateTime<chrono::Utc>,
+    pub(super) updated_at: chrono::DateTime<chrono::Utc>,
+    web_url: String,
+    pub(super) merged_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub(super) closed_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Who merged the MR; `merged_by` on GitLab before 14.7
+    pub(super) merge_user: Option<GitLabUser>,
+    pub(super) merged_by: Option<GitLabUser>,
+    /// Files changed, only on the single merge request endpoint; "1000+" when capped
+    changes_count: Option<String>,
+}
+
+impl GitLabMr {
+    /// Exact number of files changed, if GitLab reported one
+    fn changed_files(&self) -> Option<u64> {
+        self.changes_count.as_deref()?.parse().ok()
+    }
+
+    /// Older GitLab versions only flag drafts through the title prefix
+    fn is_draft(&self) -> bool {
+        let title = self.title.to_lowercase();
+        self.draft
+            || self.work_in_progress
+            || title.starts_with("draft:")
+            || title.starts_with("[draft]")
+            || title.starts_with("(draft)")
+            || title.starts_with("wip:")
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(super) struct GitLabUser {
+    id: i64,
+    username: String,
+    avatar_url: Option<String>,
+}
+
+/// Comment or system note on an issue or merge request
+#[derive(Debug, Deserialize)]
+pub(super) struct GitLabNote {
+    pub(super) id: i64,
+    #[serde(default)]
+    pub(super) body: String,
+    /// Null for deleted accounts
+    pub(super) author: Option<GitLabUser>,
+    #[serde(default)]
+    pub(super) system: bool,
+    pub(super) created_at: chrono::DateTime<chrono::Utc>,
+    pub(super) updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(super) struct GitLabPipeline {
+    status: String,
+    sha: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabApprovals {
+    #[serde(default)]
+    approved_by: Vec<GitLabApprover>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabApprover {
+    user: GitLabUser,
 }
 
 #[cfg(test)]
@@ -477,6 +1429,455 @@ mod tests {
         assert_eq!(config.api_base_url(), "https://api.github.com");
     }
 
+    #[test]
+    fn test_git_config_gitlab_self_hosted() {
+        let config = GitConfig::gitlab("user")
+            .with_base_url("https://gitlab.example.com/")
+            .with_token("t");
+
+        assert_eq!(config.provider, GitProviderType::GitLab);
+        assert_eq!(config.api_base_url(), "https://gitlab.example.com/api/v4");
+
+        let config = GitConfig::gitlab("user").with_base_url("https://gitlab.example.com/api/v4");
+        assert_eq!(config.api_base_url(), "https://gitlab.example.com/api/v4");
+
+        let config = GitConfig::gitlab("user");
+        assert_eq!(config.api_base_url(), "https://gitlab.com/api/v4");
+    }
+
+    #[test]
+    fn test_gitlab_project_path_with_namespace() {
+        let config = GitConfig::gitlab("user").with_workspace("platform/backend");
+
+        assert_eq!(config.gitlab_project_path("billing"), "platform/backend/billing");
+        assert_eq!(config.gitlab_project_path("other/group/api"), "other/group/api");
+        assert_eq!(
+            urlencoding::encode(&config.gitlab_project_path("billing")),
+            "platform%2Fbackend%2Fbilling"
+        );
+
+        let config = GitConfig::gitlab("user");
+        assert_eq!(config.gitlab_project_path("billing"), "billing");
+    }
+
+    fn gitlab_mr_json(title: &str, draft: bool) -> serde_json::Value {
+        serde_json::json!({
+            "iid": 42,
+            "title": title,
+            "description": "",
+            "author": {"id": 1, "username": "alice", "avatar_url": null},
+            "reviewers": [
+                {"id": 2, "username": "bob", "avatar_url": null},
+                {"id": 3, "username": "carol", "avatar_url": null}
+            ],
+            "source_branch": "feature/x",
+            "target_branch": "main",
+            "draft": draft,
+            "created_at": "2024-01-01T00:00:00Z",
+            "updated_at": "2024-01-02T00:00:00Z",
+            "web_url": "https://gitlab.example.com/group/repo/-/merge_requests/42"
+        })
+    }
+
+    #[test]
+    fn test_map_gitlab_mr() {
+        let provider = GitProvider::new(GitConfig::gitlab("user").with_token("t")).unwrap();
+        let mr: GitLabMr = serde_json::from_value(gitlab_mr_json("Add billing", false)).unwrap();
+        let approved_by = vec![
+            GitLabUser { id: 2, username: "bob".to_string(), avatar_url: None },
+            GitLabUser { id: 9, username: "dave".to_string(), avatar_url: None },
+        ];
+        let pipeline = GitLabPipeline { status: "failed".to_string(), sha: None };
+
+        let pr = provider.map_gitlab_mr(&mr, "group/repo", &approved_by, Some(&pipeline));
+
+        assert_eq!(pr.id, "42");
+        assert_eq!(pr.repository, "group/repo");
+        assert_eq!(pr.state, PrState::Open);
+        assert!(pr.description.is_none());
+        assert_eq!(pr.author.name, "alice");
+        assert_eq!(pr.checks_status, ChecksStatus::Fail);
+        assert_eq!(pr.reviewers.len(), 3);
+        assert!(pr.reviewers.iter().any(|r| r.user.name == "bob" && r.state == ReviewState::Approved));
+        assert!(pr.reviewers.iter().any(|r| r.user.name == "carol" && r.state == ReviewState::Pending));
+        assert!(pr.reviewers.iter().any(|r| r.user.name == "dave" && r.is_approved()));
+    }
+
+    #[test]
+    fn test_gitlab_draft_detection() {
+        let mr: GitLabMr = serde_json::from_value(gitlab_mr_json("Add billing", true)).unwrap();
+        assert!(mr.is_draft());
+
+        let mr: GitLabMr = serde_json::from_value(gitlab_mr_json("Draft: Add billing", false)).unwrap();
+        assert!(mr.is_draft());
+
+        let mr: GitLabMr = serde_json::from_value(gitlab_mr_json("WIP: Add billing", false)).unwrap();
+        assert!(mr.is_draft());
+
+        let mr: GitLabMr = serde_json::from_value(gitlab_mr_json("Drafting rules", false)).unwrap();
+        assert!(!mr.is_draft());
+    }
+
+    #[test]
+    fn test_map_gitlab_pipeline_status() {
+        let provider = GitProvider::new(GitConfig::gitlab("user").with_token("t")).unwrap();
+
+        assert_eq!(provider.map_gitlab_pipeline_status("success"), ChecksStatus::Pass);
+        assert_eq!(provider.map_gitlab_pipeline_status("failed"), ChecksStatus::Fail);
+        assert_eq!(provider.map_gitlab_pipeline_status("running"), ChecksStatus::Running);
+        assert_eq!(provider.map_gitlab_pipeline_status("pending"), ChecksStatus::Running);
+        assert_eq!(provider.map_gitlab_pipeline_status("canceled"), ChecksStatus::None);
+    }
+
+    fn github_user_json(id: i64, login: &str) -> serde_json::Value {
+        serde_json::json!({"id": id, "login": login, "avatar_url": "https://avatars.example.com/u"})
+    }
+
+    #[test]
+    fn test_map_github_reviewers() {
+        let provider = GitProvider::new(GitConfig::github("user").with_token("t")).unwrap();
+        let pr: GitHubPr = serde_json::from_value(serde_json::json!({
+            "number": 7,
+            "title": "Add billing",
+            "body": null,
+            "user": github_user_json(1, "alice"),
+            "requested_reviewers": [github_user_json(5, "erin"), github_user_json(4, "dave")],
+            "head": {"ref": "feature/x", "sha": "abc123"},
+            "base": {"ref": "main", "sha": "def456"},
+            "created_at": "2024-01-01T00:00:00Z",
+            "updated_at": "2024-01-02T00:00:00Z",
+            "html_url": "https://github.com/org/repo/pull/7"
+        })).unwrap();
+        let reviews: Vec<GitHubReview> = serde_json::from_value(serde_json::json!([
+            {"user": github_user_json(2, "bob"), "state": "APPROVED"},
+            {"user": github_user_json(2, "bob"), "state": "COMMENTED"},
+            {"user": github_user_json(3, "carol"), "state": "CHANGES_REQUESTED"},
+            {"user": github_user_json(4, "dave"), "state": "APPROVED"},
+            {"user": github_user_json(1, "alice"), "state": "COMMENTED"},
+            {"user": null, "state": "APPROVED"},
+            {"user": github_user_json(6, "frank"), "state": "APPROVED"},
+            {"user": github_user_json(6, "frank"), "state": "DISMISSED"}
+        ])).unwrap();
+
+        let pr = provider.map_github_pr(&pr, "org/repo", &reviews, ChecksStatus::None);
+        let state_of = |name: &str| pr.reviewers.iter().find(|r| r.user.name == name).map(|r| r.state);
+
+        assert_eq!(pr.reviewers.len(), 5);
+        assert_eq!(state_of("bob"), Some(ReviewState::Approved));
+        assert_eq!(state_of("carol"), Some(ReviewState::ChangesRequested));
+        // Re-requested after approving
+        assert_eq!(state_of("dave"), Some(ReviewState::Pending));
+        assert_eq!(state_of("erin"), Some(ReviewState::Pending));
+        assert_eq!(state_of("frank"), Some(ReviewState::Commented));
+        assert_eq!(state_of("alice"), None);
+    }
+
+    #[test]
+    fn test_map_github_pr_labels_and_diff_stats() {
+        let provider = GitProvider::new(GitConfig::github("user").with_token("t")).unwrap();
+        let mut json = serde_json::json!({
+            "number": 7,
+            "title": "Add billing",
+            "body": null,
+            "user": github_user_json(1, "alice"),
+            "head": {"ref": "feature/x", "sha": "abc123"},
+            "base": {"ref": "main", "sha": "def456"},
+            "created_at": "2024-01-01T00:00:00Z",
+            "updated_at": "2024-01-02T00:00:00Z",
+            "html_url": "https://github.com/org/repo/pull/7",
+            "labels": [{"name": "backend"}]
+        });
+
+        // The listing omits the diff size
+        let listed: GitHubPr = serde_json::from_value(json.clone()).unwrap();
+        let pr = provider.map_github_pr(&listed, "org/repo", &[], ChecksStatus::None);
+        assert_eq!(pr.labels, vec!["backend"]);
+        assert_eq!(pr.diff_stats, None);
+
+        json["additions"] = serde_json::json!(10);
+        json["deletions"] = serde_json::json!(2);
+        json["changed_files"] = serde_json::json!(1);
+        let single: GitHubPr = serde_json::from_value(json).unwrap();
+        let pr = provider.map_github_pr(&single, "org/repo", &[], ChecksStatus::None);
+        assert_eq!(pr.diff_stats.map(|d| d.lines_changed()), Some(12));
+    }
+
+    #[test]
+    fn test_map_bitbucket_reviewers() {
+        let provider = GitProvider::new(GitConfig::bitbucket("ws", "user").with_token("t")).unwrap();
+        let user = |uuid: &str, name: &str| serde_json::json!({
+            "uuid": uuid, "display_name": name, "links": {"avatar": null}
+        });
+        let pr: BitbucketPr = serde_json::from_value(serde_json::json!({
+            "id": 12,
+            "title": "Add billing",
+            "description": null,
+            "author": user("{a}", "Alice"),
+            "reviewers": [user("{b}", "Bob"), user("{c}", "Carol"), user("{d}", "Dave")],
+            "participants": [
+                {"user": user("{a}", "Alice"), "role": "PARTICIPANT", "approved": false, "state": null},
+                {"user": user("{b}", "Bob"), "role": "REVIEWER", "approved": true, "state": "approved"},
+                {"user": user("{c}", "Carol"), "role": "REVIEWER", "approved": false, "state": "changes_requested"},
+                {"user": user("{e}", "Erin"), "role": "PARTICIPANT", "approved": false, "state": null}
+            ],
+            "source": {"branch": {"name": "feature/x"}},
+            "destination": {"branch": {"name": "main"}},
+            "created_on": "2024-01-01T00:00:00Z",
+            "updated_on": "2024-01-02T00:00:00Z",
+            "links": {"html": {"href": "https://bitbucket.org/ws/repo/pull-requests/12"}}
+        })).unwrap();
+
+        let pr = provider.map_bitbucket_pr(&pr, "repo", ChecksStatus::None);
+        let state_of = |name: &str| pr.reviewers.iter().find(|r| r.user.name == name).map(|r| r.state);
+
+        assert_eq!(pr.reviewers.len(), 4);
+        assert_eq!(state_of("Bob"), Some(ReviewState::Approved));
+        assert_eq!(state_of("Carol"), Some(ReviewState::ChangesRequested));
+        assert_eq!(state_of("Dave"), Some(ReviewState::Pending));
+        assert_eq!(state_of("Erin"), Some(ReviewState::Commented));
+        assert_eq!(state_of("Alice"), None);
+    }
+
+    #[test]
+    fn test_map_github_checks() {
+        let provider = GitProvider::new(GitConfig::github("user").with_token("t")).unwrap();
+        let runs: GitHubCheckRuns = serde_json::from_value(serde_json::json!({
+            "total_count": 3,
+            "check_runs": [
+                {"status": "completed", "conclusion": "success"},
+                {"status": "completed", "conclusion": "skipped"},
+                {"status": "in_progress", "conclusion": null}
+            ]
+        })).unwrap();
+        let statuses: Vec<ChecksStatus> = runs.check_runs.iter()
+            .map(|run| provider.map_github_check_run(run))
+            .collect();
+        assert_eq!(statuses, vec![ChecksStatus::Pass, ChecksStatus::Pass, ChecksStatus::Running]);
+
+        let failed = GitHubCheckRun { status: "completed".to_string(), conclusion: Some("timed_out".to_string()) };
+        assert_eq!(provider.map_github_check_run(&failed), ChecksStatus::Fail);
+
+        let empty = GitHubCombinedStatus { state: "pending".to_string(), total_count: 0 };
+        assert_eq!(provider.map_github_combined_status(&empty), ChecksStatus::None);
+        let errored = GitHubCombinedStatus { state: "error".to_string(), total_count: 2 };
+        assert_eq!(provider.map_github_combined_status(&errored), ChecksStatus::Fail);
+    }
+
+    #[test]
+    fn test_map_bitbucket_status() {
+        let provider = GitProvider::new(GitConfig::bitbucket("ws", "user").with_token("t")).unwrap();
+
+        assert_eq!(provider.map_bitbucket_status("SUCCESSFUL"), ChecksStatus::Pass);
+        assert_eq!(provider.map_bitbucket_status("INPROGRESS"), ChecksStatus::Running);
+        assert_eq!(provider.map_bitbucket_status("FAILED"), ChecksStatus::Fail);
+        assert_eq!(provider.map_bitbucket_status("STOPPED"), ChecksStatus::Fail);
+    }
+
+    #[test]
+    fn test_select_head_pipeline() {
+        let pipeline = |status: &str, sha: &str| GitLabPipeline {
+            status: status.to_string(),
+            sha: Some(sha.to_string()),
+        };
+        let pipelines = vec![pipeline("running", "new"), pipeline("failed", "head")];
+
+        let selected = select_head_pipeline(pipelines.clone(), Some("head")).unwrap();
+        assert_eq!(selected.status, "failed");
+
+        let selected = select_head_pipeline(pipelines, Some("unknown")).unwrap();
+        assert_eq!(selected.status, "running");
+
+        assert!(select_head_pipeline(vec![], Some("head")).is_none());
+    }
+
+    #[test]
+    fn test_page_size_clamped_per_provider() {
+        let config = GitConfig::github("user");
+        assert_eq!(config.page_size, 50);
+        assert_eq!(config.effective_page_size(), 50);
+
+        let config = GitConfig::github("user").with_page_size(500);
+        assert_eq!(config.effective_page_size(), 100);
+
+        let config = GitConfig::bitbucket("ws", "user").with_page_size(500);
+        assert_eq!(config.effective_page_size(), 50);
+
+        let config = GitConfig::gitlab("user").with_page_size(0);
+        assert_eq!(config.effective_page_size(), 1);
+    }
+
+    #[test]
+    fn test_git_config_page_size_defaults_when_missing() {
+        let json = r#"{"provider":"github","base_url":null,"workspace":null,"username":"u","repositories":[]}"#;
+        let config: GitConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.page_size, 50);
+    }
+
+    #[test]
+    fn test_parse_next_link() {
+        let header = r#"<https://api.github.com/repositories/1/pulls?page=2>; rel="next", <https://api.github.com/repositories/1/pulls?page=5>; rel="last""#;
+        assert_eq!(
+            parse_next_link(header),
+            Some("https://api.github.com/repositories/1/pulls?page=2".to_string())
+        );
+
+        let last_page = r#"<https://api.github.com/repositories/1/pulls?page=1>; rel="prev", <https://api.github.com/repositories/1/pulls?page=1>; rel="first""#;
+        assert_eq!(parse_next_link(last_page), None);
+    }
+
+    #[test]
+    fn test_bitbucket_page_next_url() {
+        let page: BitbucketPrList = serde_json::from_value(serde_json::json!({
+            "values": [],
+            "next": "https://api.bitbucket.org/2.0/repositories/ws/repo/pullrequests?page=2"
+        }))
+        .unwrap();
+        assert!(page.next.is_some());
+
+        let last: BitbucketPrList = serde_json::from_value(serde_json::json!({"values": []})).unwrap();
+        assert!(last.next.is_none());
+    }
+
+    #[test]
+    fn test_concurrency_settings() {
+        let config = GitConfig::github("user");
+        assert_eq!(config.max_concurrency, 8);
+        assert_eq!(config.repo_timeout_secs, 30);
+
+        let config = GitConfig::github("user")
+            .with_max_concurrency(4)
+            .with_repo_timeout(10);
+        assert_eq!(config.max_concurrency, 4);
+        assert_eq!(config.repo_timeout_secs, 10);
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_preserves_repository_order() {
+        let repos: Vec<String> = vec!["slow".to_string(), "medium".to_string(), "fast".to_string()];
+
+        let results = fan_out(&repos, 3, std::time::Duration::from_secs(5), |repo| async move {
+            let delay = match repo.as_str() {
+                "slow" => 60,
+                "medium" => 30,
+                _ => 0,
+            };
+            tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
+            Ok(repo)
+        })
+        .await;
+
+        let names: Vec<String> = results.into_iter().map(|r| r.unwrap()).collect();
+        assert_eq!(names, repos);
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_respects_concurrency_cap() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let repos: Vec<String> = (0..10).map(|i| format!("repo{}", i)).collect();
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let results = fan_out(&repos, 3, std::time::Duration::from_secs(5), |_| {
+            let in_flight = in_flight.clone();
+            let peak = peak.clone();
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                Ok(())
+            }
+        })
+        .await;
+
+        assert_eq!(results.len(), 10);
+        assert!(peak.load(Ordering::SeqCst) <= 3);
+    }
+
+    #[tokio::test]
+    async fn test_enrich_all_keeps_order_within_cap() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let enriched = enrich_all((0..10u64).collect(), 3, |n| {
+            let in_flight = in_flight.clone();
+            let peak = peak.clone();
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(std::time::Duration::from_millis(20 - 2 * n)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                Ok(n * 10)
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(enriched, (0..10u64).map(|n| n * 10).collect::<Vec<_>>());
+        assert!(peak.load(Ordering::SeqCst) <= 3);
+    }
+
+    #[tokio::test]
+    async fn test_enrich_all_returns_first_error() {
+        let result = enrich_all(vec![1, 2, 3], 2, |n| async move {
+            if n == 2 {
+                Err(IntegrationError::RateLimit { reset_at: None })
+            } else {
+                Ok(n)
+            }
+        })
+        .await;
+
+        assert!(matches!(result, Err(IntegrationError::RateLimit { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_times_out_slow_repository() {
+        let repos = vec!["hung".to_string(), "ok".to_string()];
+
+        let results = fan_out(&repos, 2, std::time::Duration::from_millis(20), |repo| async move {
+            if repo == "hung" {
+                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+            }
+            Ok(repo)
+        })
+        .await;
+
+        assert!(matches!(results[0], Err(IntegrationError::Network(_))));
+        assert_eq!(results[1].as_ref().unwrap(), "ok");
+    }
+
+    #[test]
+    fn test_repo_status_error() {
+        let provider = GitProvider::new(GitConfig::github("user").with_token("t")).unwrap();
+
+        assert!(matches!(provider.repo_status_error(401, "r"), IntegrationError::Auth(_)));
+        assert!(matches!(provider.repo_status_error(403, "r"), IntegrationError::Auth(_)));
+        assert!(matches!(provider.repo_status_error(404, "r"), IntegrationError::NotFound(_)));
+        assert!(matches!(provider.repo_status_error(429, "r"), IntegrationError::RateLimit { .. }));
+        assert!(matches!(provider.repo_status_error(500, "r"), IntegrationError::ApiError(_)));
+    }
+
+    #[tokio::test]
+    async fn test_get_open_prs_without_repositories() {
+        let provider = GitProvider::new(GitConfig::github("user").with_token("t")).unwrap();
+        let prs = provider.get_open_prs(&PrFilter::new()).await.unwrap();
+        assert!(prs.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_bitbucket_requires_workspace() {
+        let mut config = GitConfig::bitbucket("ws", "user").with_token("t");
+        config.workspace = None;
+        let provider = GitProvider::new(config).unwrap();
+
+        let result = provider.get_open_prs(&PrFilter::new()).await;
+        assert!(matches!(result.unwrap_err(), IntegrationError::ConfigError(_)));
+    }
+
     #[test]
     fn test_provider_requires_token() {
         let config = GitConfig::github("user");
@@ -0,0 +1,303 @@
+//! Conditional HTTP requests
+//!
+//! Remembers the `ETag` / `Last-Modified` validators and body of GET responses
+//! in the `CacheService`, sends them back as `If-None-Match` /
+//! `If-Modified-Since`, and replays the stored body when the server answers
+//! `304 Not Modified`. Most APIs do not count 304s against the rate limit.
+//! Entries are keyed by URL and credentials, so two accounts on the same host
+//! never see each other's responses.
+
+use chrono::Duration;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION};
+use reqwest::RequestBuilder;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use super::traits::IntegrationError;
+use crate::services::CacheService;
+
+/// Response headers needed to page through a replayed response
+const REPLAYED_HEADERS: &[&str] = &["link", "x-next-page", "x-total", "x-total-pages"];
+
+/// Validators and body of a cached response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedResponse {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub body: String,
+    #[serde(default)]
+    pub headers: Vec<(String, String)>,
+}
+
+/// A fully read response, either fresh or replayed from the cache after a 304
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    /// Status of the response; 200 when replayed from the cache
+    pub status: u16,
+    pub headers: HeaderMap,
+    pub body: String,
+    /// Whether the server answered 304 and the body came from the cache
+    pub not_modified: bool,
+}
+
+impl HttpResponse {
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T, IntegrationError> {
+        serde_json::from_str(&self.body).map_err(|e| IntegrationError::ParseError(e.to_string()))
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|v| v.to_str().ok())
+    }
+
+    async fn read(response: reqwest::Response) -> Result<Self, IntegrationError> {
+        let status = response.status().as_u16();
+        let headers = response.headers().clone();
+        let body = response.text().await?;
+
+        Ok(Self {
+            status,
+            headers,
+            body,
+            not_modified: false,
+        })
+    }
+}
+
+/// Conditional request support backed by the `CacheService`
+#[derive(Clone)]
+pub struct HttpCache {
+    cache: Arc<CacheService>,
+    ttl: Duration,
+}
+
+impl HttpCache {
+    pub fn new(cache: Arc<CacheService>) -> Self {
+        Self {
+            cache,
+            ttl: Duration::days(7),
+        }
+    }
+
+    /// How long validators are kept for URLs that are no longer requested
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Cache key of a request; the credentials are hashed rather than stored
+    fn key(url: &str, request: &RequestBuilder) -> String {
+        let credentials = request
+            .try_clone()
+            .and_then(|r| r.build().ok())
+            .and_then(|r| r.headers().get(AUTHORIZATION).cloned());
+
+        match credentials {
+            Some(value) => {
+                let mut hasher = DefaultHasher::new();
+                value.as_bytes().hash(&mut hasher);
+                format!("http:{:016x}:{}", hasher.finish(), url)
+            }
+            None => format!("http:{}", url),
+        }
+    }
+
+    /// Send a GET request for `url`, conditionally if validators are known
+    pub async fn send(&self, url: &str, request: RequestBuilder) -> Result<HttpResponse, IntegrationError> {
+        let key = Self::key(url, &request);
+        let cached: Option<CachedResponse> = self.cache.get_stale(&key);
+
+        let request = match cached {
+            Some(ref cached) => Self::with_validators(request, cached),
+            None => request,
+        };
+
+        let response = request.send().await?;
+
+        if response.status().as_u16() == 304 {
+            if let Some(cached) = cached {
+                log::debug!("HttpCache: {} not modified", url);
+                return Ok(Self::replay(response.headers(), cached));
+            }
+        }
+
+        let response = HttpResponse::read(response).await?;
+        if response.status == 200 {
+            if let Some(entry) = Self::to_cached(&response) {
+                let _ = self.cache.set(&key, &entry, self.ttl);
+            }
+        }
+
+        Ok(response)
+    }
+
+    fn with_validators(request: RequestBuilder, cached: &CachedResponse) -> RequestBuilder {
+        let request = match cached.etag {
+            Some(ref etag) => request.header("If-None-Match", etag),
+            None => request,
+        };
+        match cached.last_modified {
+            Some(ref last_modified) => request.header("If-Modified-Since", last_modified),
+            None => request,
+        }
+    }
+
+    /// Keep a response if the server gave it a validator
+    fn to_cached(response: &HttpResponse) -> Option<CachedResponse> {
+        let etag = response.header("etag").map(str::to_string);
+        let last_modified = response.header("last-modified").map(str::to_string);
+        if etag.is_none() && last_modified.is_none() {
+            return None;
+        }
+
+        let headers = REPLAYED_HEADERS
+            .iter()
+            .filter_map(|name| response.header(name).map(|v| (name.to_string(), v.to_string())))
+            .collect();
+
+        Some(CachedResponse {
+            etag,
+            last_modified,
+            body: response.body.clone(),
+            headers,
+        })
+    }
+
+    /// Rebuild the original response, keeping the live headers (e.g. rate limits) of the 304
+    fn replay(live_headers: &HeaderMap, cached: CachedResponse) -> HttpResponse {
+        let mut headers = live_headers.clone();
+        for (name, value) in &cached.headers {
+            if let (Ok(name), Ok(value)) = (HeaderName::try_from(name.as_str()), HeaderValue::from_str(value)) {
+                headers.insert(name, value);
+            }
+        }
+
+        HttpResponse {
+            status: 200,
+            headers,
+            body: cached.body,
+            not_modified: true,
+        }
+    }
+}
+
+impl std::fmt::Debug for HttpCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HttpCache").field("ttl", &self.ttl).finish()
+    }
+}
+
+/// Send a GET request through the cache when one is configured
+pub async fn send_get(
+    cache: Option<&HttpCache>,
+    url: &str,
+    request: RequestBuilder,
+) -> Result<HttpResponse, IntegrationError> {
+    match cache {
+        Some(cache) => cache.send(url, request).await,
+        None => HttpResponse::read(request.send().await?).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(headers: &[(&'static str, &str)], body: &str) -> HttpResponse {
+        let mut map = HeaderMap::new();
+        for (name, value) in headers {
+            map.insert(*name, HeaderValue::from_str(value).unwrap());
+        }
+        HttpResponse {
+            status: 200,
+            headers: map,
+            body: body.to_string(),
+            not_modified: false,
+        }
+    }
+
+    #[test]
+    fn test_to_cached_requires_validator() {
+        assert!(HttpCache::to_cached(&response(&[], "[]")).is_none());
+
+        let cached = HttpCache::to_cached(&response(
+            &[("etag", "W/\"abc\""), ("link", "<https://api.example.com?page=2>; rel=\"next\"")],
+            "[1]",
+        ))
+        .unwrap();
+        assert_eq!(cached.etag.as_deref(), Some("W/\"abc\""));
+        assert!(cached.last_modified.is_none());
+        assert_eq!(cached.body, "[1]");
+        assert_eq!(cached.headers.len(), 1);
+    }
+
+    #[test]
+    fn test_replay_restores_body_and_paging_headers() {
+        let cached = CachedResponse {
+            etag: Some("\"abc\"".to_string()),
+            last_modified: None,
+            body: "[1,2]".to_string(),
+            headers: vec![("x-next-page".to_string(), "2".to_string())],
+        };
+        let mut live = HeaderMap::new();
+        live.insert("x-ratelimit-remaining", HeaderValue::from_static("4999"));
+
+        let replayed = HttpCache::replay(&live, cached);
+
+        assert_eq!(replayed.status, 200);
+        assert!(replayed.not_modified);
+        assert_eq!(replayed.header("x-next-page"), Some("2"));
+        assert_eq!(replayed.header("x-ratelimit-remaining"), Some("4999"));
+        assert_eq!(replayed.json::<Vec<i32>>().unwrap(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_key_depends_on_credentials() {
+        let client = reqwest::Client::new();
+        let url = "https://api.github.com/repos/acme/api/pulls";
+
+        let alice = HttpCache::key(url, &client.get(url).bearer_auth("alice-token"));
+        let bob = HttpCache::key(url, &client.get(url).bearer_auth("bob-token"));
+
+        assert_ne!(alice, bob);
+        assert_eq!(alice, HttpCache::key(url, &client.get(url).bearer_auth("alice-token")));
+        assert!(!alice.contains("alice-token"));
+        assert_eq!(HttpCache::key(url, &client.get(url)), format!("http:{}", url));
+    }
+
+    #[test]
+    fn test_validators_are_sent() {
+        let client = reqwest::Client::new();
+        let cached = CachedResponse {
+            etag: Some("\"abc\"".to_string()),
+            last_modified: Some("Mon, 01 Jan 2024 00:00:00 GMT".to_string()),
+            body: String::new(),
+            headers: vec![],
+        };
+
+        let request = HttpCache::with_validators(client.get("https://api.example.com/pulls"), &cached)
+            .build()
+            .unwrap();
+
+        assert_eq!(request.headers().get("if-none-match").unwrap(), "\"abc\"");
+        assert_eq!(
+            request.headers().get("if-modified-since").unwrap(),
+            "Mon, 01 Jan 2024 00:00:00 GMT"
+        );
+    }
+
+    #[test]
+    fn test_cached_response_round_trips_through_cache_service() {
+        let cache = Arc::new(CacheService::new_in_memory().unwrap());
+        let http_cache = HttpCache::new(cache.clone());
+        let url = "https://api.example.com/pulls";
+        let key = HttpCache::key(url, &reqwest::Client::new().get(url).bearer_auth("t"));
+        let entry = HttpCache::to_cached(&response(&[("etag", "\"abc\"")], "[]")).unwrap();
+
+        cache.set(&key, &entry, http_cache.ttl).unwrap();
+
+        let stored: Option<CachedResponse> = cache.get_stale(&key);
+        assert_eq!(stored.unwrap().etag.as_deref(), Some("\"abc\""));
+    }
+}
@@ -0,0 +1,486 @@
+//! Atlassian Document Format
+//!
+//! Jira Cloud's REST v3 returns rich text (descriptions, comments) as ADF, a JSON
+//! tree of block and inline nodes. This renders such documents as plain text, for
+//! search and previews, or as Markdown, for the detail view.
+//!
+//! Unknown nodes are rendered through their children, so new node types degrade
+//! to their text instead of failing the whole ticket.
+
+use chrono::{TimeZone, Utc};
+use serde_json::{json, Value};
+
+/// Render an ADF document as plain text
+pub fn to_plain_text(doc: &Value) -> String {
+    Renderer { markdown: false }.document(doc)
+}
+
+/// Render an ADF document as Markdown
+pub fn to_markdown(doc: &Value) -> String {
+    Renderer { markdown: true }.document(doc)
+}
+
+/// Build an ADF document from plain text: blank lines separate paragraphs, single newlines become hard breaks
+pub fn from_plain_text(text: &str) -> Value {
+    let paragraphs: Vec<Value> = text
+        .replace("\r\n", "\n")
+        .split("\n\n")
+        .filter(|paragraph| !paragraph.trim().is_empty())
+        .map(|paragraph| {
+            let mut content = Vec::new();
+            for (i, line) in paragraph.lines().enumerate() {
+                if i > 0 {
+                    content.push(json!({ "type": "hardBreak" }));
+                }
+                if !line.is_empty() {
+                    content.push(json!({ "type": "text", "text": line }));
+                }
+            }
+            json!({ "type": "paragraph", "content": content })
+        })
+        .collect();
+
+    json!({ "type": "doc", "version": 1, "content": paragraphs })
+}
+
+/// Plain text and Markdown of a rich text field that may be ADF or a plain string.
+///
+/// Returns `None` for missing or empty fields.
+pub fn render_field(value: Option<&Value>) -> Option<(String, String)> {
+    let (text, markdown) = match value? {
+        Value::String(s) => (s.clone(), s.clone()),
+        Value::Null => return None,
+        doc => (to_plain_text(doc), to_markdown(doc)),
+    };
+
+    if text.trim().is_empty() {
+        None
+    } else {
+        Some((text, markdown))
+    }
+}
+
+fn node_type(node: &Value) -> &str {
+    node.get("type").and_then(Value::as_str).unwrap_or("")
+}
+
+fn content(node: &Value) -> &[Value] {
+    node.get("content")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or(&[])
+}
+
+fn attr<'a>(node: &'a Value, name: &str) -> Option<&'a Value> {
+    node.get("attrs").and_then(|attrs| attrs.get(name))
+}
+
+fn attr_str<'a>(node: &'a Value, name: &str) -> Option<&'a str> {
+    attr(node, name).and_then(Value::as_str)
+}
+
+/// Prefix every line of `text`, e.g. for quotes
+fn prefix_lines(text: &str, prefix: &str) -> String {
+    text.lines()
+        .map(|line| format!("{}{}", prefix, line).trim_end().to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+struct Renderer {
+    markdown: bool,
+}
+
+impl Renderer {
+    fn document(&self, doc: &Value) -> String {
+        let text = if node_type(doc) == "doc" {
+            self.blocks(content(doc), "\n\n")
+        } else {
+            self.block(doc)
+        };
+        text.trim().to_string()
+    }
+
+    fn blocks(&self, nodes: &[Value], separator: &str) -> String {
+        nodes
+            .iter()
+            .map(|node| self.block(node))
+            .filter(|block| !block.is_empty())
+            .collect::<Vec<_>>()
+            .join(separator)
+    }
+
+    fn block(&self, node: &Value) -> String {
+        match node_type(node) {
+            "paragraph" => self.inline(content(node)),
+            "heading" => {
+                let text = self.inline(content(node));
+                if self.markdown {
+                    let level = attr(node, "level").and_then(Value::as_u64).unwrap_or(1).clamp(1, 6);
+                    format!("{} {}", "#".repeat(level as usize), text)
+                } else {
+                    text
+                }
+            }
+            "bulletList" | "orderedList" | "taskList" => self.list(node),
+            "codeBlock" => {
+                let code = self.raw_text(content(node));
+                if self.markdown {
+                    format!("```{}\n{}\n```", attr_str(node, "language").unwrap_or(""), code)
+                } else {
+                    code
+                }
+            }
+            "blockquote" => {
+                let text = self.blocks(content(node), "\n\n");
+                if self.markdown {
+                    prefix_lines(&text, "> ")
+                } else {
+                    text
+                }
+            }
+            "panel" => self.panel(node),
+            "rule" => if self.markdown { "---".to_string() } else { String::new() },
+            "table" => self.table(node),
+            "expand" | "nestedExpand" => {
+                let body = self.blocks(content(node), "\n\n");
+                match attr_str(node, "title").filter(|t| !t.is_empty()) {
+                    Some(title) if self.markdown => format!("**{}**\n\n{}", title, body),
+                    Some(title) => format!("{}\n\n{}", title, body),
+                    None => body,
+                }
+            }
+            "mediaSingle" | "mediaGroup" => self.blocks(content(node), "\n"),
+            "media" => match attr_str(node, "alt").or_else(|| attr_str(node, "id")) {
+                Some(name) => format!("[attachment: {}]", name),
+                None => "[attachment]".to_string(),
+            },
+            "blockCard" | "embedCard" => self.card(node),
+            // Inline nodes at block level and unknown containers
+            _ => self.inline_node(node),
+        }
+    }
+
+    fn list(&self, node: &Value) -> String {
+        let ordered = node_type(node) == "orderedList";
+        let start = attr(node, "order").and_then(Value::as_u64).unwrap_or(1);
+
+        content(node)
+            .iter()
+            .enumerate()
+            .map(|(i, item)| {
+                let marker = if ordered {
+                    format!("{}. ", start + i as u64)
+                } else if node_type(item) == "taskItem" {
+                    if attr_str(item, "state") == Some("DONE") { "- [x] " } else { "- [ ] " }.to_string()
+                } else {
+                    "- ".to_string()
+                };
+
+                let body = if node_type(item) == "taskItem" {
+                    self.inline(content(item))
+                } else {
+                    self.blocks(content(item), "\n")
+                };
+
+                // Continuation lines, e.g. nested lists, line up with the item text
+                let indent = " ".repeat(marker.len());
+                let mut lines = body.lines();
+                let first = lines.next().unwrap_or("");
+                std::iter::once(format!("{}{}", marker, first))
+                    .chain(lines.map(|line| format!("{}{}", indent, line).trim_end().to_string()))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn panel(&self, node: &Value) -> String {
+        let label = match attr_str(node, "panelType").unwrap_or("info") {
+            "note" => "Note",
+            "warning" => "Warning",
+            "success" => "Success",
+            "error" => "Error",
+            "tip" => "Tip",
+            _ => "Info",
+        };
+        let body = self.blocks(content(node), "\n\n");
+
+        if self.markdown {
+            prefix_lines(&format!("**{}:** {}", label, body), "> ")
+        } else {
+            format!("{}: {}", label, body)
+        }
+    }
+
+    fn table(&self, node: &Value) -> String {
+        let rows: Vec<Vec<String>> = content(node)
+            .iter()
+            .map(|row| {
+                content(row)
+                    .iter()
+                    .map(|cell| {
+                        let text = self.blocks(content(cell), " ").replace('\n', " ");
+                        if self.markdown { text.replace('|', "\\|") } else { text }
+                    })
+                    .collect()
+            })
+            .filter(|cells: &Vec<String>| !cells.is_empty())
+            .collect();
+
+        if !self.markdown {
+            return rows.iter().map(|cells| cells.join(" | ")).collect::<Vec<_>>().join("\n");
+        }
+
+        let Some(columns) = rows.iter().map(Vec::len).max() else {
+            return String::new();
+        };
+
+        // Markdown tables need a header row, the first row serves as one
+        let mut lines = Vec::with_capacity(rows.len() + 1);
+        for (i, cells) in rows.iter().enumerate() {
+            let mut cells = cells.clone();
+            cells.resize(columns, String::new());
+            lines.push(format!("| {} |", cells.join(" | ")));
+            if i == 0 {
+                lines.push(format!("|{}", " --- |".repeat(columns)));
+            }
+        }
+        lines.join("\n")
+    }
+
+    fn card(&self, node: &Value) -> String {
+        match attr_str(node, "url") {
+            Some(url) if self.markdown => format!("<{}>", url),
+            Some(url) => url.to_string(),
+            None => String::new(),
+        }
+    }
+
+    fn inline(&self, nodes: &[Value]) -> String {
+        nodes.iter().map(|node| self.inline_node(node)).collect()
+    }
+
+    fn inline_node(&self, node: &Value) -> String {
+        match node_type(node) {
+            "text" => self.text(node),
+            "hardBreak" => if self.markdown { "  \n".to_string() } else { "\n".to_string() },
+            "mention" => {
+                let name = attr_str(node, "text")
+                    .filter(|t| !t.is_empty())
+                    .or_else(|| attr_str(node, "id"))
+                    .unwrap_or("unknown");
+                if name.starts_with('@') { name.to_string() } else { format!("@{}", name) }
+            }
+            "emoji" => attr_str(node, "text")
+                .or_else(|| attr_str(node, "shortName"))
+                .unwrap_or("")
+                .to_string(),
+            "inlineCard" => self.card(node),
+            "status" => format!("[{}]", attr_str(node, "text").unwrap_or("").to_uppercase()),
+            "date" => attr(node, "timestamp")
+                .and_then(|t| t.as_str().and_then(|s| s.parse().ok()).or_else(|| t.as_i64()))
+                .and_then(|millis| Utc.timestamp_millis_opt(millis).single())
+                .map(|date| date.format("%Y-%m-%d").to_string())
+                .unwrap_or_default(),
+            _ => self.blocks(content(node), "\n"),
+        }
+    }
+
+    fn text(&self, node: &Value) -> String {
+        let text = node.get("text").and_then(Value::as_str).unwrap_or("");
+        let marks = node.get("marks").and_then(Value::as_array).map(Vec::as_slice).unwrap_or(&[]);
+        let link = marks
+            .iter()
+            .find(|mark| node_type(mark) == "link")
+            .and_then(|mark| attr_str(mark, "href"));
+
+        if !self.markdown {
+            return match link {
+                Some(href) if href != text => format!("{} ({})", text, href),
+                _ => text.to_string(),
+            };
+        }
+
+        let has = |mark: &str| marks.iter().any(|m| node_type(m) == mark);
+        let mut out = text.to_string();
+        if has("code") {
+            out = format!("`{}`", out);
+        } else {
+            if has("strike") {
+                out = format!("~~{}~~", out);
+            }
+            if has("em") {
+                out = format!("*{}*", out);
+            }
+            if has("strong") {
+                out = format!("**{}**", out);
+            }
+        }
+        match link {
+            Some(href) => format!("[{}]({})", out, href),
+            None => out,
+        }
+    }
+
+    /// Unformatted text of code block content
+    fn raw_text(&self, nodes: &[Value]) -> String {
+        nodes
+            .iter()
+            .map(|node| match node_type(node) {
+                "text" => node.get("text").and_then(Value::as_str).unwrap_or("").to_string(),
+                "hardBreak" => "\n".to_string(),
+                _ => self.raw_text(content(node)),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(content: Value) -> Value {
+        json!({ "type": "doc", "version": 1, "content": content })
+    }
+
+    fn paragraph(text: &str) -> Value {
+        json!({ "type": "paragraph", "content": [{ "type": "text", "text": text }] })
+    }
+
+    #[test]
+    fn test_paragraphs_and_marks() {
+        let doc = doc(json!([
+            { "type": "heading", "attrs": { "level": 2 }, "content": [{ "type": "text", "text": "Summary" }] },
+            { "type": "paragraph", "content": [
+                { "type": "text", "text": "Fix " },
+                { "type": "text", "text": "login", "marks": [{ "type": "strong" }] },
+                { "type": "text", "text": " in " },
+                { "type": "text", "text": "auth.rs", "marks": [{ "type": "code" }] },
+                { "type": "hardBreak" },
+                { "type": "text", "text": "see docs", "marks": [{ "type": "link", "attrs": { "href": "https://docs.example.com" } }] }
+            ]}
+        ]));
+
+        assert_eq!(
+            to_plain_text(&doc),
+            "Summary\n\nFix login in auth.rs\nsee docs (https://docs.example.com)"
+        );
+        assert_eq!(
+            to_markdown(&doc),
+            "## Summary\n\nFix **login** in `auth.rs`  \n[see docs](https://docs.example.com)"
+        );
+    }
+
+    #[test]
+    fn test_nested_lists() {
+        let doc = doc(json!([
+            { "type": "orderedList", "attrs": { "order": 3 }, "content": [
+                { "type": "listItem", "content": [paragraph("First")] },
+                { "type": "listItem", "content": [
+                    paragraph("Second"),
+                    { "type": "bulletList", "content": [
+                        { "type": "listItem", "content": [paragraph("Nested")] }
+                    ]}
+                ]}
+            ]}
+        ]));
+
+        assert_eq!(to_markdown(&doc), "3. First\n4. Second\n   - Nested");
+    }
+
+    #[test]
+    fn test_code_block() {
+        let doc = doc(json!([
+            { "type": "codeBlock", "attrs": { "language": "rust" }, "content": [
+                { "type": "text", "text": "fn main() {}\nmain();" }
+            ]}
+        ]));
+
+        assert_eq!(to_plain_text(&doc), "fn main() {}\nmain();");
+        assert_eq!(to_markdown(&doc), "```rust\nfn main() {}\nmain();\n```");
+    }
+
+    #[test]
+    fn test_mentions_emoji_and_status() {
+        let doc = doc(json!([
+            { "type": "paragraph", "content": [
+                { "type": "mention", "attrs": { "id": "abc", "text": "@Jane Doe" } },
+                { "type": "text", "text": " please review " },
+                { "type": "mention", "attrs": { "id": "def", "text": "John" } },
+                { "type": "text", "text": " " },
+                { "type": "emoji", "attrs": { "shortName": ":smile:", "text": "😄" } },
+                { "type": "text", "text": " " },
+                { "type": "status", "attrs": { "text": "in review", "color": "blue" } }
+            ]}
+        ]));
+
+        assert_eq!(to_plain_text(&doc), "@Jane Doe please review @John 😄 [IN REVIEW]");
+    }
+
+    #[test]
+    fn test_table() {
+        let cell = |kind: &str, text: &str| json!({ "type": kind, "content": [paragraph(text)] });
+        let doc = doc(json!([
+            { "type": "table", "content": [
+                { "type": "tableRow", "content": [cell("tableHeader", "Env"), cell("tableHeader", "Status")] },
+                { "type": "tableRow", "content": [cell("tableCell", "prod"), cell("tableCell", "a | b")] }
+            ]}
+        ]));
+
+        assert_eq!(to_markdown(&doc), "| Env | Status |\n| --- | --- |\n| prod | a \\| b |");
+        assert_eq!(to_plain_text(&doc), "Env | Status\nprod | a | b");
+    }
+
+    #[test]
+    fn test_panel_and_quote() {
+        let doc = doc(json!([
+            { "type": "panel", "attrs": { "panelType": "warning" }, "content": [paragraph("Deploy freeze")] },
+            { "type": "blockquote", "content": [paragraph("Quoted")] },
+            { "type": "rule" }
+        ]));
+
+        assert_eq!(to_markdown(&doc), "> **Warning:** Deploy freeze\n\n> Quoted\n\n---");
+        assert_eq!(to_plain_text(&doc), "Warning: Deploy freeze\n\nQuoted");
+    }
+
+    #[test]
+    fn test_unknown_nodes_fall_back_to_children() {
+        let doc = doc(json!([
+            { "type": "layoutSection", "content": [
+                { "type": "layoutColumn", "content": [paragraph("Left")] },
+                { "type": "layoutColumn", "content": [paragraph("Right")] }
+            ]},
+            { "type": "paragraph", "content": [
+                { "type": "inlineCard", "attrs": { "url": "https://example.com/PROJ-1" } }
+            ]}
+        ]));
+
+        assert_eq!(to_plain_text(&doc), "Left\nRight\n\nhttps://example.com/PROJ-1");
+    }
+
+    #[test]
+    fn test_from_plain_text_round_trips() {
+        let doc = from_plain_text("Moved to QA\nplease verify\n\nThanks");
+
+        assert_eq!(doc["content"].as_array().unwrap().len(), 2);
+        assert_eq!(doc["content"][0]["content"][1]["type"], "hardBreak");
+        assert_eq!(to_plain_text(&doc), "Moved to QA\nplease verify\n\nThanks");
+    }
+
+    #[test]
+    fn test_render_field() {
+        assert_eq!(render_field(None), None);
+        assert_eq!(render_field(Some(&Value::Null)), None);
+        assert_eq!(render_field(Some(&doc(json!([])))), None);
+        assert_eq!(
+            render_field(Some(&json!("Plain *wiki* text"))),
+            Some(("Plain *wiki* text".to_string(), "Plain *wiki* text".to_string()))
+        );
+        assert_eq!(
+            render_field(Some(&doc(json!([paragraph("Hello")])))),
+            Some(("Hello".to_string(), "Hello".to_string()))
+        );
+    }
+}
@@ -0,0 +1,407 @@
+//! Jira Agile API
+//!
+//! Boards and sprints from Jira Software's `/rest/agile/1.0`, which is the same
+//! on Cloud and Data Center. Scope changes and completion times are read from
+//! each issue's changelog, carry-over from the closed sprints in its sprint field.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use super::client::{issue_fields, JiraChangelog, JiraClient, JiraIssue};
+use super::fields::{self, JiraFieldMapping};
+use crate::integrations::traits::{
+    Board, IntegrationError, Sprint, SprintIssue, SprintRepository, SprintState, StatusCategory,
+};
+
+/// Largest page of boards the Agile API returns
+const BOARD_PAGE_SIZE: usize = 50;
+
+/// Largest page of sprint issues the Agile API returns
+const ISSUE_PAGE_SIZE: usize = 100;
+
+impl JiraClient {
+    fn map_board(&self, board: &AgileBoard) -> Board {
+        Board {
+            id: board.id,
+            name: board.name.clone(),
+            board_type: board.board_type.clone(),
+            project_key: board.location.as_ref().and_then(|l| l.project_key.clone()),
+        }
+    }
+
+    fn map_sprint(&self, sprint: &AgileSprint) -> Sprint {
+        Sprint {
+            id: sprint.id,
+            name: sprint.name.clone(),
+            state: match sprint.state.as_str() {
+                "active" => SprintState::Active,
+                "closed" => SprintState::Closed,
+                _ => SprintState::Future,
+            },
+            goal: sprint.goal.clone().filter(|g| !g.trim().is_empty()),
+            start_date: sprint.start_date,
+            end_date: sprint.end_date,
+            complete_date: sprint.complete_date,
+            board_id: sprint.origin_board_id,
+        }
+    }
+
+    /// `changelog` is the issue's full changelog, which the embedded one may not be
+    fn map_sprint_issue(
+        &self,
+        issue: &JiraIssue,
+        changelog: Option<&JiraChangelog>,
+        sprint_id: u64,
+        mapping: &JiraFieldMapping,
+    ) -> SprintIssue {
+        let ticket = self.map_issue(issue, mapping);
+
+        let completed_at = (ticket.status.category == StatusCategory::Done)
+            .then(|| changelog.and_then(last_status_change).unwrap_or(ticket.updated_at));
+
+        SprintIssue {
+            added_at: changelog.and_then(|c| added_to_sprint_at(c, sprint_id)),
+            carried_over_from: mapping
+                .sprint
+                .as_deref()
+                .and_then(|id| issue.custom_field(id))
+                .and_then(fields::previous_sprint_name),
+            completed_at,
+            ticket,
+        }
+    }
+
+    fn agile_error(status: u16, body: &str, resource: &str) -> IntegrationError {
+        match status {
+            401 => IntegrationError::Auth("Invalid credentials".to_string()),
+            403 => IntegrationError::PermissionDenied(resource.to_string()),
+            404 => IntegrationError::NotFound(format!("{} not found", resource)),
+            status => IntegrationError::ApiError(format!("Status {}: {}", status, body)),
+        }
+    }
+}
+
+/// When the issue was last moved into the sprint, according to its changelog
+fn added_to_sprint_at(changelog: &JiraChangelog, sprint_id: u64) -> Option<DateTime<Utc>> {
+    changelog
+        .histories
+        .iter()
+        .filter(|history| {
+            history.items.iter().any(|item| {
+                item.field.eq_ignore_ascii_case("sprint")
+                    && contains_sprint(item.to.as_deref(), sprint_id)
+                    && !contains_sprint(item.from.as_deref(), sprint_id)
+            })
+        })
+        .map(|history| history.created)
+        .max()
+}
+
+/// Time of the latest status change, which for a done ticket is when it was finished
+fn last_status_change(changelog: &JiraChangelog) -> Option<DateTime<Utc>> {
+    changelog
+        .histories
+        .iter()
+        .filter(|history| history.items.iter().any(|item| item.field == "status"))
+        .map(|history| history.created)
+        .max()
+}
+
+/// Whether a comma separated list of sprint ids (`"12, 13"`) holds `sprint_id`
+fn contains_sprint(ids: Option<&str>, sprint_id: u64) -> bool {
+    ids.is_some_and(|ids| ids.split(',').any(|id| id.trim().parse() == Ok(sprint_id)))
+}
+
+#[async_trait]
+impl SprintRepository for JiraClient {
+    async fn list_boards(&self, project: Option<&str>) -> Result<Vec<Board>, IntegrationError> {
+        let mut boards = Vec::new();
+        let mut start_at = 0;
+
+        loop {
+            let mut url = self.config().agile_url(&format!(
+                "board?startAt={}&maxResults={}",
+                start_at, BOARD_PAGE_SIZE
+            ));
+            if let Some(project) = project {
+                url.push_str(&format!("&projectKeyOrId={}", urlencoding::encode(project)));
+            }
+
+            let response = self.get(&url).await?;
+            let page: AgilePage<AgileBoard> = match response.status {
+                200 => response.json()?,
+                status => return Err(Self::agile_error(status, &response.body, "Boards")),
+            };
+
+            start_at += page.values.len();
+            boards.extend(page.values.iter().map(|b| self.map_board(b)));
+            if page.is_last || page.values.is_empty() {
+                break;
+            }
+        }
+
+        Ok(boards)
+    }
+
+    async fn get_active_sprint(&self, board_id: u64) -> Result<Option<Sprint>, IntegrationError> {
+        let url = self.config().agile_url(&format!("board/{}/sprint?state=active", board_id));
+
+        let response = self.get(&url).await?;
+
+        match response.status {
+            200 => {
+                let page: AgilePage<AgileSprint> = response.json()?;
+                // Parallel sprints are possible; the one ending first is the most pressing
+                Ok(page
+                    .values
+                    .iter()
+                    .map(|s| self.map_sprint(s))
+                    .min_by_key(|s| s.end_date.unwrap_or(DateTime::<Utc>::MAX_UTC)))
+            }
+            // Kanban boards do not support sprints
+            400 => Ok(None),
+            status => Err(Self::agile_error(
+                status,
+                &response.body,
+                &format!("Board {}", board_id),
+            )),
+        }
+    }
+
+    async fn get_sprint_issues(&self, sprint_id: u64) -> Result<Vec<SprintIssue>, IntegrationError> {
+        let mapping = self.field_mapping().await;
+        let fields = issue_fields(&mapping).join(",");
+        let mut issues = Vec::new();
+        let mut start_at = 0;
+
+        loop {
+            let url = self.config().agile_url(&format!(
+                "sprint/{}/issue?startAt={}&maxResults={}&fields={}&expand=changelog",
+                sprint_id, start_at, ISSUE_PAGE_SIZE, fields
+            ));
+
+            let response = self.get(&url).await?;
+            let page: AgileIssuePage = match response.status {
+                200 => response.json()?,
+                status => {
+                    return Err(Self::agile_error(
+                        status,
+                        &response.body,
+                        &format!("Sprint {}", sprint_id),
+                    ))
+                }
+            };
+
+            start_at += page.issues.len();
+            for issue in &page.issues {
+                // The embedded changelog only holds the latest entries of busy issues
+                let fetched = match &issue.changelog {
+                    Some(changelog) if changelog.is_truncated() => Some(self.fetch_changelog(&issue.key).await?),
+                    _ => None,
+                };
+                let changelog = fetched.as_ref().or(issue.changelog.as_ref());
+                issues.push(self.map_sprint_issue(issue, changelog, sprint_id, &mapping));
+            }
+            if page.issues.is_empty() || start_at >= page.total {
+                break;
+            }
+        }
+
+        Ok(issues)
+    }
+}
+
+// ===== Jira Agile API Response Types =====
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AgilePage<T> {
+    values: Vec<T>,
+    #[serde(default)]
+    is_last: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct AgileBoard {
+    id: u64,
+    name: String,
+    #[serde(rename = "type")]
+    board_type: String,
+    location: Option<AgileBoardLocation>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AgileBoardLocation {
+    project_key: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AgileSprint {
+    id: u64,
+    name: String,
+    state: String,
+    goal: Option<String>,
+    start_date: Option<DateTime<Utc>>,
+    end_date: Option<DateTime<Utc>>,
+    complete_date: Option<DateTime<Utc>>,
+    origin_board_id: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AgileIssuePage {
+    issues: Vec<JiraIssue>,
+    #[serde(default)]
+    total: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::jira::JiraConfig;
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    fn client() -> JiraClient {
+        JiraClient::new(
+            JiraConfig::new("https://test.atlassian.net", "user@test.com").with_token("test-token"),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_agile_url() {
+        let config = JiraConfig::new("https://jira.internal/", "jdoe");
+        assert_eq!(config.agile_url("board/1/sprint"), "https://jira.internal/rest/agile/1.0/board/1/sprint");
+    }
+
+    #[test]
+    fn test_map_board() {
+        let page: AgilePage<AgileBoard> = serde_json::from_value(json!({
+            "maxResults": 50, "startAt": 0, "isLast": true,
+            "values": [
+                { "id": 84, "name": "Payments board", "type": "scrum",
+                  "location": { "projectId": 10040, "projectKey": "PAY" } },
+                { "id": 92, "name": "Support", "type": "kanban" }
+            ]
+        }))
+        .unwrap();
+        let client = client();
+
+        let scrum = client.map_board(&page.values[0]);
+        assert_eq!(scrum.project_key.as_deref(), Some("PAY"));
+        assert!(scrum.has_sprints());
+        assert!(!client.map_board(&page.values[1]).has_sprints());
+        assert!(page.is_last);
+    }
+
+    #[test]
+    fn test_map_sprint() {
+        let sprint: AgileSprint = serde_json::from_value(json!({
+            "id": 42, "name": "Sprint 42", "state": "active", "goal": "",
+            "startDate": "2024-02-26T09:00:00.000Z",
+            "endDate": "2024-03-08T17:00:00.000+01:00",
+            "originBoardId": 84
+        }))
+        .unwrap();
+
+        let sprint = client().map_sprint(&sprint);
+
+        assert_eq!(sprint.state, SprintState::Active);
+        assert_eq!(sprint.goal, None);
+        assert_eq!(sprint.end_date, Some(Utc.with_ymd_and_hms(2024, 3, 8, 16, 0, 0).unwrap()));
+        assert_eq!(sprint.board_id, Some(84));
+    }
+
+    #[test]
+    fn test_added_to_sprint_at() {
+        let changelog: JiraChangelog = serde_json::from_value(json!({
+            "histories": [
+                { "created": "2024-02-20T10:00:00.000+0000",
+                  "items": [{ "field": "Sprint", "from": "41", "to": "41, 42",
+                              "fromString": "Sprint 41", "toString": "Sprint 41, Sprint 42" }] },
+                { "created": "2024-02-27T10:00:00.000+0000",
+                  "items": [{ "field": "status", "from": "1", "to": "3" }] },
+                { "created": "2024-02-28T15:30:00.000+0000",
+                  "items": [{ "field": "Sprint", "from": "41", "to": "41,42" }] }
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(
+            added_to_sprint_at(&changelog, 42),
+            Some(Utc.with_ymd_and_hms(2024, 2, 28, 15, 30, 0).unwrap())
+        );
+        assert_eq!(added_to_sprint_at(&changelog, 41), None);
+        assert_eq!(added_to_sprint_at(&changelog, 4), None);
+    }
+
+    #[test]
+    fn test_sprint_issue_scope_change() {
+        let page: AgileIssuePage = serde_json::from_value(json!({
+            "startAt": 0, "maxResults": 100, "total": 1,
+            "issues": [{
+                "id": "10001", "key": "PAY-7",
+                "fields": {
+                    "summary": "Refund webhook",
+                    "status": { "name": "To Do", "statusCategory": { "key": "new" } }
+                },
+                "changelog": { "histories": [
+                    { "created": "2024-02-28T15:30:00.000+0000",
+                      "items": [{ "field": "Sprint", "from": null, "to": "42" }] }
+                ] }
+            }]
+        }))
+        .unwrap();
+        let sprint = client().map_sprint(&AgileSprint {
+            id: 42,
+            name: "Sprint 42".to_string(),
+            state: "active".to_string(),
+            goal: None,
+            start_date: Some(Utc.with_ymd_and_hms(2024, 2, 26, 9, 0, 0).unwrap()),
+            end_date: None,
+            complete_date: None,
+            origin_board_id: None,
+        });
+
+        let issue = client().map_sprint_issue(&page.issues[0], page.issues[0].changelog.as_ref(), 42, &JiraFieldMapping::default());
+
+        assert_eq!(issue.ticket.key, "PAY-7");
+        assert!(issue.is_added_after_start(&sprint));
+        assert_eq!(issue.completed_at, None);
+    }
+
+    #[test]
+    fn test_sprint_issue_done_and_carried_over() {
+        let issue: JiraIssue = serde_json::from_value(json!({
+            "id": "10002", "key": "PAY-3",
+            "fields": {
+                "summary": "Ledger export",
+                "status": { "name": "Done", "statusCategory": { "key": "done" } },
+                "updated": "2024-03-01T12:00:00.000+0000",
+                "customfield_10020": [
+                    { "id": 41, "name": "Sprint 41", "state": "closed" },
+                    { "id": 42, "name": "Sprint 42", "state": "active" }
+                ]
+            },
+            "changelog": { "histories": [
+                { "created": "2024-02-27T09:00:00.000+0000",
+                  "items": [{ "field": "status", "from": "1", "to": "3" }] },
+                { "created": "2024-02-29T16:45:00.000+0000",
+                  "items": [{ "field": "status", "from": "3", "to": "10001" }] },
+                { "created": "2024-03-01T12:00:00.000+0000",
+                  "items": [{ "field": "labels", "from": null, "to": "billing" }] }
+            ] }
+        }))
+        .unwrap();
+        let mapping = JiraFieldMapping::default().with_sprint("customfield_10020");
+
+        let issue = client().map_sprint_issue(&issue, issue.changelog.as_ref(), 42, &mapping);
+
+        assert_eq!(issue.carried_over_from.as_deref(), Some("Sprint 41"));
+        assert_eq!(issue.completed_at, Some(Utc.with_ymd_and_hms(2024, 2, 29, 16, 45, 0).unwrap()));
+        assert_eq!(issue.added_at, None);
+    }
+}
@@ -0,0 +1,257 @@
+//! Jira Changelogs
+//!
+//! Status transitions from issue changelogs, for cycle-time analytics. Searches
+//! embed the latest 100 changelog entries of each issue; longer histories are
+//! fetched from `/issue/{key}/changelog` on Cloud, or from the expanded issue on
+//! Data Center, which always returns the full history.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use super::client::{is_issue_key, JiraChangelog, JiraClient, JiraDeployment, JiraHistory, JiraIssue};
+use crate::integrations::traits::{
+    IntegrationError, StatusCategory, StatusTransition, TicketHistory, TicketHistoryRepository,
+    TicketSearchQuery,
+};
+
+/// Largest page of changelog entries `/issue/{key}/changelog` returns
+const CHANGELOG_PAGE_SIZE: usize = 100;
+
+/// Largest page of searched issues when changelogs are expanded
+const HISTORY_PAGE_SIZE: usize = 50;
+
+impl JiraClient {
+    /// Status changes of a changelog, oldest first
+    fn map_transitions(
+        &self,
+        changelog: &JiraChangelog,
+        categories: &HashMap<String, StatusCategory>,
+    ) -> Vec<StatusTransition> {
+        let category = |id: &Option<String>| id.as_ref().and_then(|id| categories.get(id)).copied();
+
+        let mut histories: Vec<&JiraHistory> = changelog.histories.iter().collect();
+        histories.sort_by_key(|history| history.created);
+
+        histories
+            .into_iter()
+            .flat_map(|history| {
+                history
+                    .items
+                    .iter()
+                    .filter(|item| item.field.eq_ignore_ascii_case("status"))
+                    .map(move |item| StatusTransition {
+                        at: history.created,
+                        author: history.author.as_ref().map(|a| self.map_user(a)),
+                        from: item.from_display.clone(),
+                        to: item.to_display.clone().unwrap_or_default(),
+                        from_category: category(&item.from),
+                        to_category: category(&item.to),
+                    })
+            })
+            .collect()
+    }
+
+    /// Full changelog of an issue
+    pub(super) async fn fetch_changelog(&self, key: &str) -> Result<JiraChangelog, IntegrationError> {
+        // Issue refs of other trackers, like `#42`, cannot exist here
+        if !is_issue_key(key) {
+            return Err(IntegrationError::NotFound(format!("Issue {} not found", key)));
+        }
+        let encoded = urlencoding::encode(key);
+
+        if self.config().deployment == JiraDeployment::DataCenter {
+            let url = self
+                .config()
+                .api_url(&format!("issue/{}?expand=changelog&fields=summary,status", encoded));
+            let response = self.get(&url).await?;
+
+            return match response.status {
+                200 => {
+                    let issue: JiraIssue = response.json()?;
+                    Ok(issue.changelog.unwrap_or_default())
+                }
+                status => Err(Self::changelog_error(status, &response.body, key)),
+            };
+        }
+
+        let mut histories = Vec::new();
+        loop {
+            let url = self.config().api_url(&format!(
+                "issue/{}/changelog?startAt={}&maxResults={}",
+                encoded,
+                histories.len(),
+                CHANGELOG_PAGE_SIZE
+            ));
+            let response = self.get(&url).await?;
+
+            let page: JiraChangelogPage = match response.status {
+                200 => response.json()?,
+                status => return Err(Self::changelog_error(status, &response.body, key)),
+            };
+
+            let done = page.is_last || page.values.is_empty();
+            histories.extend(page.values);
+            if done {
+                break;
+            }
+        }
+
+        Ok(JiraChangelog {
+            total: Some(histories.len()),
+            histories,
+        })
+    }
+
+    fn changelog_error(status: u16, body: &str, key: &str) -> IntegrationError {
+        match status {
+            401 => IntegrationError::Auth("Invalid credentials".to_string()),
+            404 => IntegrationError::NotFound(format!("Issue {} not found", key)),
+            status => IntegrationError::ApiError(format!("Status {}: {}", status, body)),
+        }
+    }
+}
+
+#[async_trait]
+impl TicketHistoryRepository for JiraClient {
+    async fn get_status_history(&self, key: &str) -> Result<Vec<StatusTransition>, IntegrationError> {
+        let categories = self.status_categories().await;
+        let changelog = self.fetch_changelog(key).await?;
+        Ok(self.map_transitions(&changelog, &categories))
+    }
+
+    async fn search_with_history(&self, query: &TicketSearchQuery) -> Result<Vec<TicketHistory>, IntegrationError> {
+        let mapping = self.field_mapping().await;
+        let categories = self.status_categories().await;
+        let mut tickets = Vec::new();
+        let mut page_token: Option<String> = None;
+
+        while tickets.len() < query.limit {
+            let page_query = TicketSearchQuery {
+                limit: (query.limit - tickets.len()).min(HISTORY_PAGE_SIZE),
+                ..query.clone()
+            };
+            let mut body = self.search_body(&page_query, page_token.as_deref(), &mapping);
+            body["expand"] = match self.config().deployment {
+                JiraDeployment::Cloud => "changelog".into(),
+                JiraDeployment::DataCenter => serde_json::json!(["changelog"]),
+            };
+
+            let result = self.post_search(&body).await?;
+            if result.issues.is_empty() {
+                break;
+            }
+
+            for issue in &result.issues {
+                let transitions = match issue.changelog.as_ref().filter(|c| !c.is_truncated()) {
+                    Some(changelog) => self.map_transitions(changelog, &categories),
+                    None => self.map_transitions(&self.fetch_changelog(&issue.key).await?, &categories),
+                };
+                tickets.push(TicketHistory {
+                    ticket: self.map_issue(issue, &mapping),
+                    transitions,
+                });
+            }
+
+            page_token = Self::next_page_token(&result);
+            if page_token.is_none() {
+                break;
+            }
+        }
+
+        tickets.truncate(query.limit);
+        Ok(tickets)
+    }
+}
+
+// ===== Jira Changelog API Types =====
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JiraChangelogPage {
+    values: Vec<JiraHistory>,
+    #[serde(default)]
+    is_last: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::jira::JiraConfig;
+    use serde_json::json;
+
+    fn client() -> JiraClient {
+        JiraClient::new(
+            JiraConfig::new("https://test.atlassian.net", "user@test.com").with_token("test-token"),
+        )
+        .unwrap()
+    }
+
+    fn categories() -> HashMap<String, StatusCategory> {
+        HashMap::from([
+            ("1".to_string(), StatusCategory::Todo),
+            ("3".to_string(), StatusCategory::InProgress),
+            ("10001".to_string(), StatusCategory::Done),
+        ])
+    }
+
+    #[test]
+    fn test_map_transitions() {
+        let changelog: JiraChangelog = serde_json::from_value(json!({
+            "startAt": 0, "maxResults": 100, "total": 3,
+            "histories": [
+                { "id": "3", "created": "2024-03-05T16:00:00.000+0000",
+                  "author": { "accountId": "abc", "displayName": "Jane Doe" },
+                  "items": [{ "field": "status", "fieldtype": "jira", "fieldId": "status",
+                              "from": "3", "fromString": "In Progress", "to": "10001", "toString": "Done" }] },
+                { "id": "1", "created": "2024-03-01T09:00:00.000+0000",
+                  "items": [{ "field": "status", "from": "1", "fromString": "To Do", "to": "3", "toString": "In Progress" },
+                            { "field": "assignee", "from": null, "to": "abc" }] },
+                { "id": "2", "created": "2024-03-02T09:00:00.000+0000",
+                  "items": [{ "field": "labels", "from": null, "to": "billing" }] }
+            ]
+        }))
+        .unwrap();
+
+        let transitions = client().map_transitions(&changelog, &categories());
+
+        assert_eq!(transitions.len(), 2);
+        assert_eq!(transitions[0].from.as_deref(), Some("To Do"));
+        assert_eq!(transitions[0].to_category, Some(StatusCategory::InProgress));
+        assert_eq!(transitions[1].to, "Done");
+        assert_eq!(transitions[1].to_category, Some(StatusCategory::Done));
+        assert_eq!(transitions[1].author.as_ref().unwrap().name, "Jane Doe");
+        assert!(!changelog.is_truncated());
+    }
+
+    #[test]
+    fn test_unknown_status_has_no_category() {
+        let changelog: JiraChangelog = serde_json::from_value(json!({
+            "histories": [{ "created": "2024-03-01T09:00:00.000+0000",
+                "items": [{ "field": "status", "from": "1", "fromString": "To Do", "to": "777", "toString": "Legacy QA" }] }]
+        }))
+        .unwrap();
+
+        let transitions = client().map_transitions(&changelog, &categories());
+
+        assert_eq!(transitions[0].from_category, Some(StatusCategory::Todo));
+        assert_eq!(transitions[0].to_category, None);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_changelog_rejects_issue_refs() {
+        assert!(matches!(client().fetch_changelog("#42").await, Err(IntegrationError::NotFound(_))));
+        assert!(matches!(client().fetch_changelog("acme/api#42").await, Err(IntegrationError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_truncated_changelog() {
+        let changelog: JiraChangelog = serde_json::from_value(json!({
+            "startAt": 0, "maxResults": 1, "total": 140,
+            "histories": [{ "created": "2024-03-01T09:00:00.000+0000", "items": [] }]
+        }))
+        .unwrap();
+
+        assert!(changelog.is_truncated());
+    }
+}
@@ -6,24 +6,74 @@ use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tokio::sync::OnceCell;
 
+use super::adf;
+use super::wiki;
+use super::fields::{self, JiraFieldDefinition, JiraFieldMapping};
+use super::jql::{Field, Jql, Order, Value};
+use crate::integrations::http_cache::{send_get, HttpCache, HttpResponse};
+use crate::integrations::rate_limit::RateLimitTracker;
 use crate::integrations::traits::{
-    IntegrationError, Priority, StatusCategory, Ticket, TicketRepository, TicketSearchQuery,
-    TicketStatus, User,
+    Assignee, Comment, IntegrationError, Page, Priority, SprintFilter, StatusCategory, Ticket, TicketCommands,
+    TicketRepository, TicketSearchQuery, TicketStatus, Transition, User,
 };
 
+/// Largest page `/search/jql` returns
+const MAX_PAGE_SIZE: usize = 100;
+
+/// Issue fields a `Ticket` is mapped from, besides the custom ones
+const TICKET_FIELDS: &[&str] = &[
+    "summary", "description", "status", "assignee", "reporter",
+    "priority", "labels", "parent", "updated", "created",
+];
+
+/// Fields to request for mapping issues to tickets
+pub(super) fn issue_fields(mapping: &JiraFieldMapping) -> Vec<&str> {
+    let mut fields = TICKET_FIELDS.to_vec();
+    fields.extend(mapping.field_ids());
+    fields
+}
+
+/// Where Jira is hosted, which decides the API flavour
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JiraDeployment {
+    /// Jira Cloud: REST v3, Basic auth with email and API token, ADF rich text
+    #[default]
+    Cloud,
+    /// Jira Server / Data Center: REST v2, Bearer personal access token, wiki markup
+    #[serde(alias = "server")]
+    DataCenter,
+}
+
+impl JiraDeployment {
+    pub fn api_version(&self) -> u8 {
+        match self {
+            JiraDeployment::Cloud => 3,
+            JiraDeployment::DataCenter => 2,
+        }
+    }
+}
+
 /// Jira client configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JiraConfig {
     /// Base URL (e.g., "https://company.atlassian.net")
     pub base_url: String,
-    /// Username (email)
+    /// Username (email on Cloud, login name on Data Center)
     pub username: String,
-    /// API token (not stored here, retrieved from credential manager)
+    /// API token or personal access token (not stored here, retrieved from credential manager)
     #[serde(skip)]
     pub token: Option<String>,
     /// Default project
     pub default_project: Option<String>,
+    #[serde(default)]
+    pub deployment: JiraDeployment,
+    /// Custom field ids; unset ones are discovered from the site
+    #[serde(default)]
+    pub field_mapping: JiraFieldMapping,
 }
 
 impl JiraConfig {
@@ -33,6 +83,8 @@ impl JiraConfig {
             username: username.to_string(),
             token: None,
             default_project: None,
+            deployment: JiraDeployment::Cloud,
+            field_mapping: JiraFieldMapping::default(),
         }
     }
 
@@ -45,6 +97,26 @@ impl JiraConfig {
         self.default_project = Some(project.to_string());
         self
     }
+
+    pub fn with_deployment(mut self, deployment: JiraDeployment) -> Self {
+        self.deployment = deployment;
+        self
+    }
+
+    /// URL of a REST API resource for the configured deployment
+    pub fn api_url(&self, path: &str) -> String {
+        format!("{}/rest/api/{}/{}", self.base_url, self.deployment.api_version(), path)
+    }
+
+    pub fn with_field_mapping(mut self, mapping: JiraFieldMapping) -> Self {
+        self.field_mapping = mapping;
+        self
+    }
+
+    /// URL of a Jira Software (Agile) API resource, the same on Cloud and Data Center
+    pub fn agile_url(&self, path: &str) -> String {
+        format!("{}/rest/agile/1.0/{}", self.base_url, path)
+    }
 }
 
 /// Jira API client
@@ -52,6 +124,10 @@ impl JiraConfig {
 pub struct JiraClient {
     config: JiraConfig,
     http_client: Client,
+    rate_limiter: RateLimitTracker,
+    http_cache: Option<HttpCache>,
+    resolved_fields: OnceCell<JiraFieldMapping>,
+    status_categories: OnceCell<HashMap<String, StatusCategory>>,
 }
 
 impl JiraClient {
@@ -71,94 +147,396 @@ impl JiraClient {
         Ok(Self {
             config,
             http_client,
+            rate_limiter: RateLimitTracker::new("jira"),
+            http_cache: None,
+            resolved_fields: OnceCell::new(),
+            status_categories: OnceCell::new(),
         })
     }
 
+    /// Share a rate limit tracker, e.g. with the background poller
+    pub fn with_rate_limiter(mut self, tracker: RateLimitTracker) -> Self {
+        self.rate_limiter = tracker;
+        self
+    }
+
+    pub fn rate_limiter(&self) -> &RateLimitTracker {
+        &self.rate_limiter
+    }
+
+    /// Send issue lookups conditionally and reuse cached payloads on 304
+    pub fn with_http_cache(mut self, cache: HttpCache) -> Self {
+        self.http_cache = Some(cache);
+        self
+    }
+
     /// Create for testing with mock capabilities
     #[cfg(test)]
     pub fn new_for_test(config: JiraConfig, client: Client) -> Self {
         Self {
             config,
             http_client: client,
+            rate_limiter: RateLimitTracker::new("jira"),
+            http_cache: None,
+            resolved_fields: OnceCell::new(),
+            status_categories: OnceCell::new(),
         }
     }
 
     /// Build authorization header value
     fn auth_header(&self) -> String {
-        use base64::Engine;
-        let credentials = format!(
-            "{}:{}",
-            self.config.username,
-            self.config.token.as_deref().unwrap_or("")
-        );
-        format!(
-            "Basic {}",
-            base64::engine::general_purpose::STANDARD.encode(credentials)
-        )
+        let token = self.config.token.as_deref().unwrap_or("");
+        match self.config.deployment {
+            JiraDeployment::Cloud => {
+                use base64::Engine;
+                let credentials = format!("{}:{}", self.config.username, token);
+                format!(
+                    "Basic {}",
+                    base64::engine::general_purpose::STANDARD.encode(credentials)
+                )
+            }
+            JiraDeployment::DataCenter => format!("Bearer {}", token),
+        }
+    }
+
+    /// GET a resource through the HTTP cache, observing rate limits
+    pub(super) async fn get(&self, url: &str) -> Result<HttpResponse, IntegrationError> {
+        self.rate_limiter.check()?;
+        let request = self
+            .http_client
+            .get(url)
+            .header("Authorization", self.auth_header())
+            .header("Accept", "application/json");
+        let response = send_get(self.http_cache.as_ref(), url, request).await?;
+
+        if let Some(err) = self.rate_limiter.observe(response.status, &response.headers) {
+            return Err(err);
+        }
+
+        Ok(response)
+    }
+
+    pub(super) fn config(&self) -> &JiraConfig {
+        &self.config
+    }
+
+    /// Plain text and Markdown of a rich text field: ADF on Cloud, wiki markup on Data Center
+    fn render_rich_text(&self, value: Option<&serde_json::Value>) -> Option<(String, String)> {
+        match self.config.deployment {
+            JiraDeployment::Cloud => adf::render_field(value),
+            JiraDeployment::DataCenter => wiki::render_field(value.and_then(|v| v.as_str())),
+        }
+    }
+
+    /// Field mapping of the site: the configured ids, completed by discovery on first use
+    pub(super) async fn field_mapping(&self) -> JiraFieldMapping {
+        let overrides = &self.config.field_mapping;
+        if overrides.is_complete() {
+            return overrides.clone();
+        }
+
+        let resolved = self
+            .resolved_fields
+            .get_or_try_init(|| async {
+                self.discover_fields().await.map(|discovered| overrides.clone().or(discovered))
+            })
+            .await;
+
+        match resolved {
+            Ok(mapping) => mapping.clone(),
+            Err(e) => {
+                // Retried on the next request; tickets just lack the custom values meanwhile
+                log::warn!("Jira field discovery failed: {}", e);
+                overrides.clone()
+            }
+        }
+    }
+
+    async fn discover_fields(&self) -> Result<JiraFieldMapping, IntegrationError> {
+        let url = self.config.api_url("field");
+
+        let response = self.get(&url).await?;
+
+        match response.status {
+            200 => {
+                let definitions: Vec<JiraFieldDefinition> = response.json()?;
+                Ok(JiraFieldMapping::discover(&definitions))
+            }
+            401 => Err(IntegrationError::Auth("Invalid credentials".to_string())),
+            status => Err(IntegrationError::ApiError(format!(
+                "Status {}: {}",
+                status, response.body
+            ))),
+        }
+    }
+
+    /// Category of every status on the site by status id, loaded on first use
+    pub(super) async fn status_categories(&self) -> HashMap<String, StatusCategory> {
+        let loaded = self
+            .status_categories
+            .get_or_try_init(|| self.load_status_categories())
+            .await;
+
+        match loaded {
+            Ok(categories) => categories.clone(),
+            Err(e) => {
+                // Retried on the next request; transitions just lack categories meanwhile
+                log::warn!("Jira status lookup failed: {}", e);
+                HashMap::new()
+            }
+        }
+    }
+
+    async fn load_status_categories(&self) -> Result<HashMap<String, StatusCategory>, IntegrationError> {
+        let url = self.config.api_url("status");
+
+        let response = self.get(&url).await?;
+
+        match response.status {
+            200 => {
+                let statuses: Vec<JiraStatusDefinition> = response.json()?;
+                Ok(statuses
+                    .iter()
+                    .map(|s| (s.id.clone(), self.map_status_category(&s.status_category)))
+                    .collect())
+            }
+            401 => Err(IntegrationError::Auth("Invalid credentials".to_string())),
+            status => Err(IntegrationError::ApiError(format!(
+                "Status {}: {}",
+                status, response.body
+            ))),
+        }
     }
 
     /// Build JQL query from search parameters
     fn build_jql(&self, query: &TicketSearchQuery) -> String {
-        let mut conditions = Vec::new();
+        let mut jql = Jql::new();
+        if query.project.is_none() {
+            if let Some(ref default_project) = self.config.default_project {
+                jql = jql.eq(Field::Project, Value::Key(default_project.clone()));
+            }
+        }
 
+        Self::filter_jql(jql, query)
+            .order_by(Field::Updated, Order::Desc)
+            .to_string()
+    }
+
+    /// Add the filters of a query, and of its alternative groups, to `jql`
+    fn filter_jql(mut jql: Jql, query: &TicketSearchQuery) -> Jql {
         if let Some(ref project) = query.project {
-            conditions.push(format!("project = {}", project));
-        } else if let Some(ref default_project) = self.config.default_project {
-            conditions.push(format!("project = {}", default_project));
+            jql = jql.eq(Field::Project, Value::Key(project.clone()));
         }
 
         if let Some(ref text) = query.text {
-            conditions.push(format!("text ~ \"{}\"", text));
+            jql = jql.contains(Field::Text, text);
         }
 
         if let Some(ref assignee) = query.assignee {
-            conditions.push(format!("assignee = \"{}\"", assignee));
+            jql = jql.eq(Field::Assignee, Value::user(assignee));
+        }
+
+        if let Some(ref reporter) = query.reporter {
+            jql = jql.eq(Field::Reporter, Value::user(reporter));
         }
 
         if let Some(ref status) = query.status {
-            conditions.push(format!("status = \"{}\"", status));
+            jql = jql.eq(Field::Status, Value::string(status));
         }
 
-        if conditions.is_empty() {
-            "ORDER BY updated DESC".to_string()
-        } else {
-            format!("{} ORDER BY updated DESC", conditions.join(" AND "))
+        if let Some(category) = query.status_category {
+            jql = jql.eq(Field::StatusCategory, Value::string(Self::status_category_name(category)));
+        }
+
+        jql = jql
+            .one_of(Field::Labels, query.labels.iter().map(|l| Value::string(l)).collect())
+            .one_of(Field::Priority, query.priorities.iter().map(|p| Value::string(p.as_str())).collect())
+            .one_of(Field::IssueType, query.issue_types.iter().map(|t| Value::string(t)).collect());
+
+        jql = match query.sprint {
+            Some(SprintFilter::Open) => jql.in_function(Field::Sprint, "openSprints()"),
+            Some(SprintFilter::Closed) => jql.in_function(Field::Sprint, "closedSprints()"),
+            Some(SprintFilter::Specific(ref sprint)) => match sprint.parse() {
+                Ok(id) => jql.eq(Field::Sprint, Value::Number(id)),
+                Err(_) => jql.eq(Field::Sprint, Value::string(sprint)),
+            },
+            None => jql,
+        };
+
+        if let Some(since) = query.updated_since {
+            jql = jql.at_least(Field::Updated, Value::Date(since));
+        }
+
+        jql.any_of(
+            query
+                .any_of
+                .iter()
+                .map(|group| Self::filter_jql(Jql::new(), group))
+                .collect(),
+        )
+    }
+
+    /// Name of a status category in JQL
+    fn status_category_name(category: StatusCategory) -> &'static str {
+        match category {
+            StatusCategory::Todo => "To Do",
+            StatusCategory::InProgress => "In Progress",
+            StatusCategory::Done => "Done",
+        }
+    }
+
+    /// Search endpoint: `/search/jql` with page tokens on Cloud, `/search` with `startAt` on Data Center
+    fn search_url(&self) -> String {
+        match self.config.deployment {
+            JiraDeployment::Cloud => self.config.api_url("search/jql"),
+            JiraDeployment::DataCenter => self.config.api_url("search"),
+        }
+    }
+
+    /// Request body of a search for one page
+    pub(super) fn search_body(
+        &self,
+        query: &TicketSearchQuery,
+        page_token: Option<&str>,
+        mapping: &JiraFieldMapping,
+    ) -> serde_json::Value {
+        let fields = issue_fields(mapping);
+
+        let mut body = serde_json::json!({
+            "jql": self.build_jql(query),
+            "maxResults": query.limit.min(MAX_PAGE_SIZE),
+            "fields": fields
+        });
+        match self.config.deployment {
+            JiraDeployment::Cloud => {
+                if let Some(token) = page_token {
+                    body["nextPageToken"] = serde_json::Value::String(token.to_string());
+                }
+            }
+            JiraDeployment::DataCenter => {
+                let start_at: usize = page_token.and_then(|t| t.parse().ok()).unwrap_or(0);
+                body["startAt"] = start_at.into();
+            }
+        }
+        body
+    }
+
+    /// Run a search request built by `search_body`
+    pub(super) async fn post_search(&self, body: &serde_json::Value) -> Result<JiraSearchResult, IntegrationError> {
+        self.rate_limiter.check()?;
+        let response = self
+            .http_client
+            .post(self.search_url())
+            .header("Authorization", self.auth_header())
+            .header("Content-Type", "application/json")
+            .json(body)
+            .send()
+            .await?;
+
+        let status = response.status().as_u16();
+        if let Some(err) = self.rate_limiter.observe(status, response.headers()) {
+            return Err(err);
+        }
+
+        match status {
+            200 => response
+                .json()
+                .await
+                .map_err(|e| IntegrationError::ParseError(e.to_string())),
+            401 => Err(IntegrationError::Auth("Invalid credentials".to_string())),
+            status => {
+                let body = response.text().await.unwrap_or_default();
+                Err(IntegrationError::ApiError(format!(
+                    "Status {}: {}",
+                    status, body
+                )))
+            }
+        }
+    }
+
+    /// Token of the page after `result`: `nextPageToken` on Cloud, the next `startAt` on Data Center
+    pub(super) fn next_page_token(result: &JiraSearchResult) -> Option<String> {
+        match (result.start_at, result.total) {
+            (Some(start_at), Some(total)) => {
+                let next = start_at + result.issues.len();
+                (!result.issues.is_empty() && next < total).then(|| next.to_string())
+            }
+            _ if result.is_last => None,
+            _ => result.next_page_token.clone(),
         }
     }
 
+    fn map_search_result(&self, result: JiraSearchResult, mapping: &JiraFieldMapping) -> Page<Ticket> {
+        let next_page_token = Self::next_page_token(&result);
+        Page::new(
+            result.issues.iter().map(|i| self.map_issue(i, mapping)).collect(),
+            next_page_token,
+        )
+    }
+
     /// Map Jira issue to domain Ticket
-    fn map_issue(&self, issue: &JiraIssue) -> Ticket {
+    pub(super) fn map_issue(&self, issue: &JiraIssue, mapping: &JiraFieldMapping) -> Ticket {
         let fields = &issue.fields;
+        let (description, description_markdown) = self.render_rich_text(fields.description.as_ref()).unzip();
+        let custom = |field: &Option<String>| field.as_deref().and_then(|id| issue.custom_field(id));
+
+        // Team-managed projects link epics as the parent issue
+        let parent_epic = fields
+            .parent
+            .as_ref()
+            .filter(|p| p.fields.as_ref().is_some_and(|f| f.issuetype.name.eq_ignore_ascii_case("Epic")))
+            .map(|p| p.key.clone());
 
         Ticket {
             id: issue.id.clone(),
             key: issue.key.clone(),
             summary: fields.summary.clone(),
-            description: fields.description.clone(),
+            description,
+            description_markdown,
             status: TicketStatus {
                 name: fields.status.name.clone(),
                 category: self.map_status_category(&fields.status.status_category),
             },
-            assignee: fields.assignee.as_ref().map(|a| User {
-                id: a.account_id.clone(),
-                name: a.display_name.clone(),
-                email: a.email_address.clone(),
-                avatar_url: a.avatar_urls.as_ref().and_then(|av| av.x48.clone()),
-            }),
-            reporter: fields.reporter.as_ref().map(|r| User {
-                id: r.account_id.clone(),
-                name: r.display_name.clone(),
-                email: r.email_address.clone(),
-                avatar_url: r.avatar_urls.as_ref().and_then(|av| av.x48.clone()),
-            }),
+            assignee: fields.assignee.as_ref().map(|a| self.map_user(a)),
+            reporter: fields.reporter.as_ref().map(|r| self.map_user(r)),
             priority: fields.priority.as_ref().map(|p| self.map_priority(&p.name)),
-            sprint: fields.sprint.as_ref().map(|s| s.name.clone()),
+            sprint: custom(&mapping.sprint).and_then(fields::sprint_name),
+            story_points: custom(&mapping.story_points).and_then(fields::story_points),
+            epic: custom(&mapping.epic).and_then(fields::epic_key).or(parent_epic),
+            team: custom(&mapping.team).and_then(fields::team_name),
             labels: fields.labels.clone().unwrap_or_default(),
             updated_at: fields.updated.unwrap_or_else(Utc::now),
             created_at: fields.created.unwrap_or_else(Utc::now),
         }
     }
 
+    pub(super) fn map_user(&self, user: &JiraUser) -> User {
+        User {
+            id: user
+                .account_id
+                .clone()
+                .or_else(|| user.name.clone())
+                .or_else(|| user.key.clone())
+                .unwrap_or_default(),
+            name: user.display_name.clone(),
+            email: user.email_address.clone(),
+            avatar_url: user.avatar_urls.as_ref().and_then(|av| av.x48.clone()),
+        }
+    }
+
+    fn map_comment(&self, comment: &JiraComment) -> Comment {
+        let (body, body_markdown) = self.render_rich_text(comment.body.as_ref()).unwrap_or_default();
+
+        Comment {
+            id: comment.id.clone(),
+            author: comment.author.as_ref().map(|a| self.map_user(a)),
+            body,
+            body_markdown,
+            created_at: comment.created,
+            updated_at: comment.updated.unwrap_or(comment.created),
+        }
+    }
+
     fn map_status_category(&self, category: &JiraStatusCategory) -> StatusCategory {
         match category.key.as_str() {
             "new" | "undefined" => StatusCategory::Todo,
@@ -178,84 +556,271 @@ impl JiraClient {
             _ => Priority::Medium,
         }
     }
-}
 
-#[async_trait]
-impl TicketRepository for JiraClient {
-    async fn find_by_id(&self, id: &str) -> Result<Ticket, IntegrationError> {
-        let url = format!(
-            "{}/rest/api/3/issue/{}",
-            self.config.base_url, id
-        );
+    fn map_transition(&self, transition: &JiraTransition) -> Transition {
+        Transition {
+            id: transition.id.clone(),
+            name: transition.name.clone(),
+            to: TicketStatus {
+                name: transition.to.name.clone(),
+                category: self.map_status_category(&transition.to.status_category),
+            },
+        }
+    }
 
-        let response = self
-            .http_client
-            .get(&url)
+    /// Assignee payload: account id on Cloud, username on Data Center, `null` to unassign
+    fn assignee_body(&self, user_id: Option<&str>) -> serde_json::Value {
+        match self.config.deployment {
+            JiraDeployment::Cloud => serde_json::json!({ "accountId": user_id }),
+            JiraDeployment::DataCenter => serde_json::json!({ "name": user_id }),
+        }
+    }
+
+    /// Comment payload: ADF on Cloud, wiki markup (plain text is valid markup) on Data Center
+    fn comment_body(&self, text: &str) -> serde_json::Value {
+        match self.config.deployment {
+            JiraDeployment::Cloud => serde_json::json!({ "body": adf::from_plain_text(text) }),
+            JiraDeployment::DataCenter => serde_json::json!({ "body": text }),
+        }
+    }
+
+    /// Send a request past the HTTP cache, for writes and state that changes with them
+    async fn send_uncached(&self, request: reqwest::RequestBuilder) -> Result<(u16, String), IntegrationError> {
+        self.rate_limiter.check()?;
+        let response = request
             .header("Authorization", self.auth_header())
             .header("Accept", "application/json")
             .send()
             .await?;
 
-        match response.status().as_u16() {
+        let status = response.status().as_u16();
+        if let Some(err) = self.rate_limiter.observe(status, response.headers()) {
+            return Err(err);
+        }
+
+        Ok((status, response.text().await.unwrap_or_default()))
+    }
+
+    /// API URL of a resource of issue `key`. Refs of other trackers, like
+    /// `#42`, cannot exist here and are NotFound without a request.
+    pub(super) fn issue_url(&self, key: &str, resource: &str) -> Result<String, IntegrationError> {
+        if !is_issue_key(key) {
+            return Err(IntegrationError::NotFound(format!("Issue {} not found", key)));
+        }
+        Ok(self.config.api_url(&format!("issue/{}/{}", urlencoding::encode(key), resource)))
+    }
+
+    /// Error of a failed request on issue `key`
+    fn issue_error(status: u16, body: &str, key: &str) -> IntegrationError {
+        match status {
+            401 => IntegrationError::Auth("Invalid credentials".to_string()),
+            403 => IntegrationError::PermissionDenied(format!("{}: {}", key, Self::error_message(body))),
+            404 => IntegrationError::NotFound(format!("Issue {} not found", key)),
+            status => IntegrationError::ApiError(format!("Status {}: {}", status, Self::error_message(body))),
+        }
+    }
+
+    /// Messages of a Jira error response, or the raw body when it has none
+    fn error_message(body: &str) -> String {
+        let Ok(error) = serde_json::from_str::<JiraErrorResponse>(body) else {
+            return body.to_string();
+        };
+
+        let mut messages = error.error_messages;
+        messages.extend(error.errors.into_values());
+        if messages.is_empty() {
+            body.to_string()
+        } else {
+            messages.join("; ")
+        }
+    }
+
+    /// Id of the authenticated user, as used in assignee payloads
+    async fn current_user_id(&self) -> Result<String, IntegrationError> {
+        let url = self.config.api_url("myself");
+
+        let response = self.get(&url).await?;
+
+        match response.status {
             200 => {
-                let issue: JiraIssue = response
-                    .json()
-                    .await
-                    .map_err(|e| IntegrationError::ParseError(e.to_string()))?;
-                Ok(self.map_issue(&issue))
+                let user: JiraUser = response.json()?;
+                Ok(self.map_user(&user).id)
+            }
+            401 => Err(IntegrationError::Auth("Invalid credentials".to_string())),
+            status => Err(IntegrationError::ApiError(format!(
+                "Status {}: {}",
+                status, response.body
+            ))),
+        }
+    }
+}
+
+/// Whether `id` can name a Jira issue: a key like `PROJ-123` or a numeric id
+pub(super) fn is_issue_key(id: &str) -> bool {
+    let is_number = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    match id.rsplit_once('-') {
+        Some((project, number)) => {
+            is_number(number)
+                && project.starts_with(|c: char| c.is_ascii_alphabetic())
+                && project.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        None => is_number(id),
+    }
+}
+
+#[async_trait]
+impl TicketRepository for JiraClient {
+    async fn find_by_id(&self, id: &str) -> Result<Ticket, IntegrationError> {
+        // Issue refs of other trackers, like `#42`, cannot exist here
+        if !is_issue_key(id) {
+            return Err(IntegrationError::NotFound(format!("Issue {} not found", id)));
+        }
+        let url = self.config.api_url(&format!("issue/{}", urlencoding::encode(id)));
+        let mapping = self.field_mapping().await;
+
+        let response = self.get(&url).await?;
+
+        match response.status {
+            200 => {
+                let issue: JiraIssue = response.json()?;
+                Ok(self.map_issue(&issue, &mapping))
             }
             401 => Err(IntegrationError::Auth("Invalid credentials".to_string())),
             404 => Err(IntegrationError::NotFound(format!("Issue {} not found", id))),
-            429 => Err(IntegrationError::RateLimit),
-            status => {
-                let body = response.text().await.unwrap_or_default();
-                Err(IntegrationError::ApiError(format!(
-                    "Status {}: {}",
-                    status, body
-                )))
+            status => Err(IntegrationError::ApiError(format!(
+                "Status {}: {}",
+                status, response.body
+            ))),
+        }
+    }
+
+    async fn get_comments(&self, key: &str, limit: usize) -> Result<Vec<Comment>, IntegrationError> {
+        let url = self.issue_url(key, &format!("comment?orderBy=-created&maxResults={}", limit.min(MAX_PAGE_SIZE)))?;
+
+        let response = self.get(&url).await?;
+
+        match response.status {
+            200 => {
+                let result: JiraCommentList = response.json()?;
+                let mut comments: Vec<Comment> = result.comments.iter().map(|c| self.map_comment(c)).collect();
+                // Sorting is ignored by some Jira versions
+                comments.sort_by_key(|c| std::cmp::Reverse(c.created_at));
+                comments.truncate(limit);
+                Ok(comments)
             }
+            401 => Err(IntegrationError::Auth("Invalid credentials".to_string())),
+            404 => Err(IntegrationError::NotFound(format!("Issue {} not found", key))),
+            status => Err(IntegrationError::ApiError(format!(
+                "Status {}: {}",
+                status, response.body
+            ))),
         }
     }
 
     async fn search(&self, query: &TicketSearchQuery) -> Result<Vec<Ticket>, IntegrationError> {
-        let url = format!("{}/rest/api/3/search", self.config.base_url);
-        let jql = self.build_jql(query);
-
-        let body = serde_json::json!({
-            "jql": jql,
-            "maxResults": query.limit,
-            "fields": [
-                "summary", "description", "status", "assignee", "reporter",
-                "priority", "labels", "updated", "created"
-            ]
-        });
+        let mut tickets = Vec::new();
+        let mut page_token: Option<String> = None;
 
-        let response = self
-            .http_client
-            .post(&url)
-            .header("Authorization", self.auth_header())
-            .header("Content-Type", "application/json")
-            .json(&body)
-            .send()
-            .await?;
+        while tickets.len() < query.limit {
+            let page_query = TicketSearchQuery {
+                limit: (query.limit - tickets.len()).min(MAX_PAGE_SIZE),
+                ..query.clone()
+            };
+            let page = self.search_page(&page_query, page_token.as_deref()).await?;
+            if page.items.is_empty() {
+                break;
+            }
+
+            tickets.extend(page.items);
+            page_token = page.next_page_token;
+            if page_token.is_none() {
+                break;
+            }
+        }
 
-        match response.status().as_u16() {
+        tickets.truncate(query.limit);
+        Ok(tickets)
+    }
+
+    async fn search_page(
+        &self,
+        query: &TicketSearchQuery,
+        page_token: Option<&str>,
+    ) -> Result<Page<Ticket>, IntegrationError> {
+        let mapping = self.field_mapping().await;
+        let body = self.search_body(query, page_token, &mapping);
+
+        let result = self.post_search(&body).await?;
+        Ok(self.map_search_result(result, &mapping))
+    }
+}
+
+#[async_trait]
+impl TicketCommands for JiraClient {
+    async fn get_transitions(&self, key: &str) -> Result<Vec<Transition>, IntegrationError> {
+        let url = self.issue_url(key, "transitions")?;
+
+        let (status, body) = self.send_uncached(self.http_client.get(&url)).await?;
+
+        match status {
             200 => {
-                let result: JiraSearchResult = response
-                    .json()
-                    .await
-                    .map_err(|e| IntegrationError::ParseError(e.to_string()))?;
-                Ok(result.issues.iter().map(|i| self.map_issue(i)).collect())
+                let result: JiraTransitionList =
+                    serde_json::from_str(&body).map_err(|e| IntegrationError::ParseError(e.to_string()))?;
+                Ok(result.transitions.iter().map(|t| self.map_transition(t)).collect())
             }
-            401 => Err(IntegrationError::Auth("Invalid credentials".to_string())),
-            429 => Err(IntegrationError::RateLimit),
-            status => {
-                let body = response.text().await.unwrap_or_default();
-                Err(IntegrationError::ApiError(format!(
-                    "Status {}: {}",
-                    status, body
-                )))
+            status => Err(Self::issue_error(status, &body, key)),
+        }
+    }
+
+    async fn transition(&self, key: &str, transition_id: &str) -> Result<(), IntegrationError> {
+        let url = self.issue_url(key, "transitions")?;
+        let payload = serde_json::json!({ "transition": { "id": transition_id } });
+
+        let (status, body) = self.send_uncached(self.http_client.post(&url).json(&payload)).await?;
+
+        match status {
+            200 | 204 => Ok(()),
+            // Unknown transition, or one the workflow does not allow from the current status
+            400 => Err(IntegrationError::InvalidTransition(format!(
+                "{} cannot take transition {}: {}",
+                key,
+                transition_id,
+                Self::error_message(&body)
+            ))),
+            status => Err(Self::issue_error(status, &body, key)),
+        }
+    }
+
+    async fn assign(&self, key: &str, assignee: &Assignee) -> Result<(), IntegrationError> {
+        let url = self.issue_url(key, "assignee")?;
+        let user_id = match assignee {
+            Assignee::Me => Some(self.current_user_id().await?),
+            Assignee::User(id) => Some(id.clone()),
+            Assignee::Unassigned => None,
+        };
+        let payload = self.assignee_body(user_id.as_deref());
+
+        let (status, body) = self.send_uncached(self.http_client.put(&url).json(&payload)).await?;
+
+        match status {
+            200 | 204 => Ok(()),
+            status => Err(Self::issue_error(status, &body, key)),
+        }
+    }
+
+    async fn add_comment(&self, key: &str, text: &str) -> Result<Comment, IntegrationError> {
+        let url = self.issue_url(key, "comment")?;
+        let payload = self.comment_body(text);
+
+        let (status, body) = self.send_uncached(self.http_client.post(&url).json(&payload)).await?;
+
+        match status {
+            200 | 201 => {
+                let comment: JiraComment =
+                    serde_json::from_str(&body).map_err(|e| IntegrationError::ParseError(e.to_string()))?;
+                Ok(self.map_comment(&comment))
             }
+            status => Err(Self::issue_error(status, &body, key)),
         }
     }
 }
@@ -263,31 +828,86 @@ impl TicketRepository for JiraClient {
 // ===== Jira API Response Types =====
 
 #[derive(Debug, Deserialize)]
-struct JiraSearchResult {
-    issues: Vec<JiraIssue>,
-    #[allow(dead_code)]
-    total: i32,
+#[serde(rename_all = "camelCase")]
+pub(super) struct JiraSearchResult {
+    pub(super) issues: Vec<JiraIssue>,
+    next_page_token: Option<String>,
+    #[serde(default)]
+    is_last: bool,
+    /// Offset pagination of REST v2
+    start_at: Option<usize>,
+    total: Option<usize>,
 }
 
 #[derive(Debug, Deserialize)]
-struct JiraIssue {
+pub(super) struct JiraIssue {
     id: String,
-    key: String,
+    pub(super) key: String,
     fields: JiraFields,
+    /// Present when requested with `expand=changelog`
+    #[serde(default)]
+    pub(super) changelog: Option<JiraChangelog>,
 }
 
 #[derive(Debug, Deserialize)]
 struct JiraFields {
     summary: String,
-    description: Option<String>,
+    /// ADF document on REST v3
+    description: Option<serde_json::Value>,
     status: JiraStatus,
     assignee: Option<JiraUser>,
     reporter: Option<JiraUser>,
     priority: Option<JiraPriority>,
-    sprint: Option<JiraSprint>,
     labels: Option<Vec<String>>,
+    parent: Option<JiraParent>,
     updated: Option<DateTime<Utc>>,
     created: Option<DateTime<Utc>>,
+    /// Custom fields, read through the field mapping
+    #[serde(flatten)]
+    custom: HashMap<String, serde_json::Value>,
+}
+
+impl JiraIssue {
+    /// Value of a custom field, `None` when unset
+    pub(super) fn custom_field(&self, id: &str) -> Option<&serde_json::Value> {
+        self.fields.custom.get(id).filter(|value| !value.is_null())
+    }
+}
+
+/// Issue history; embedded changelogs hold at most the latest 100 entries
+#[derive(Debug, Default, Deserialize)]
+pub(super) struct JiraChangelog {
+    #[serde(default)]
+    pub(super) histories: Vec<JiraHistory>,
+    /// Number of entries in the full history
+    pub(super) total: Option<usize>,
+}
+
+impl JiraChangelog {
+    /// Whether entries were cut off, so the full history has to be fetched separately
+    pub(super) fn is_truncated(&self) -> bool {
+        self.total.is_some_and(|total| total > self.histories.len())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct JiraHistory {
+    pub(super) author: Option<JiraUser>,
+    pub(super) created: DateTime<Utc>,
+    #[serde(default)]
+    pub(super) items: Vec<JiraHistoryItem>,
+}
+
+/// One field change; `from` and `to` are ids, the `*String` variants display values
+#[derive(Debug, Deserialize)]
+pub(super) struct JiraHistoryItem {
+    pub(super) field: String,
+    pub(super) from: Option<String>,
+    pub(super) to: Option<String>,
+    #[serde(rename = "fromString")]
+    pub(super) from_display: Option<String>,
+    #[serde(rename = "toString")]
+    pub(super) to_display: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -302,10 +922,22 @@ struct JiraStatusCategory {
     key: String,
 }
 
+/// Entry of `/rest/api/3/status`
 #[derive(Debug, Deserialize)]
-struct JiraUser {
+struct JiraStatusDefinition {
+    id: String,
+    #[serde(rename = "statusCategory")]
+    status_category: JiraStatusCategory,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct JiraUser {
+    /// Cloud user id
     #[serde(rename = "accountId")]
-    account_id: String,
+    account_id: Option<String>,
+    /// Data Center username and user key
+    name: Option<String>,
+    key: Option<String>,
     #[serde(rename = "displayName")]
     display_name: String,
     #[serde(rename = "emailAddress")]
@@ -326,10 +958,58 @@ struct JiraPriority {
 }
 
 #[derive(Debug, Deserialize)]
-struct JiraSprint {
+struct JiraParent {
+    key: String,
+    fields: Option<JiraParentFields>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraParentFields {
+    issuetype: JiraIssueType,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraIssueType {
     name: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct JiraCommentList {
+    comments: Vec<JiraComment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraComment {
+    id: String,
+    author: Option<JiraUser>,
+    /// ADF document on REST v3
+    body: Option<serde_json::Value>,
+    created: DateTime<Utc>,
+    updated: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraTransitionList {
+    transitions: Vec<JiraTransition>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraTransition {
+    id: String,
+    name: String,
+    to: JiraStatus,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JiraErrorResponse {
+    #[serde(default)]
+    error_messages: Vec<String>,
+    /// Field errors, keyed by field id
+    #[serde(default)]
+    errors: HashMap<String, String>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -406,6 +1086,216 @@ mod tests {
         assert_eq!(jql, "ORDER BY updated DESC");
     }
 
+    #[test]
+    fn test_build_jql_escapes_user_input() {
+        let client = JiraClient::new(test_config()).unwrap();
+
+        let query = TicketSearchQuery::new()
+            .with_text(r#"login "SSO" \ fails"#)
+            .with_assignee(r#"o"brien"#);
+
+        assert_eq!(
+            client.build_jql(&query),
+            r#"project = TEST AND text ~ "login \"SSO\" \\ fails" AND assignee = "o\"brien" ORDER BY updated DESC"#
+        );
+    }
+
+    #[test]
+    fn test_build_jql_golden() {
+        let client = JiraClient::new(test_config()).unwrap();
+        let since = Utc::now() - chrono::Duration::hours(2) + chrono::Duration::seconds(30);
+
+        let cases: Vec<(TicketSearchQuery, &str)> = vec![
+            (
+                TicketSearchQuery::new()
+                    .with_label("backend")
+                    .with_label("needs review")
+                    .with_priority(Priority::Highest)
+                    .with_priority(Priority::High),
+                r#"project = TEST AND labels in ("backend", "needs review") AND priority in ("Highest", "High") ORDER BY updated DESC"#,
+            ),
+            (
+                TicketSearchQuery::new()
+                    .with_sprint(SprintFilter::Open)
+                    .with_issue_type("Bug")
+                    .with_status_category(StatusCategory::InProgress),
+                r#"project = TEST AND statusCategory = "In Progress" AND issuetype = "Bug" AND sprint in openSprints() ORDER BY updated DESC"#,
+            ),
+            (
+                TicketSearchQuery::new().with_sprint(SprintFilter::Closed),
+                r#"project = TEST AND sprint in closedSprints() ORDER BY updated DESC"#,
+            ),
+            (
+                TicketSearchQuery::new().with_sprint(SprintFilter::Specific("42".to_string())),
+                r#"project = TEST AND sprint = 42 ORDER BY updated DESC"#,
+            ),
+            (
+                TicketSearchQuery::new().with_sprint(SprintFilter::Specific("Sprint 7".to_string())),
+                r#"project = TEST AND sprint = "Sprint 7" ORDER BY updated DESC"#,
+            ),
+            (
+                TicketSearchQuery::new()
+                    .with_project("OPS")
+                    .with_reporter("currentUser()")
+                    .with_status("Done")
+                    .updated_since(since),
+                r#"project = OPS AND reporter = currentUser() AND status = "Done" AND updated >= "-120m" ORDER BY updated DESC"#,
+            ),
+            (
+                TicketSearchQuery::new()
+                    .with_status_category(StatusCategory::Todo)
+                    .with_any_of(vec![
+                        TicketSearchQuery::new().with_assignee("currentUser()"),
+                        TicketSearchQuery::new()
+                            .with_label("on-call")
+                            .with_priority(Priority::Highest),
+                    ]),
+                r#"project = TEST AND statusCategory = "To Do" AND (assignee = currentUser() OR (labels = "on-call" AND priority = "Highest")) ORDER BY updated DESC"#,
+            ),
+        ];
+
+        for (query, expected) in cases {
+            assert_eq!(client.build_jql(&query), expected);
+        }
+    }
+
+    #[test]
+    fn test_search_body_paging() {
+        let client = JiraClient::new(test_config()).unwrap();
+
+        let mapping = JiraFieldMapping::default().with_sprint("customfield_10020");
+
+        let first = client.search_body(&TicketSearchQuery::new().with_limit(500), None, &mapping);
+        assert_eq!(first["maxResults"], 100);
+        assert!(first.get("nextPageToken").is_none());
+
+        assert!(first["fields"].as_array().unwrap().contains(&serde_json::json!("customfield_10020")));
+
+        let next = client.search_body(&TicketSearchQuery::new(), Some("token-2"), &mapping);
+        assert_eq!(next["maxResults"], 10);
+        assert_eq!(next["nextPageToken"], "token-2");
+    }
+
+    #[test]
+    fn test_map_search_result_pages() {
+        let client = JiraClient::new(test_config()).unwrap();
+        let issue = serde_json::json!({
+            "id": "1",
+            "key": "TEST-1",
+            "fields": {
+                "summary": "First",
+                "status": { "name": "Open", "statusCategory": { "key": "new" } }
+            }
+        });
+
+        let result: JiraSearchResult = serde_json::from_value(serde_json::json!({
+            "issues": [issue],
+            "nextPageToken": "abc"
+        }))
+        .unwrap();
+        let page = client.map_search_result(result, &JiraFieldMapping::default());
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.next_page_token.as_deref(), Some("abc"));
+
+        let last: JiraSearchResult = serde_json::from_value(serde_json::json!({
+            "issues": [],
+            "nextPageToken": "stale",
+            "isLast": true
+        }))
+        .unwrap();
+        assert!(!client.map_search_result(last, &JiraFieldMapping::default()).has_more());
+    }
+
+    #[test]
+    fn test_map_issue_custom_fields() {
+        let client = JiraClient::new(test_config()).unwrap();
+        let mapping = JiraFieldMapping::default()
+            .with_sprint("customfield_10020")
+            .with_story_points("customfield_10016")
+            .with_epic("customfield_10014")
+            .with_team("customfield_10001");
+        let issue: JiraIssue = serde_json::from_value(serde_json::json!({
+            "id": "10002",
+            "key": "TEST-2",
+            "fields": {
+                "summary": "Checkout redesign",
+                "status": { "name": "In Progress", "statusCategory": { "key": "indeterminate" } },
+                "customfield_10020": [
+                    { "id": 7, "name": "Sprint 7", "state": "closed" },
+                    { "id": 8, "name": "Sprint 8", "state": "active" }
+                ],
+                "customfield_10016": 5.0,
+                "customfield_10014": "TEST-1",
+                "customfield_10001": { "id": "team-1", "name": "Payments" }
+            }
+        }))
+        .unwrap();
+
+        let ticket = client.map_issue(&issue, &mapping);
+
+        assert_eq!(ticket.sprint.as_deref(), Some("Sprint 8"));
+        assert_eq!(ticket.story_points, Some(5.0));
+        assert_eq!(ticket.epic.as_deref(), Some("TEST-1"));
+        assert_eq!(ticket.team.as_deref(), Some("Payments"));
+
+        // Without a mapping the custom fields are ignored
+        let unmapped = client.map_issue(&issue, &JiraFieldMapping::default());
+        assert!(unmapped.sprint.is_none());
+        assert!(unmapped.story_points.is_none());
+    }
+
+    #[test]
+    fn test_map_issue_parent_epic() {
+        let client = JiraClient::new(test_config()).unwrap();
+        let issue: JiraIssue = serde_json::from_value(serde_json::json!({
+            "id": "10003",
+            "key": "TEST-3",
+            "fields": {
+                "summary": "Subtask of epic",
+                "status": { "name": "Open", "statusCategory": { "key": "new" } },
+                "parent": { "key": "TEST-1", "fields": { "issuetype": { "name": "Epic" } } }
+            }
+        }))
+        .unwrap();
+
+        let ticket = client.map_issue(&issue, &JiraFieldMapping::default());
+
+        assert_eq!(ticket.epic.as_deref(), Some("TEST-1"));
+    }
+
+    #[test]
+    fn test_map_comment() {
+        let client = JiraClient::new(test_config()).unwrap();
+        let list: JiraCommentList = serde_json::from_value(serde_json::json!({
+            "comments": [{
+                "id": "100",
+                "author": { "accountId": "abc", "displayName": "Jane Doe" },
+                "body": {
+                    "type": "doc",
+                    "version": 1,
+                    "content": [{
+                        "type": "paragraph",
+                        "content": [
+                            { "type": "mention", "attrs": { "id": "def", "text": "@John" } },
+                            { "type": "text", "text": " shipped in " },
+                            { "type": "text", "text": "v2", "marks": [{ "type": "code" }] }
+                        ]
+                    }]
+                },
+                "created": "2024-01-01T10:00:00.000+0000",
+                "updated": "2024-01-02T10:00:00.000+0000"
+            }]
+        }))
+        .unwrap();
+
+        let comment = client.map_comment(&list.comments[0]);
+
+        assert_eq!(comment.author.unwrap().name, "Jane Doe");
+        assert_eq!(comment.body, "@John shipped in v2");
+        assert_eq!(comment.body_markdown, "@John shipped in `v2`");
+        assert!(comment.updated_at > comment.created_at);
+    }
+
     #[test]
     fn test_map_priority() {
         let config = test_config();
@@ -419,6 +1309,36 @@ mod tests {
         assert_eq!(client.map_priority("Unknown"), Priority::Medium);
     }
 
+    #[test]
+    fn test_map_issue_with_adf_description() {
+        let client = JiraClient::new(test_config()).unwrap();
+        let issue: JiraIssue = serde_json::from_value(serde_json::json!({
+            "id": "10001",
+            "key": "TEST-1",
+            "fields": {
+                "summary": "Login fails",
+                "description": {
+                    "type": "doc",
+                    "version": 1,
+                    "content": [{
+                        "type": "paragraph",
+                        "content": [
+                            { "type": "text", "text": "Steps for " },
+                            { "type": "text", "text": "SSO", "marks": [{ "type": "strong" }] }
+                        ]
+                    }]
+                },
+                "status": { "name": "Open", "statusCategory": { "key": "new" } }
+            }
+        }))
+        .unwrap();
+
+        let ticket = client.map_issue(&issue, &JiraFieldMapping::default());
+
+        assert_eq!(ticket.description.as_deref(), Some("Steps for SSO"));
+        assert_eq!(ticket.description_markdown.as_deref(), Some("Steps for **SSO**"));
+    }
+
     #[test]
     fn test_auth_header_format() {
         let config = test_config();
@@ -427,4 +1347,182 @@ mod tests {
         let header = client.auth_header();
         assert!(header.starts_with("Basic "));
     }
+
+    fn data_center_client() -> JiraClient {
+        let config = JiraConfig::new("https://jira.company.internal", "jdoe")
+            .with_token("pat-token")
+            .with_deployment(JiraDeployment::DataCenter);
+        JiraClient::new(config).unwrap()
+    }
+
+    #[test]
+    fn test_data_center_endpoints_and_auth() {
+        let client = data_center_client();
+
+        assert_eq!(client.auth_header(), "Bearer pat-token");
+        assert_eq!(client.search_url(), "https://jira.company.internal/rest/api/2/search");
+        assert_eq!(
+            client.config.api_url("issue/OPS-1"),
+            "https://jira.company.internal/rest/api/2/issue/OPS-1"
+        );
+        assert_eq!(
+            JiraClient::new(test_config()).unwrap().search_url(),
+            "https://test.atlassian.net/rest/api/3/search/jql"
+        );
+    }
+
+    #[test]
+    fn test_data_center_offset_pagination() {
+        let client = data_center_client();
+        let mapping = JiraFieldMapping::default();
+
+        let body = client.search_body(&TicketSearchQuery::new().with_limit(50), Some("50"), &mapping);
+        assert_eq!(body["startAt"], 50);
+        assert!(body.get("nextPageToken").is_none());
+
+        let issue = serde_json::json!({
+            "id": "1",
+            "key": "OPS-1",
+            "fields": { "summary": "A", "status": { "name": "Open", "statusCategory": { "key": "new" } } }
+        });
+        let page: JiraSearchResult = serde_json::from_value(serde_json::json!({
+            "startAt": 0, "maxResults": 1, "total": 3, "issues": [issue.clone()]
+        }))
+        .unwrap();
+        assert_eq!(client.map_search_result(page, &mapping).next_page_token.as_deref(), Some("1"));
+
+        let last: JiraSearchResult = serde_json::from_value(serde_json::json!({
+            "startAt": 2, "maxResults": 1, "total": 3, "issues": [issue]
+        }))
+        .unwrap();
+        assert!(!client.map_search_result(last, &mapping).has_more());
+    }
+
+    #[test]
+    fn test_data_center_issue_mapping() {
+        let client = data_center_client();
+        let issue: JiraIssue = serde_json::from_value(serde_json::json!({
+            "id": "20001",
+            "key": "OPS-7",
+            "fields": {
+                "summary": "Disk full",
+                "description": "h3. Impact\n*All* nodes, see [runbook|https://wiki.internal/disk]",
+                "status": { "name": "Open", "statusCategory": { "key": "new" } },
+                "assignee": { "name": "jdoe", "key": "JIRAUSER10100", "displayName": "John Doe" }
+            }
+        }))
+        .unwrap();
+
+        let ticket = client.map_issue(&issue, &JiraFieldMapping::default());
+
+        assert_eq!(ticket.assignee.unwrap().id, "jdoe");
+        assert_eq!(
+            ticket.description.as_deref(),
+            Some("Impact\nAll nodes, see runbook (https://wiki.internal/disk)")
+        );
+        assert_eq!(
+            ticket.description_markdown.as_deref(),
+            Some("### Impact\n**All** nodes, see [runbook](https://wiki.internal/disk)")
+        );
+    }
+
+    #[test]
+    fn test_deployment_config_serialization() {
+        let config: JiraConfig = serde_json::from_str(
+            r#"{"base_url": "https://jira.internal", "username": "jdoe", "default_project": null, "deployment": "datacenter"}"#,
+        )
+        .unwrap();
+        assert_eq!(config.deployment, JiraDeployment::DataCenter);
+
+        let legacy: JiraConfig = serde_json::from_str(
+            r#"{"base_url": "https://x.atlassian.net", "username": "a@b.c", "default_project": null}"#,
+        )
+        .unwrap();
+        assert_eq!(legacy.deployment, JiraDeployment::Cloud);
+    }
+
+    #[test]
+    fn test_map_transition() {
+        let client = JiraClient::new(test_config()).unwrap();
+        let list: JiraTransitionList = serde_json::from_value(serde_json::json!({
+            "transitions": [{
+                "id": "31",
+                "name": "Start review",
+                "to": { "name": "In Review", "statusCategory": { "key": "indeterminate" } }
+            }]
+        }))
+        .unwrap();
+
+        let transition = client.map_transition(&list.transitions[0]);
+
+        assert_eq!(transition.id, "31");
+        assert_eq!(transition.to.name, "In Review");
+        assert_eq!(transition.to.category, StatusCategory::InProgress);
+    }
+
+    #[test]
+    fn test_write_payloads_per_deployment() {
+        let cloud = JiraClient::new(test_config()).unwrap();
+        assert_eq!(cloud.assignee_body(Some("5b10a2844c20165700ede21g"))["accountId"], "5b10a2844c20165700ede21g");
+        assert!(cloud.assignee_body(None)["accountId"].is_null());
+        assert_eq!(cloud.comment_body("Deployed")["body"]["type"], "doc");
+
+        let dc = data_center_client();
+        assert_eq!(dc.assignee_body(Some("jdoe"))["name"], "jdoe");
+        assert_eq!(dc.comment_body("Deployed")["body"], "Deployed");
+    }
+
+    #[test]
+    fn test_is_issue_key() {
+        assert!(is_issue_key("PROJ-123"));
+        assert!(is_issue_key("AB_2-7"));
+        assert!(is_issue_key("10042"));
+        assert!(!is_issue_key("#42"));
+        assert!(!is_issue_key("acme/api#42"));
+        assert!(!is_issue_key("PROJ-"));
+        assert!(!is_issue_key("-12"));
+    }
+
+    #[tokio::test]
+    async fn test_find_by_id_rejects_issue_refs() {
+        let client = JiraClient::new(test_config()).unwrap();
+        // Rejected before any request is sent, so search falls back to text
+        assert!(matches!(client.find_by_id("#42").await, Err(IntegrationError::NotFound(_))));
+        assert!(matches!(client.find_by_id("acme/api#42").await, Err(IntegrationError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_ticket_commands_reject_issue_refs() {
+        let client = JiraClient::new(test_config()).unwrap();
+        let not_found = |result: Result<(), IntegrationError>| matches!(result, Err(IntegrationError::NotFound(_)));
+
+        assert!(not_found(client.get_transitions("#42").await.map(|_| ())));
+        assert!(not_found(client.transition("acme/api#42", "31").await));
+        assert!(not_found(client.assign("#42", &Assignee::Unassigned).await));
+        assert!(not_found(client.add_comment("../PROJ-1", "Done").await.map(|_| ())));
+
+        assert!(matches!(client.get_comments("#42", 5).await, Err(IntegrationError::NotFound(_))));
+        assert_eq!(
+            client.issue_url("PROJ-1", "comment").unwrap(),
+            test_config().api_url("issue/PROJ-1/comment")
+        );
+    }
+
+    #[test]
+    fn test_issue_error_mapping() {
+        let forbidden = r#"{"errorMessages": ["You do not have permission to assign issues"], "errors": {}}"#;
+        assert!(matches!(
+            JiraClient::issue_error(403, forbidden, "PROJ-1"),
+            IntegrationError::PermissionDenied(msg) if msg == "PROJ-1: You do not have permission to assign issues"
+        ));
+        assert!(matches!(JiraClient::issue_error(404, "", "PROJ-1"), IntegrationError::NotFound(_)));
+        assert!(matches!(JiraClient::issue_error(401, "", "PROJ-1"), IntegrationError::Auth(_)));
+
+        let invalid_user = r#"{"errorMessages": [], "errors": {"assignee": "User 'bob' cannot be assigned issues."}}"#;
+        assert!(matches!(
+            JiraClient::issue_error(400, invalid_user, "PROJ-1"),
+            IntegrationError::ApiError(msg) if msg.ends_with("User 'bob' cannot be assigned issues.")
+        ));
+        assert_eq!(JiraClient::error_message("<html>Bad gateway</html>"), "<html>Bad gateway</html>");
+    }
 }
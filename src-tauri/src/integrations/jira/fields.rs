@@ -0,0 +1,297 @@
+//! Jira Custom Fields
+//!
+//! Sprint, story points, epic and team live in custom fields whose ids differ
+//! per site (`customfield_10020`, ...). The mapping is discovered from
+//! `/rest/api/3/field`, and any id set in the config takes precedence.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Custom field ids of the values the cockpit reads
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JiraFieldMapping {
+    pub sprint: Option<String>,
+    pub story_points: Option<String>,
+    pub epic: Option<String>,
+    pub team: Option<String>,
+}
+
+impl JiraFieldMapping {
+    pub fn with_sprint(mut self, field: &str) -> Self {
+        self.sprint = Some(field.to_string());
+        self
+    }
+
+    pub fn with_story_points(mut self, field: &str) -> Self {
+        self.story_points = Some(field.to_string());
+        self
+    }
+
+    pub fn with_epic(mut self, field: &str) -> Self {
+        self.epic = Some(field.to_string());
+        self
+    }
+
+    pub fn with_team(mut self, field: &str) -> Self {
+        self.team = Some(field.to_string());
+        self
+    }
+
+    /// Whether every field is mapped, so discovery can be skipped
+    pub fn is_complete(&self) -> bool {
+        self.sprint.is_some() && self.story_points.is_some() && self.epic.is_some() && self.team.is_some()
+    }
+
+    /// Fill the fields not set here from `discovered`
+    pub fn or(self, discovered: JiraFieldMapping) -> Self {
+        Self {
+            sprint: self.sprint.or(discovered.sprint),
+            story_points: self.story_points.or(discovered.story_points),
+            epic: self.epic.or(discovered.epic),
+            team: self.team.or(discovered.team),
+        }
+    }
+
+    /// Ids of all mapped fields, to request them in searches
+    pub fn field_ids(&self) -> Vec<&str> {
+        [&self.sprint, &self.story_points, &self.epic, &self.team]
+            .into_iter()
+            .filter_map(|f| f.as_deref())
+            .collect()
+    }
+
+    /// Find the fields by their custom type, falling back to well-known names
+    pub fn discover(fields: &[JiraFieldDefinition]) -> Self {
+        let by_type = |custom: &[&str]| {
+            fields
+                .iter()
+                .find(|f| f.custom_type().is_some_and(|t| custom.contains(&t)))
+                .map(|f| f.id.clone())
+        };
+        let by_name = |names: &[&str]| {
+            fields
+                .iter()
+                .find(|f| names.iter().any(|n| f.name.eq_ignore_ascii_case(n)))
+                .map(|f| f.id.clone())
+        };
+
+        Self {
+            sprint: by_type(&["com.pyxis.greenhopper.jira:gh-sprint"]).or_else(|| by_name(&["Sprint"])),
+            story_points: by_name(&["Story Points", "Story point estimate"]),
+            epic: by_type(&["com.pyxis.greenhopper.jira:gh-epic-link"]).or_else(|| by_name(&["Epic Link"])),
+            team: by_type(&["com.atlassian.jira.plugin.system.customfieldtypes:atlassian-team"])
+                .or_else(|| by_name(&["Team"])),
+        }
+    }
+}
+
+/// Entry of `/rest/api/3/field`
+#[derive(Debug, Clone, Deserialize)]
+pub struct JiraFieldDefinition {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub schema: Option<JiraFieldSchema>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct JiraFieldSchema {
+    pub custom: Option<String>,
+}
+
+impl JiraFieldDefinition {
+    fn custom_type(&self) -> Option<&str> {
+        self.schema.as_ref().and_then(|s| s.custom.as_deref())
+    }
+}
+
+/// Name and lowercase state of a sprint in a sprint field
+#[derive(Debug, PartialEq)]
+struct SprintRef {
+    name: String,
+    state: String,
+}
+
+/// Sprints of a sprint field, as objects (Cloud) or `toString()` strings (Data Center)
+fn sprint_refs(value: &Value) -> Vec<SprintRef> {
+    match value {
+        Value::Array(sprints) => sprints.iter().filter_map(sprint_ref).collect(),
+        _ => sprint_ref(value).into_iter().collect(),
+    }
+}
+
+fn sprint_ref(value: &Value) -> Option<SprintRef> {
+    match value {
+        Value::Object(_) => Some(SprintRef {
+            name: value.get("name")?.as_str()?.to_string(),
+            state: value.get("state").and_then(Value::as_str).unwrap_or_default().to_lowercase(),
+        }),
+        Value::String(s) => parse_server_sprint(s),
+        _ => None,
+    }
+}
+
+/// Parse `com.atlassian.greenhopper.service.sprint.Sprint@1a2b[id=5,state=ACTIVE,name=Sprint 5,...]`.
+///
+/// Values are not escaped, so a comma only starts a new field when a `key=` follows it.
+fn parse_server_sprint(s: &str) -> Option<SprintRef> {
+    let body = s.split_once('[')?.1.strip_suffix(']')?;
+
+    let mut fields: Vec<(&str, String)> = Vec::new();
+    for part in body.split(',') {
+        match part.split_once('=') {
+            Some((key, value)) if !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric()) => {
+                fields.push((key, value.to_string()));
+            }
+            _ => {
+                let (_, value) = fields.last_mut()?;
+                value.push(',');
+                value.push_str(part);
+            }
+        }
+    }
+
+    let field = |key: &str| fields.iter().find(|(k, _)| *k == key).map(|(_, v)| v.clone());
+    Some(SprintRef {
+        name: field("name")?,
+        state: field("state").unwrap_or_default().to_lowercase(),
+    })
+}
+
+/// Name of the current sprint: the active one, otherwise the latest
+pub fn sprint_name(value: &Value) -> Option<String> {
+    let mut sprints = sprint_refs(value);
+    let index = sprints
+        .iter()
+        .position(|s| s.state == "active")
+        .or_else(|| sprints.len().checked_sub(1))?;
+    Some(sprints.swap_remove(index).name)
+}
+
+/// Name of the latest closed sprint, i.e. the one an unfinished ticket was carried over from
+pub fn previous_sprint_name(value: &Value) -> Option<String> {
+    sprint_refs(value)
+        .into_iter()
+        .rev()
+        .find(|s| s.state == "closed")
+        .map(|s| s.name)
+}
+
+pub fn story_points(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Epic key from an epic link field (a key) or a `parent` issue
+pub fn epic_key(value: &Value) -> Option<String> {
+    match value {
+        Value::String(key) => Some(key.clone()),
+        Value::Object(_) => value.get("key").and_then(Value::as_str).map(str::to_string),
+        _ => None,
+    }
+}
+
+/// Team name from an Atlassian team (`name`) or an Advanced Roadmaps team (`title`)
+pub fn team_name(value: &Value) -> Option<String> {
+    match value {
+        Value::String(name) => Some(name.clone()),
+        Value::Object(_) => ["name", "title", "value"]
+            .iter()
+            .find_map(|key| value.get(*key).and_then(Value::as_str))
+            .map(str::to_string),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn definitions() -> Vec<JiraFieldDefinition> {
+        serde_json::from_value(json!([
+            { "id": "summary", "name": "Summary", "schema": { "type": "string" } },
+            { "id": "customfield_10020", "name": "Sprint",
+              "schema": { "type": "array", "custom": "com.pyxis.greenhopper.jira:gh-sprint" } },
+            { "id": "customfield_10016", "name": "Story point estimate",
+              "schema": { "type": "number", "custom": "com.atlassian.jira.plugin.system.customfieldtypes:jsw-story-points" } },
+            { "id": "customfield_10014", "name": "Epic Link",
+              "schema": { "type": "any", "custom": "com.pyxis.greenhopper.jira:gh-epic-link" } },
+            { "id": "customfield_10001", "name": "Team",
+              "schema": { "type": "team", "custom": "com.atlassian.jira.plugin.system.customfieldtypes:atlassian-team" } },
+            { "id": "customfield_99999", "name": "Notes" }
+        ]))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_discover() {
+        let mapping = JiraFieldMapping::discover(&definitions());
+
+        assert_eq!(mapping.sprint.as_deref(), Some("customfield_10020"));
+        assert_eq!(mapping.story_points.as_deref(), Some("customfield_10016"));
+        assert_eq!(mapping.epic.as_deref(), Some("customfield_10014"));
+        assert_eq!(mapping.team.as_deref(), Some("customfield_10001"));
+        assert!(mapping.is_complete());
+    }
+
+    #[test]
+    fn test_overrides_take_precedence() {
+        let overrides = JiraFieldMapping::default().with_story_points("customfield_10002");
+
+        let mapping = overrides.or(JiraFieldMapping::discover(&definitions()));
+
+        assert_eq!(mapping.story_points.as_deref(), Some("customfield_10002"));
+        assert_eq!(mapping.sprint.as_deref(), Some("customfield_10020"));
+        assert_eq!(mapping.field_ids().len(), 4);
+    }
+
+    #[test]
+    fn test_sprint_name_prefers_active() {
+        let value = json!([
+            { "id": 1, "name": "Sprint 1", "state": "closed" },
+            { "id": 2, "name": "Sprint 2", "state": "active" },
+            { "id": 3, "name": "Sprint 3", "state": "future" }
+        ]);
+        assert_eq!(sprint_name(&value).as_deref(), Some("Sprint 2"));
+
+        let closed = json!([{ "name": "Sprint 1", "state": "closed" }, { "name": "Sprint 3", "state": "future" }]);
+        assert_eq!(sprint_name(&closed).as_deref(), Some("Sprint 3"));
+        assert_eq!(sprint_name(&json!([])), None);
+
+        assert_eq!(previous_sprint_name(&value).as_deref(), Some("Sprint 1"));
+        assert_eq!(previous_sprint_name(&json!([{ "name": "Sprint 2", "state": "active" }])), None);
+    }
+
+    #[test]
+    fn test_sprint_name_from_data_center_strings() {
+        // Sprint field as returned by Jira Data Center 8.x `/rest/api/2/issue`
+        let value = json!([
+            "com.atlassian.greenhopper.service.sprint.Sprint@6a1f3c9e[id=41,rapidViewId=7,state=CLOSED,name=Payments 2024.05,startDate=2024-02-26T09:00:00.000+07:00,endDate=2024-03-08T17:00:00.000+07:00,completeDate=2024-03-08T17:12:44.303+07:00,activatedDate=2024-02-26T09:03:11.911+07:00,sequence=41,goal=Refunds, chargebacks,synced=false,autoStartStop=false]",
+            "com.atlassian.greenhopper.service.sprint.Sprint@2b7d91aa[id=42,rapidViewId=7,state=ACTIVE,name=Payments 2024.06,startDate=2024-03-11T09:00:00.000+07:00,endDate=2024-03-22T17:00:00.000+07:00,completeDate=<null>,activatedDate=2024-03-11T09:01:02.117+07:00,sequence=42,goal=,synced=false,autoStartStop=false]"
+        ]);
+
+        assert_eq!(sprint_name(&value).as_deref(), Some("Payments 2024.06"));
+        assert_eq!(previous_sprint_name(&value).as_deref(), Some("Payments 2024.05"));
+
+        let closed = parse_server_sprint(value[0].as_str().unwrap());
+        assert_eq!(closed.map(|s| s.state).as_deref(), Some("closed"));
+        assert_eq!(parse_server_sprint("not a sprint"), None);
+    }
+
+    #[test]
+    fn test_value_extractors() {
+        assert_eq!(story_points(&json!(5.0)), Some(5.0));
+        assert_eq!(story_points(&json!("3")), Some(3.0));
+        assert_eq!(story_points(&Value::Null), None);
+
+        assert_eq!(epic_key(&json!("PROJ-5")).as_deref(), Some("PROJ-5"));
+        assert_eq!(epic_key(&json!({ "id": "1", "key": "PROJ-6" })).as_deref(), Some("PROJ-6"));
+
+        assert_eq!(team_name(&json!({ "id": "abc", "name": "Payments" })).as_deref(), Some("Payments"));
+        assert_eq!(team_name(&json!({ "id": 1, "title": "Core" })).as_deref(), Some("Core"));
+    }
+}
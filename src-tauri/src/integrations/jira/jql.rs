@@ -0,0 +1,312 @@
+//! JQL Builder
+//!
+//! Builds JQL from typed clauses. Every string value is quoted and escaped, so
+//! user input from the Flight Console can never change the structure of a query.
+
+use chrono::{DateTime, Utc};
+use std::fmt;
+
+/// Fields used in JQL clauses
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Project,
+    Text,
+    Assignee,
+    Reporter,
+    Status,
+    StatusCategory,
+    Labels,
+    Priority,
+    IssueType,
+    Sprint,
+    Updated,
+    Created,
+}
+
+impl Field {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Field::Project => "project",
+            Field::Text => "text",
+            Field::Assignee => "assignee",
+            Field::Reporter => "reporter",
+            Field::Status => "status",
+            Field::StatusCategory => "statusCategory",
+            Field::Labels => "labels",
+            Field::Priority => "priority",
+            Field::IssueType => "issuetype",
+            Field::Sprint => "sprint",
+            Field::Updated => "updated",
+            Field::Created => "created",
+        }
+    }
+}
+
+/// A value on the right-hand side of a clause
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    /// Any user supplied text, always quoted
+    String(String),
+    /// Numeric id, e.g. of a sprint
+    Number(u64),
+    /// Project or issue key, unquoted when it is a plain identifier
+    Key(String),
+    /// JQL function such as `openSprints()`
+    Function(&'static str),
+    /// Instant, written as an offset from now (`"-90m"`). Jira reads absolute
+    /// dates in the searching user's timezone, which the cockpit does not know,
+    /// while an offset means the same in every timezone.
+    Date(DateTime<Utc>),
+}
+
+impl Value {
+    pub fn string(value: &str) -> Self {
+        Value::String(value.to_string())
+    }
+
+    /// A user reference, where `currentUser()` refers to the authenticated user
+    pub fn user(value: &str) -> Self {
+        if value.eq_ignore_ascii_case("currentUser()") {
+            Value::Function("currentUser()")
+        } else {
+            Value::string(value)
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::String(s) => f.write_str(&quote(s)),
+            Value::Number(n) => write!(f, "{}", n),
+            Value::Key(key) if is_plain_key(key) => f.write_str(key),
+            Value::Key(key) => f.write_str(&quote(key)),
+            Value::Function(function) => f.write_str(function),
+            Value::Date(date) => write!(f, "\"{}m\"", minutes_from_now(*date)),
+        }
+    }
+}
+
+/// Whole minutes from now to `date`, rounded down so a `>=` clause still includes `date`
+fn minutes_from_now(date: DateTime<Utc>) -> i64 {
+    (date - Utc::now()).num_seconds().div_euclid(60)
+}
+
+/// Quote a string for JQL, escaping quotes and backslashes
+pub fn quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' | '\\' => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            // Line breaks end a JQL string, and other control characters are rejected
+            c if c.is_control() => quoted.push(' '),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// JQL words that would be parsed as keywords instead of a key
+const RESERVED_WORDS: &[&str] = &["and", "or", "not", "in", "is", "was", "empty", "null", "order", "by"];
+
+fn is_plain_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        && !RESERVED_WORDS.contains(&key.to_ascii_lowercase().as_str())
+}
+
+/// Sort direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Order {
+    Asc,
+    Desc,
+}
+
+/// A JQL query: clauses joined with `AND`, with an optional ordering
+#[derive(Debug, Clone, Default)]
+pub struct Jql {
+    clauses: Vec<String>,
+    order_by: Option<(Field, Order)>,
+}
+
+impl Jql {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `field = value`
+    pub fn eq(self, field: Field, value: Value) -> Self {
+        self.clause(format!("{} = {}", field.as_str(), value))
+    }
+
+    /// `field ~ "text"`
+    pub fn contains(self, field: Field, text: &str) -> Self {
+        self.clause(format!("{} ~ {}", field.as_str(), quote(text)))
+    }
+
+    /// `field in (values)`, or `field = value` for a single value; skipped when empty
+    pub fn one_of(self, field: Field, values: Vec<Value>) -> Self {
+        match values.len() {
+            0 => self,
+            1 => self.eq(field, values.into_iter().next().unwrap()),
+            _ => {
+                let list = values.iter().map(Value::to_string).collect::<Vec<_>>().join(", ");
+                self.clause(format!("{} in ({})", field.as_str(), list))
+            }
+        }
+    }
+
+    /// `field >= value`
+    pub fn at_least(self, field: Field, value: Value) -> Self {
+        self.clause(format!("{} >= {}", field.as_str(), value))
+    }
+
+    /// `field in function()`, for list functions such as `openSprints()`
+    pub fn in_function(self, field: Field, function: &'static str) -> Self {
+        self.clause(format!("{} in {}", field.as_str(), function))
+    }
+
+    /// `(a OR b OR ...)` of the given sub-queries; empty ones are ignored
+    pub fn any_of(self, alternatives: Vec<Jql>) -> Self {
+        let parts: Vec<String> = alternatives
+            .iter()
+            .filter(|jql| !jql.clauses.is_empty())
+            .map(|jql| {
+                if jql.clauses.len() == 1 {
+                    jql.where_clause()
+                } else {
+                    format!("({})", jql.where_clause())
+                }
+            })
+            .collect();
+
+        match parts.len() {
+            0 => self,
+            1 => self.clause(parts.into_iter().next().unwrap()),
+            _ => self.clause(format!("({})", parts.join(" OR "))),
+        }
+    }
+
+    pub fn order_by(mut self, field: Field, order: Order) -> Self {
+        self.order_by = Some((field, order));
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.clauses.is_empty()
+    }
+
+    fn clause(mut self, clause: String) -> Self {
+        self.clauses.push(clause);
+        self
+    }
+
+    fn where_clause(&self) -> String {
+        self.clauses.join(" AND ")
+    }
+}
+
+impl fmt::Display for Jql {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.where_clause())?;
+
+        if let Some((field, order)) = self.order_by {
+            if !self.clauses.is_empty() {
+                f.write_str(" ")?;
+            }
+            let direction = match order {
+                Order::Asc => "ASC",
+                Order::Desc => "DESC",
+            };
+            write!(f, "ORDER BY {} {}", field.as_str(), direction)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote_escapes_quotes_and_backslashes() {
+        assert_eq!(quote("plain"), "\"plain\"");
+        assert_eq!(quote(r#"say "hi""#), r#""say \"hi\"""#);
+        assert_eq!(quote(r"C:\temp"), r#""C:\\temp""#);
+        assert_eq!(quote("two\nlines"), "\"two lines\"");
+    }
+
+    #[test]
+    fn test_injection_stays_inside_string() {
+        let jql = Jql::new()
+            .contains(Field::Text, r#"x" OR project = SECRET OR text ~ "y"#)
+            .to_string();
+
+        assert_eq!(jql, r#"text ~ "x\" OR project = SECRET OR text ~ \"y""#);
+    }
+
+    #[test]
+    fn test_keys_are_quoted_unless_plain() {
+        assert_eq!(Value::Key("PROJ".to_string()).to_string(), "PROJ");
+        assert_eq!(Value::Key("ORDER".to_string()).to_string(), "\"ORDER\"");
+        assert_eq!(Value::Key("My Project".to_string()).to_string(), "\"My Project\"");
+    }
+
+    #[test]
+    fn test_values() {
+        assert_eq!(Value::user("currentUser()").to_string(), "currentUser()");
+        assert_eq!(Value::user("jane").to_string(), "\"jane\"");
+        assert_eq!(Value::Number(42).to_string(), "42");
+        // Half a minute of slack for the time the test takes
+        let since = Utc::now() - chrono::Duration::minutes(90) + chrono::Duration::seconds(30);
+        assert_eq!(Value::Date(since).to_string(), "\"-90m\"");
+        assert_eq!(Value::Date(Utc::now() - chrono::Duration::days(400)).to_string(), "\"-576000m\"");
+    }
+
+    #[test]
+    fn test_one_of() {
+        let jql = Jql::new()
+            .one_of(Field::Labels, vec![])
+            .one_of(Field::IssueType, vec![Value::string("Bug")])
+            .one_of(Field::Priority, vec![Value::string("High"), Value::string("Highest")]);
+
+        assert_eq!(
+            jql.to_string(),
+            r#"issuetype = "Bug" AND priority in ("High", "Highest")"#
+        );
+    }
+
+    #[test]
+    fn test_any_of_groups() {
+        let jql = Jql::new()
+            .eq(Field::Project, Value::Key("PROJ".to_string()))
+            .any_of(vec![
+                Jql::new().eq(Field::Assignee, Value::user("currentUser()")),
+                Jql::new()
+                    .eq(Field::Reporter, Value::user("currentUser()"))
+                    .eq(Field::Status, Value::string("Open")),
+                Jql::new(),
+            ])
+            .order_by(Field::Updated, Order::Desc);
+
+        assert_eq!(
+            jql.to_string(),
+            r#"project = PROJ AND (assignee = currentUser() OR (reporter = currentUser() AND status = "Open")) ORDER BY updated DESC"#
+        );
+    }
+
+    #[test]
+    fn test_empty_query_only_orders() {
+        let jql = Jql::new().any_of(vec![Jql::new()]).order_by(Field::Updated, Order::Desc);
+
+        assert!(jql.is_empty());
+        assert_eq!(jql.to_string(), "ORDER BY updated DESC");
+    }
+}
@@ -1,8 +1,17 @@
 //! Jira Integration
 //!
-//! Provides Jira API client implementing the TicketRepository trait.
+//! Provides Jira API client implementing the TicketRepository,
+//! TicketHistoryRepository and SprintRepository traits.
 
+pub mod adf;
+mod agile;
+mod changelog;
 mod client;
+pub mod fields;
+pub mod jql;
+pub mod wiki;
 
 pub use client::JiraClient;
 pub use client::JiraConfig;
+pub use client::JiraDeployment;
+pub use fields::JiraFieldMapping;
@@ -0,0 +1,281 @@
+//! Jira Wiki Markup
+//!
+//! Jira Server / Data Center returns descriptions and comments as wiki markup
+//! over REST v2. This renders the common constructs (headings, lists, code,
+//! quotes, tables, links, mentions) as plain text or Markdown, mirroring the
+//! ADF renderer used for Jira Cloud.
+
+use regex::{Captures, Regex};
+use std::sync::OnceLock;
+
+/// Render wiki markup as plain text
+pub fn to_plain_text(text: &str) -> String {
+    render(text, false)
+}
+
+/// Render wiki markup as Markdown
+pub fn to_markdown(text: &str) -> String {
+    render(text, true)
+}
+
+/// Plain text and Markdown of a wiki field, `None` when it is empty
+pub fn render_field(text: Option<&str>) -> Option<(String, String)> {
+    let text = text?;
+    if text.trim().is_empty() {
+        return None;
+    }
+    Some((to_plain_text(text), to_markdown(text)))
+}
+
+fn regex(cell: &'static OnceLock<Regex>, pattern: &str) -> &'static Regex {
+    cell.get_or_init(|| Regex::new(pattern).unwrap())
+}
+
+fn heading_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    regex(&RE, r"^h([1-6])\.\s+(.*)$")
+}
+
+fn list_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    regex(&RE, r"^([*#-]+)\s+(.*)$")
+}
+
+fn block_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    regex(&RE, r"^\{(code|noformat|quote|panel)(?::([^}]*))?\}\s*$")
+}
+
+fn render(text: &str, markdown: bool) -> String {
+    let mut out: Vec<String> = Vec::new();
+    // Kind of the `{...}` block the line is in
+    let mut block: Option<String> = None;
+
+    for line in text.replace("\r\n", "\n").lines() {
+        let trimmed = line.trim();
+
+        // Inside {code} / {noformat} everything is literal until the closing tag
+        if let Some(ref open) = block {
+            if open == "code" || open == "noformat" {
+                if trimmed == format!("{{{}}}", open) {
+                    if markdown {
+                        out.push("```".to_string());
+                    }
+                    block = None;
+                } else {
+                    out.push(line.to_string());
+                }
+                continue;
+            }
+        }
+
+        if let Some(caps) = block_re().captures(trimmed) {
+            let kind = caps[1].to_string();
+            let is_closing = block.as_deref() == Some(kind.as_str()) && caps.get(2).is_none();
+            if is_closing {
+                block = None;
+                continue;
+            }
+
+            let params = caps.get(2).map(|m| m.as_str().to_string());
+            match kind.as_str() {
+                "code" | "noformat" => {
+                    // `{code:java}` or `{code:title=Foo.java|language=java}`
+                    let language = params.as_deref().map(|p| {
+                        p.split('|')
+                            .find_map(|part| part.strip_prefix("language="))
+                            .unwrap_or(if p.contains('=') { "" } else { p })
+                            .to_string()
+                    });
+                    if markdown {
+                        out.push(format!("```{}", language.unwrap_or_default()));
+                    }
+                    block = Some(kind);
+                }
+                "panel" => {
+                    let title = params
+                        .as_deref()
+                        .and_then(|p| p.split('|').find_map(|part| part.strip_prefix("title=")))
+                        .map(str::to_string);
+                    if let Some(title) = title {
+                        out.push(if markdown { format!("**{}**", title) } else { title });
+                    }
+                    block = Some(kind);
+                }
+                _ => block = Some(kind),
+            }
+            continue;
+        }
+
+        let quoted = block.as_deref() == Some("quote");
+        let mut rendered = render_line(trimmed, markdown);
+        if markdown && quoted {
+            rendered = format!("> {}", rendered).trim_end().to_string();
+        }
+        out.push(rendered);
+    }
+
+    // Close a code fence left open by unbalanced markup
+    if markdown && matches!(block.as_deref(), Some("code" | "noformat")) {
+        out.push("```".to_string());
+    }
+
+    out.join("\n").trim().to_string()
+}
+
+fn render_line(line: &str, markdown: bool) -> String {
+    if line == "----" {
+        return if markdown { "---".to_string() } else { String::new() };
+    }
+
+    if let Some(caps) = heading_re().captures(line) {
+        let text = render_inline(&caps[2], markdown);
+        return if markdown {
+            format!("{} {}", "#".repeat(caps[1].parse().unwrap_or(1)), text)
+        } else {
+            text
+        };
+    }
+
+    if let Some(text) = line.strip_prefix("bq. ") {
+        let text = render_inline(text, markdown);
+        return if markdown { format!("> {}", text) } else { text };
+    }
+
+    if let Some(caps) = list_re().captures(line) {
+        let markers = &caps[1];
+        // A lone `-` only starts a list in its single form
+        if !(markers.len() > 1 && markers.contains('-')) {
+            let indent = "  ".repeat(markers.len() - 1);
+            let marker = if markers.ends_with('#') { "1." } else { "-" };
+            return format!("{}{} {}", indent, marker, render_inline(&caps[2], markdown));
+        }
+    }
+
+    if line.starts_with('|') {
+        return render_table_row(line, markdown);
+    }
+
+    render_inline(line, markdown)
+}
+
+fn render_table_row(line: &str, markdown: bool) -> String {
+    let header = line.starts_with("||");
+    let separator = if header { "||" } else { "|" };
+    let cells: Vec<String> = line
+        .trim_matches('|')
+        .split(separator)
+        .map(|cell| render_inline(cell.trim(), markdown))
+        .collect();
+
+    if !markdown {
+        return cells.join(" | ");
+    }
+
+    let row = format!("| {} |", cells.join(" | "));
+    if header {
+        format!("{}\n|{}", row, " --- |".repeat(cells.len()))
+    } else {
+        row
+    }
+}
+
+fn render_inline(text: &str, markdown: bool) -> String {
+    static LINK: OnceLock<Regex> = OnceLock::new();
+    static MENTION: OnceLock<Regex> = OnceLock::new();
+    static MONOSPACE: OnceLock<Regex> = OnceLock::new();
+    static STRONG: OnceLock<Regex> = OnceLock::new();
+    static EMPHASIS: OnceLock<Regex> = OnceLock::new();
+    static STRIKE: OnceLock<Regex> = OnceLock::new();
+
+    let text = text.replace("\\\\", "\n");
+
+    let text = regex(&MENTION, r"\[~([^\]]+)\]").replace_all(&text, "@$1");
+    let text = regex(&LINK, r"\[(?:([^|\]]*)\|)?((?:https?://|mailto:|/)[^\]]+)\]").replace_all(&text, |caps: &Captures| {
+        let url = &caps[2];
+        match caps.get(1).map(|m| m.as_str()).filter(|label| !label.is_empty()) {
+            Some(label) if markdown => format!("[{}]({})", label, url),
+            Some(label) => format!("{} ({})", label, url),
+            None if markdown => format!("<{}>", url),
+            None => url.to_string(),
+        }
+    });
+
+    let (monospace, strong, emphasis, strike) = if markdown {
+        ("`$1`", "$1**$2**", "$1*$2*", "$1~~$2~~")
+    } else {
+        ("$1", "$1$2", "$1$2", "$1$2")
+    };
+
+    let text = regex(&MONOSPACE, r"\{\{(.+?)\}\}").replace_all(&text, monospace);
+    let text = regex(&STRONG, r"(^|[\s(])\*(\S(?:.*?\S)?)\*").replace_all(&text, strong);
+    let text = regex(&EMPHASIS, r"(^|[\s(])_(\S(?:.*?\S)?)_").replace_all(&text, emphasis);
+    let text = regex(&STRIKE, r"(^|[\s(])-(\S(?:.*?\S)?)-").replace_all(&text, strike);
+
+    text.into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inline_markup() {
+        let wiki = "Fix *login* in {{auth.rs}}, see [docs|https://docs.example.com] cc [~jdoe]";
+
+        assert_eq!(
+            to_markdown(wiki),
+            "Fix **login** in `auth.rs`, see [docs](https://docs.example.com) cc @jdoe"
+        );
+        assert_eq!(
+            to_plain_text(wiki),
+            "Fix login in auth.rs, see docs (https://docs.example.com) cc @jdoe"
+        );
+    }
+
+    #[test]
+    fn test_hyphens_and_underscores_in_words_are_kept() {
+        assert_eq!(to_markdown("re-run snake_case_name on 2024-01-02"), "re-run snake_case_name on 2024-01-02");
+        assert_eq!(to_markdown("_really_ -gone-"), "*really* ~~gone~~");
+        assert_eq!(to_markdown("[WIP] draft"), "[WIP] draft");
+    }
+
+    #[test]
+    fn test_headings_and_lists() {
+        let wiki = "h2. Steps\n# Open the app\n## Click login\n* Expect an error";
+
+        assert_eq!(
+            to_markdown(wiki),
+            "## Steps\n1. Open the app\n  1. Click login\n- Expect an error"
+        );
+    }
+
+    #[test]
+    fn test_code_blocks_are_literal() {
+        let wiki = "Trace:\n{code:java}\nint *a* = 1;\n{code}\n{noformat}\nraw\n{noformat}";
+
+        assert_eq!(to_markdown(wiki), "Trace:\n```java\nint *a* = 1;\n```\n```\nraw\n```");
+        assert_eq!(to_plain_text(wiki), "Trace:\nint *a* = 1;\nraw");
+    }
+
+    #[test]
+    fn test_quotes_panels_and_tables() {
+        let wiki = "{quote}\nQuoted text\n{quote}\n{panel:title=Note}\nInside\n{panel}\n||Env||Status||\n|prod|down|";
+
+        assert_eq!(
+            to_markdown(wiki),
+            "> Quoted text\n**Note**\nInside\n| Env | Status |\n| --- | --- |\n| prod | down |"
+        );
+        assert_eq!(to_plain_text(wiki), "Quoted text\nNote\nInside\nEnv | Status\nprod | down");
+    }
+
+    #[test]
+    fn test_render_field() {
+        assert_eq!(render_field(None), None);
+        assert_eq!(render_field(Some("  ")), None);
+        assert_eq!(
+            render_field(Some("*done*")),
+            Some(("done".to_string(), "**done**".to_string()))
+        );
+    }
+}
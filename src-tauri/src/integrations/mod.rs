@@ -4,14 +4,22 @@
 //! for Jira, Git hosting, Documentation, Monitoring, and AI services.
 
 pub mod traits;
+pub mod rate_limit;
+pub mod http_cache;
 pub mod jira;
 pub mod git;
 pub mod ai;
 pub mod monitoring;
 
 // Re-export common types
-pub use traits::{TicketRepository, PullRequestRepository, MetricsRepository};
+pub use traits::{
+    TicketRepository, TicketHistoryRepository, SprintRepository, PullRequestRepository, MetricsRepository,
+};
+pub use rate_limit::{RateLimitBudget, RateLimitTracker};
+pub use http_cache::HttpCache;
 pub use jira::{JiraClient, JiraConfig};
-pub use git::{GitProvider, GitConfig, GitProviderType};
+pub use git::{
+    CompositePullRequestRepository, GitHubApi, GitProvider, GitConfig, GitProviderType, RepoDiscovery,
+};
 pub use ai::{GeminiClient, SpecAnalysis};
 pub use monitoring::{GrafanaClient, MonitoringConfig as GrafanaConfig};
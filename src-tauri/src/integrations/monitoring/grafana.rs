@@ -7,6 +7,8 @@ use chrono::{DateTime, Utc};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
+use crate::integrations::http_cache::{send_get, HttpCache};
+use crate::integrations::rate_limit::RateLimitTracker;
 use crate::integrations::traits::{
     Incident, IncidentStatus, IntegrationError, Metric, MetricsRepository, Severity,
 };
@@ -79,6 +81,8 @@ impl MonitoringConfig {
 pub struct GrafanaClient {
     config: MonitoringConfig,
     http_client: Client,
+    rate_limiter: RateLimitTracker,
+    http_cache: Option<HttpCache>,
 }
 
 impl GrafanaClient {
@@ -94,7 +98,30 @@ impl GrafanaClient {
             .build()
             .map_err(|e| IntegrationError::Network(e.to_string()))?;
 
-        Ok(Self { config, http_client })
+        Ok(Self {
+            config,
+            http_client,
+            rate_limiter: RateLimitTracker::new("grafana"),
+            http_cache: None,
+        })
+    }
+
+    /// Share a rate limit tracker, e.g. with the background poller
+    pub fn with_rate_limiter(mut self, tracker: RateLimitTracker) -> Self {
+        self.rate_limiter = tracker;
+        self
+    }
+
+    pub fn rate_limiter(&self) -> &RateLimitTracker {
+        &self.rate_limiter
+    }
+
+    /// Send alert polls conditionally and reuse cached payloads on 304.
+    ///
+    /// Metric queries are always sent as-is, their results change with every evaluation.
+    pub fn with_http_cache(mut self, cache: HttpCache) -> Self {
+        self.http_cache = Some(cache);
+        self
     }
 
     fn auth_header(&self) -> String {
@@ -134,6 +161,7 @@ impl MetricsRepository for GrafanaClient {
         let mut metrics = Vec::new();
 
         for (query, name, unit) in queries {
+            self.rate_limiter.check()?;
             let response = self.http_client
                 .get(&url)
                 .header("Authorization", self.auth_header())
@@ -141,7 +169,12 @@ impl MetricsRepository for GrafanaClient {
                 .send()
                 .await?;
 
-            if response.status().as_u16() == 200 {
+            let status = response.status().as_u16();
+            if let Some(err) = self.rate_limiter.observe(status, response.headers()) {
+                return Err(err);
+            }
+
+            if status == 200 {
                 let result: PrometheusResponse = response.json().await
                     .map_err(|e| IntegrationError::ParseError(e.to_string()))?;
 
@@ -167,16 +200,19 @@ impl MetricsRepository for GrafanaClient {
         // Query Grafana Alerting API
         let url = format!("{}/api/alertmanager/grafana/api/v2/alerts", self.config.base_url);
 
-        let response = self.http_client
+        self.rate_limiter.check()?;
+        let request = self.http_client
             .get(&url)
-            .header("Authorization", self.auth_header())
-            .send()
-            .await?;
+            .header("Authorization", self.auth_header());
+        let response = send_get(self.http_cache.as_ref(), &url, request).await?;
 
-        match response.status().as_u16() {
+        if let Some(err) = self.rate_limiter.observe(response.status, &response.headers) {
+            return Err(err);
+        }
+
+        match response.status {
             200 => {
-                let alerts: Vec<GrafanaAlert> = response.json().await
-                    .map_err(|e| IntegrationError::ParseError(e.to_string()))?;
+                let alerts: Vec<GrafanaAlert> = response.json()?;
 
                 let incidents: Vec<Incident> = alerts
                     .into_iter()
@@ -208,11 +244,7 @@ impl MetricsRepository for GrafanaClient {
                 Ok(incidents)
             }
             401 => Err(IntegrationError::Auth("Invalid API key".to_string())),
-            429 => Err(IntegrationError::RateLimit),
-            status => {
-                let body = response.text().await.unwrap_or_default();
-                Err(IntegrationError::ApiError(format!("Status {}: {}", status, body)))
-            }
+            status => Err(IntegrationError::ApiError(format!("Status {}: {}", status, response.body))),
         }
     }
 }
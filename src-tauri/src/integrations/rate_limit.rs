@@ -0,0 +1,424 @@
+//! Rate limit tracking
+//!
+//! Records the request budget each integration reports in its response headers,
+//! so clients can stop before an API locks them out and the poller can wait
+//! for the reset instead of burning through retries. GitHub meters REST,
+//! GraphQL and search separately, so budgets are kept per resource.
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+use super::traits::IntegrationError;
+
+/// Resource of APIs with a single budget, and of GitHub's REST API
+pub const CORE_RESOURCE: &str = "core";
+/// GitHub's GraphQL API
+pub const GRAPHQL_RESOURCE: &str = "graphql";
+/// GitHub's search API, with a much smaller per-minute budget
+pub const SEARCH_RESOURCE: &str = "search";
+
+/// Request budget of an integration, as last reported by its API
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimitBudget {
+    pub integration: String,
+    /// Budget within the integration, `CORE_RESOURCE` unless the API meters several
+    pub resource: String,
+    /// Requests allowed per window
+    pub limit: Option<u64>,
+    /// Requests left in the current window
+    pub remaining: Option<u64>,
+    /// When the current window resets
+    pub reset_at: Option<DateTime<Utc>>,
+    /// Explicit back-off requested through `Retry-After`
+    pub retry_after: Option<DateTime<Utc>>,
+    /// When the budget was last updated from a response
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl RateLimitBudget {
+    pub fn new(integration: &str) -> Self {
+        Self::for_resource(integration, CORE_RESOURCE)
+    }
+
+    pub fn for_resource(integration: &str, resource: &str) -> Self {
+        Self {
+            integration: integration.to_string(),
+            resource: resource.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Time until which no requests should be sent, if the budget is used up
+    pub fn paused_until(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.exhausted_until(0, now)
+    }
+
+    /// Like `paused_until`, but already pauses once `reserve` requests or fewer are left
+    pub fn exhausted_until(&self, reserve: u64, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        if let Some(retry_after) = self.retry_after.filter(|t| *t > now) {
+            return Some(retry_after);
+        }
+
+        match (self.remaining, self.reset_at) {
+            (Some(remaining), Some(reset_at)) if remaining <= reserve && reset_at > now => Some(reset_at),
+            _ => None,
+        }
+    }
+
+    /// Update the budget from response headers.
+    ///
+    /// Understands the `X-RateLimit-*` headers of GitHub, Bitbucket and Jira,
+    /// the `RateLimit-*` headers of GitLab, and `Retry-After`.
+    fn update(&mut self, headers: &HeaderMap, now: DateTime<Utc>) {
+        let limit = header_u64(headers, &["x-ratelimit-limit", "ratelimit-limit"]);
+        let remaining = header_u64(headers, &["x-ratelimit-remaining", "ratelimit-remaining"]);
+        let reset_at = header_str(headers, &["x-ratelimit-reset", "ratelimit-reset"])
+            .and_then(|v| parse_reset(v, now));
+        let retry_after = header_str(headers, &["retry-after"]).and_then(|v| parse_retry_after(v, now));
+
+        if limit.is_none() && remaining.is_none() && reset_at.is_none() && retry_after.is_none() {
+            return;
+        }
+
+        if limit.is_some() {
+            self.limit = limit;
+        }
+        if remaining.is_some() {
+            self.remaining = remaining;
+        }
+        if reset_at.is_some() {
+            self.reset_at = reset_at;
+        }
+        self.retry_after = retry_after;
+        self.updated_at = Some(now);
+    }
+}
+
+/// Budgets of one integration, in the order their resources were first seen
+#[derive(Debug)]
+struct TrackerState {
+    integration: String,
+    budgets: Vec<RateLimitBudget>,
+}
+
+impl TrackerState {
+    fn budget_mut(&mut self, resource: &str) -> &mut RateLimitBudget {
+        let index = match self.budgets.iter().position(|b| b.resource == resource) {
+            Some(index) => index,
+            None => {
+                self.budgets.push(RateLimitBudget::for_resource(&self.integration, resource));
+                self.budgets.len() - 1
+            }
+        };
+        &mut self.budgets[index]
+    }
+}
+
+/// Shared rate limit state of one integration.
+///
+/// Cloning is cheap and clones share the same budgets, so a client and the
+/// background poller can hold the same tracker.
+#[derive(Debug, Clone)]
+pub struct RateLimitTracker {
+    state: Arc<Mutex<TrackerState>>,
+}
+
+impl RateLimitTracker {
+    pub fn new(integration: &str) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(TrackerState {
+                integration: integration.to_string(),
+                budgets: vec![RateLimitBudget::new(integration)],
+            })),
+        }
+    }
+
+    /// Name of the integration this tracker belongs to
+    pub fn integration(&self) -> String {
+        self.lock().integration.clone()
+    }
+
+    /// Snapshot of the core budget
+    pub fn budget(&self) -> RateLimitBudget {
+        self.budget_for(CORE_RESOURCE)
+    }
+
+    /// Snapshot of the budget of `resource`
+    pub fn budget_for(&self, resource: &str) -> RateLimitBudget {
+        let state = self.lock();
+        state
+            .budgets
+            .iter()
+            .find(|b| b.resource == resource)
+            .cloned()
+            .unwrap_or_else(|| RateLimitBudget::for_resource(&state.integration, resource))
+    }
+
+    /// Snapshots of all budgets seen so far, the core one first
+    pub fn budgets(&self) -> Vec<RateLimitBudget> {
+        self.lock().budgets.clone()
+    }
+
+    /// Time until which requests to the core budget should be held back, if any
+    pub fn paused_until(&self) -> Option<DateTime<Utc>> {
+        self.budget().paused_until(Utc::now())
+    }
+
+    /// Fail fast instead of sending a request that would be rejected
+    pub fn check(&self) -> Result<(), IntegrationError> {
+        self.check_resource(CORE_RESOURCE)
+    }
+
+    /// Like `check`, for a request charged to the budget of `resource`
+    pub fn check_resource(&self, resource: &str) -> Result<(), IntegrationError> {
+        match self.budget_for(resource).paused_until(Utc::now()) {
+            Some(reset_at) => Err(IntegrationError::RateLimit { reset_at: Some(reset_at) }),
+            None => Ok(()),
+        }
+    }
+
+    /// Record the budget reported by a response to the core budget.
+    ///
+    /// Returns the error to report when the response was rejected because of the
+    /// rate limit: a 429, or a 403 with no requests remaining or with a
+    /// `Retry-After`, as GitHub sends for its primary and secondary limits.
+    pub fn observe(&self, status: u16, headers: &HeaderMap) -> Option<IntegrationError> {
+        self.observe_resource(CORE_RESOURCE, status, headers)
+    }
+
+    /// Like `observe`, for a request charged to `resource`. An `X-RateLimit-Resource`
+    /// header in the response takes precedence.
+    pub fn observe_resource(&self, resource: &str, status: u16, headers: &HeaderMap) -> Option<IntegrationError> {
+        self.observe_at(resource, status, headers, Utc::now())
+    }
+
+    fn observe_at(
+        &self,
+        resource: &str,
+        status: u16,
+        headers: &HeaderMap,
+        now: DateTime<Utc>,
+    ) -> Option<IntegrationError> {
+        let resource = header_str(headers, &["x-ratelimit-resource"]).unwrap_or(resource);
+        let retry_after = header_str(headers, &["retry-after"]).is_some();
+
+        let mut state = self.lock();
+        let budget = state.budget_mut(resource);
+        budget.update(headers, now);
+
+        let limited = status == 429 || (status == 403 && (budget.remaining == Some(0) || retry_after));
+        if !limited {
+            return None;
+        }
+
+        if status == 429 && budget.remaining.is_none() {
+            budget.remaining = Some(0);
+        }
+
+        let reset_at = budget
+            .retry_after
+            .or(budget.reset_at.filter(|t| *t > now));
+        log::warn!(
+            "{} ({}): rate limited{}",
+            budget.integration,
+            budget.resource,
+            reset_at.map(|t| format!(" until {}", t)).unwrap_or_default()
+        );
+
+        Some(IntegrationError::RateLimit { reset_at })
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, TrackerState> {
+        // The budgets stay consistent even if a holder panicked
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, names: &[&str]) -> Option<&'a str> {
+    names
+        .iter()
+        .find_map(|name| headers.get(*name))
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+}
+
+fn header_u64(headers: &HeaderMap, names: &[&str]) -> Option<u64> {
+    header_str(headers, names).and_then(|v| v.parse().ok())
+}
+
+/// Parse a reset header: epoch seconds (GitHub, GitLab), seconds from now, or an ISO timestamp (Jira)
+fn parse_reset(value: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    if let Ok(seconds) = value.parse::<i64>() {
+        // Anything this large cannot be a delay
+        return if seconds > 1_000_000_000 {
+            Utc.timestamp_opt(seconds, 0).single()
+        } else {
+            Some(now + Duration::seconds(seconds))
+        };
+    }
+
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// Parse `Retry-After`: either a delay in seconds or an HTTP date
+fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    if let Ok(seconds) = value.parse::<i64>() {
+        return Some(now + Duration::seconds(seconds));
+    }
+
+    DateTime::parse_from_rfc2822(value)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.insert(*name, HeaderValue::from_str(value).unwrap());
+        }
+        map
+    }
+
+    #[test]
+    fn test_records_github_budget() {
+        let tracker = RateLimitTracker::new("github");
+        let now = Utc::now();
+        let reset = now.timestamp() + 600;
+
+        let error = tracker.observe_at(CORE_RESOURCE, 200, &headers(&[
+            ("x-ratelimit-limit", "5000"),
+            ("x-ratelimit-remaining", "4999"),
+            ("x-ratelimit-reset", &reset.to_string()),
+        ]), now);
+
+        assert!(error.is_none());
+        let budget = tracker.budget();
+        assert_eq!(budget.integration, "github");
+        assert_eq!(budget.limit, Some(5000));
+        assert_eq!(budget.remaining, Some(4999));
+        assert_eq!(budget.reset_at.unwrap().timestamp(), reset);
+        assert!(tracker.paused_until().is_none());
+    }
+
+    #[test]
+    fn test_exhausted_github_budget_pauses_until_reset() {
+        let tracker = RateLimitTracker::new("github");
+        let reset = Utc::now().timestamp() + 600;
+
+        let error = tracker.observe(403, &headers(&[
+            ("x-ratelimit-remaining", "0"),
+            ("x-ratelimit-reset", &reset.to_string()),
+        ]));
+
+        match error {
+            Some(IntegrationError::RateLimit { reset_at }) => assert_eq!(reset_at.unwrap().timestamp(), reset),
+            other => panic!("expected rate limit error, got {:?}", other),
+        }
+        assert_eq!(tracker.paused_until().unwrap().timestamp(), reset);
+        assert!(matches!(tracker.check(), Err(IntegrationError::RateLimit { .. })));
+    }
+
+    #[test]
+    fn test_forbidden_with_budget_left_is_not_rate_limit() {
+        let tracker = RateLimitTracker::new("github");
+        let error = tracker.observe(403, &headers(&[("x-ratelimit-remaining", "12")]));
+        assert!(error.is_none());
+    }
+
+    #[test]
+    fn test_secondary_limit_is_rate_limit() {
+        let tracker = RateLimitTracker::new("github");
+        let error = tracker.observe(403, &headers(&[("x-ratelimit-remaining", "4000"), ("retry-after", "60")]));
+
+        assert!(matches!(error, Some(IntegrationError::RateLimit { reset_at: Some(_) })));
+        assert!(tracker.check().is_err());
+    }
+
+    #[test]
+    fn test_budgets_kept_per_resource() {
+        let tracker = RateLimitTracker::new("github");
+        let reset = (Utc::now().timestamp() + 60).to_string();
+
+        tracker.observe(200, &headers(&[("x-ratelimit-remaining", "4000")]));
+        // A search response reports its own budget, whatever the caller expected
+        tracker.observe(200, &headers(&[
+            ("x-ratelimit-resource", "search"),
+            ("x-ratelimit-remaining", "0"),
+            ("x-ratelimit-reset", &reset),
+        ]));
+        tracker.observe_resource(GRAPHQL_RESOURCE, 200, &headers(&[("x-ratelimit-remaining", "4900")]));
+
+        assert_eq!(tracker.budget().remaining, Some(4000));
+        assert_eq!(tracker.budget_for(SEARCH_RESOURCE).remaining, Some(0));
+        assert_eq!(tracker.budget_for(GRAPHQL_RESOURCE).remaining, Some(4900));
+        assert!(tracker.check().is_ok());
+        assert!(tracker.check_resource(SEARCH_RESOURCE).is_err());
+
+        let resources: Vec<String> = tracker.budgets().into_iter().map(|b| b.resource).collect();
+        assert_eq!(resources, vec!["core", "search", "graphql"]);
+    }
+
+    #[test]
+    fn test_retry_after_seconds() {
+        let tracker = RateLimitTracker::new("jira");
+        let now = Utc::now();
+
+        let error = tracker.observe_at(CORE_RESOURCE, 429, &headers(&[("retry-after", "30")]), now);
+
+        match error {
+            Some(IntegrationError::RateLimit { reset_at }) => {
+                assert_eq!(reset_at, Some(now + Duration::seconds(30)));
+            }
+            other => panic!("expected rate limit error, got {:?}", other),
+        }
+        assert_eq!(tracker.budget().remaining, Some(0));
+    }
+
+    #[test]
+    fn test_parse_reset_formats() {
+        let now = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+
+        assert_eq!(parse_reset("1704067260", now), Some(now + Duration::seconds(60)));
+        assert_eq!(parse_reset("60", now), Some(now + Duration::seconds(60)));
+        assert_eq!(parse_reset("2024-01-01T00:01:00Z", now), Some(now + Duration::seconds(60)));
+        assert_eq!(
+            parse_retry_after("Mon, 01 Jan 2024 00:01:00 GMT", now),
+            Some(now + Duration::seconds(60))
+        );
+        assert_eq!(parse_reset("soon", now), None);
+    }
+
+    #[test]
+    fn test_budget_reserve() {
+        let now = Utc::now();
+        let budget = RateLimitBudget {
+            remaining: Some(5),
+            reset_at: Some(now + Duration::minutes(10)),
+            ..RateLimitBudget::new("github")
+        };
+
+        assert!(budget.paused_until(now).is_none());
+        assert_eq!(budget.exhausted_until(10, now), budget.reset_at);
+        // A past reset no longer holds anything back
+        assert!(budget.exhausted_until(10, now + Duration::minutes(11)).is_none());
+    }
+
+    #[test]
+    fn test_clones_share_budget() {
+        let tracker = RateLimitTracker::new("bitbucket");
+        let shared = tracker.clone();
+
+        tracker.observe(200, &headers(&[("x-ratelimit-remaining", "42")]));
+
+        assert_eq!(shared.budget().remaining, Some(42));
+    }
+}
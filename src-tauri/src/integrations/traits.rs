@@ -9,7 +9,7 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 /// Errors that can occur during integration operations
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone)]
 pub enum IntegrationError {
     #[error("Network error: {0}")]
     Network(String),
@@ -17,8 +17,8 @@ pub enum IntegrationError {
     #[error("Authentication failed: {0}")]
     Auth(String),
 
-    #[error("Rate limit exceeded")]
-    RateLimit,
+    #[error("Rate limit exceeded{}", .reset_at.map(|t| format!(", resets at {}", t.to_rfc3339())).unwrap_or_default())]
+    RateLimit { reset_at: Option<DateTime<Utc>> },
 
     #[error("Resource not found: {0}")]
     NotFound(String),
@@ -31,6 +31,12 @@ pub enum IntegrationError {
 
     #[error("Configuration error: {0}")]
     ConfigError(String),
+
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
+
+    #[error("Invalid transition: {0}")]
+    InvalidTransition(String),
 }
 
 impl From<reqwest::Error> for IntegrationError {
@@ -97,17 +103,151 @@ pub struct Ticket {
     pub id: String,
     pub key: String,
     pub summary: String,
+    /// Description as plain text
     pub description: Option<String>,
+    /// Description as Markdown, for the detail view
+    #[serde(default)]
+    pub description_markdown: Option<String>,
     pub status: TicketStatus,
     pub assignee: Option<User>,
     pub reporter: Option<User>,
     pub priority: Option<Priority>,
     pub sprint: Option<String>,
+    #[serde(default)]
+    pub story_points: Option<f64>,
+    /// Key of the epic the ticket belongs to
+    #[serde(default)]
+    pub epic: Option<String>,
+    #[serde(default)]
+    pub team: Option<String>,
     pub labels: Vec<String>,
     pub updated_at: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
 }
 
+/// Comment on a ticket
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Comment {
+    pub id: String,
+    pub author: Option<User>,
+    /// Body as plain text
+    pub body: String,
+    /// Body as Markdown, for the detail view
+    pub body_markdown: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Workflow transition available on a ticket
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Transition {
+    pub id: String,
+    pub name: String,
+    /// Status the ticket moves to
+    pub to: TicketStatus,
+}
+
+/// A ticket moving from one status to another, from its changelog
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusTransition {
+    pub at: DateTime<Utc>,
+    pub author: Option<User>,
+    pub from: Option<String>,
+    pub to: String,
+    /// Categories are `None` for statuses that no longer exist on the site
+    pub from_category: Option<StatusCategory>,
+    pub to_category: Option<StatusCategory>,
+}
+
+/// Ticket with its status transitions, oldest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TicketHistory {
+    pub ticket: Ticket,
+    pub transitions: Vec<StatusTransition>,
+}
+
+/// Who a ticket is assigned to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Assignee {
+    /// The authenticated user
+    Me,
+    /// A user by id (account id on Jira Cloud, username on Data Center)
+    User(String),
+    Unassigned,
+}
+
+/// Jira Software board
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Board {
+    pub id: u64,
+    pub name: String,
+    /// `scrum`, `kanban` or `simple`
+    pub board_type: String,
+    pub project_key: Option<String>,
+}
+
+impl Board {
+    /// Whether the board plans work in sprints
+    pub fn has_sprints(&self) -> bool {
+        self.board_type == "scrum"
+    }
+}
+
+/// Sprint state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SprintState {
+    Future,
+    Active,
+    Closed,
+}
+
+/// Sprint with its schedule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Sprint {
+    pub id: u64,
+    pub name: String,
+    pub state: SprintState,
+    pub goal: Option<String>,
+    pub start_date: Option<DateTime<Utc>>,
+    pub end_date: Option<DateTime<Utc>>,
+    pub complete_date: Option<DateTime<Utc>>,
+    pub board_id: Option<u64>,
+}
+
+impl Sprint {
+    /// Whole days until the end date, counting a started day as left; 0 once overdue
+    pub fn days_left(&self, now: DateTime<Utc>) -> Option<i64> {
+        let end = self.end_date?;
+        let seconds = (end - now).num_seconds().max(0);
+        Some((seconds + 86_399) / 86_400)
+    }
+}
+
+/// Ticket in a sprint, with when it joined the sprint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SprintIssue {
+    pub ticket: Ticket,
+    /// Last time the ticket was moved into the sprint, `None` if it never was
+    /// (created in the sprint, or the change is older than the loaded history)
+    pub added_at: Option<DateTime<Utc>>,
+    /// Closed sprint the ticket was left unfinished in
+    #[serde(default)]
+    pub carried_over_from: Option<String>,
+    /// When the ticket reached its done status, `None` while it is not done
+    #[serde(default)]
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl SprintIssue {
+    /// Whether the ticket is scope added after the sprint started
+    pub fn is_added_after_start(&self, sprint: &Sprint) -> bool {
+        match (self.added_at, sprint.start_date) {
+            (Some(added), Some(start)) => added > start,
+            _ => false,
+        }
+    }
+}
+
 /// Pull request state
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PrState {
@@ -137,11 +277,112 @@ pub enum ChecksStatus {
     None,
 }
 
+impl ChecksStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChecksStatus::Pass => "Pass",
+            ChecksStatus::Fail => "Fail",
+            ChecksStatus::Running => "Running",
+            ChecksStatus::None => "None",
+        }
+    }
+
+    /// Overall status of several checks: any failure fails, then anything running
+    pub fn combine(statuses: impl IntoIterator<Item = ChecksStatus>) -> ChecksStatus {
+        statuses.into_iter().fold(ChecksStatus::None, |overall, status| {
+            match (overall, status) {
+                (ChecksStatus::Fail, _) | (_, ChecksStatus::Fail) => ChecksStatus::Fail,
+                (ChecksStatus::Running, _) | (_, ChecksStatus::Running) => ChecksStatus::Running,
+                (ChecksStatus::Pass, _) | (_, ChecksStatus::Pass) => ChecksStatus::Pass,
+                _ => ChecksStatus::None,
+            }
+        })
+    }
+}
+
+/// Review decision of a single reviewer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReviewState {
+    /// Review requested but not submitted yet
+    Pending,
+    /// Reviewed without approving or requesting changes
+    Commented,
+    ChangesRequested,
+    Approved,
+}
+
+impl ReviewState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReviewState::Pending => "Pending",
+            ReviewState::Commented => "Commented",
+            ReviewState::ChangesRequested => "ChangesRequested",
+            ReviewState::Approved => "Approved",
+        }
+    }
+
+    /// Whether this state is a verdict that a later comment does not override
+    pub fn is_decisive(&self) -> bool {
+        matches!(self, ReviewState::Approved | ReviewState::ChangesRequested)
+    }
+}
+
 /// Reviewer information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Reviewer {
     pub user: User,
-    pub approved: bool,
+    pub state: ReviewState,
+}
+
+impl Reviewer {
+    pub fn new(user: User, state: ReviewState) -> Self {
+        Self { user, state }
+    }
+
+    pub fn is_approved(&self) -> bool {
+        self.state == ReviewState::Approved
+    }
+
+    /// Match by user ID or name, as reviewers are configured either way
+    pub fn is_user(&self, user_id: &str) -> bool {
+        self.user.id == user_id || self.user.name == user_id
+    }
+}
+
+/// A file changed by a pull request
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangedFile {
+    pub path: String,
+    pub additions: u64,
+    pub deletions: u64,
+}
+
+/// Size of the change a pull request makes
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiffStats {
+    pub additions: u64,
+    pub deletions: u64,
+    pub changed_files: u64,
+    /// Changed files; very large PRs may list fewer than `changed_files`
+    #[serde(default)]
+    pub files: Vec<ChangedFile>,
+}
+
+impl DiffStats {
+    /// Totals of a file list
+    pub fn from_files(files: Vec<ChangedFile>) -> Self {
+        Self {
+            additions: files.iter().map(|f| f.additions).sum(),
+            deletions: files.iter().map(|f| f.deletions).sum(),
+            changed_files: files.len() as u64,
+            files,
+        }
+    }
+
+    /// Lines added plus lines removed
+    pub fn lines_changed(&self) -> u64 {
+        self.additions + self.deletions
+    }
 }
 
 /// Pull request representation
@@ -161,6 +402,216 @@ pub struct PullRequest {
     pub updated_at: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
     pub url: String,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Lines and files changed, when the provider reports them
+    #[serde(default)]
+    pub diff_stats: Option<DiffStats>,
+    /// Git connection the PR was fetched from, when several are combined
+    #[serde(default)]
+    pub connection: Option<String>,
+}
+
+impl PullRequest {
+    /// Whether the user is a reviewer who has not submitted a review yet
+    pub fn is_waiting_on(&self, user_id: &str) -> bool {
+        self.reviewers
+            .iter()
+            .any(|r| r.is_user(user_id) && r.state == ReviewState::Pending)
+    }
+
+    /// Whether any reviewer is blocking the PR with requested changes
+    pub fn has_changes_requested(&self) -> bool {
+        self.reviewers
+            .iter()
+            .any(|r| r.state == ReviewState::ChangesRequested)
+    }
+}
+
+/// A merged or declined pull request with the milestones of its review
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClosedPullRequest {
+    /// The PR, in state `Merged` or `Declined`
+    pub pr: PullRequest,
+    pub closed_at: DateTime<Utc>,
+    pub merged_at: Option<DateTime<Utc>>,
+    pub merged_by: Option<User>,
+    /// First review, approval or comment by someone other than the author
+    pub first_review_at: Option<DateTime<Utc>>,
+}
+
+impl ClosedPullRequest {
+    pub fn is_merged(&self) -> bool {
+        self.pr.state == PrState::Merged
+    }
+
+    /// Time from opening to merge, `None` for declined PRs
+    pub fn lead_time(&self) -> Option<chrono::Duration> {
+        Some(self.merged_at? - self.pr.created_at)
+    }
+
+    /// Time from opening to the first review, `None` if nobody reviewed
+    pub fn time_to_first_review(&self) -> Option<chrono::Duration> {
+        Some(self.first_review_at? - self.pr.created_at)
+    }
+}
+
+/// Closed PRs from the repositories that succeeded, plus the ones that failed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ClosedPrFetchResult {
+    pub prs: Vec<ClosedPullRequest>,
+    pub errors: Vec<RepositoryError>,
+}
+
+/// A comment on a pull request, on the diff or on the conversation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewComment {
+    pub id: String,
+    /// Shared by the comments of one thread
+    pub thread_id: String,
+    pub author: User,
+    pub body: String,
+    /// File the comment is anchored to, `None` for conversation comments
+    pub path: Option<String>,
+    pub line: Option<u32>,
+    /// Whether the comment's thread is resolved, `None` where it cannot be
+    pub resolved: Option<bool>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Who commented on a pull request last, and when
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommentActivity {
+    pub user: User,
+    pub at: DateTime<Utc>,
+}
+
+/// Whose turn it is on a pull request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PrCourt {
+    Author,
+    Reviewers,
+}
+
+/// Latest review comments of a pull request with thread statistics
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReviewComments {
+    /// Latest comments up to the requested limit, oldest first
+    pub comments: Vec<ReviewComment>,
+    /// Comments before the limit was applied
+    pub total: usize,
+    pub unresolved_threads: usize,
+    pub last_activity: Option<CommentActivity>,
+}
+
+impl ReviewComments {
+    /// Summarize all comments of a PR, keeping the latest `limit`
+    pub fn from_comments(mut comments: Vec<ReviewComment>, limit: usize) -> Self {
+        comments.sort_by_key(|c| c.created_at);
+
+        let mut unresolved: Vec<&str> = comments
+            .iter()
+            .filter(|c| c.resolved == Some(false))
+            .map(|c| c.thread_id.as_str())
+            .collect();
+        unresolved.sort_unstable();
+        unresolved.dedup();
+        let unresolved_threads = unresolved.len();
+
+        // Edits do not hand the turn over, so only posting counts
+        let last_activity = comments.last().map(|c| CommentActivity {
+            user: c.author.clone(),
+            at: c.created_at,
+        });
+
+        let total = comments.len();
+        comments.drain(..total.saturating_sub(limit));
+
+        Self {
+            comments,
+            total,
+            unresolved_threads,
+            last_activity,
+        }
+    }
+
+    /// Whose turn it is: the reviewers' when the author commented last, the
+    /// author's when anyone else did. `None` without comments.
+    pub fn court(&self, pr: &PullRequest) -> Option<PrCourt> {
+        let activity = self.last_activity.as_ref()?;
+        let by_author = activity.user.id == pr.author.id || activity.user.name == pr.author.name;
+        Some(if by_author { PrCourt::Reviewers } else { PrCourt::Author })
+    }
+}
+
+/// Category of a per-repository fetch failure
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RepositoryErrorKind {
+    Auth,
+    NotFound,
+    RateLimit,
+    Network,
+    Api,
+}
+
+impl RepositoryErrorKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RepositoryErrorKind::Auth => "Auth",
+            RepositoryErrorKind::NotFound => "NotFound",
+            RepositoryErrorKind::RateLimit => "RateLimit",
+            RepositoryErrorKind::Network => "Network",
+            RepositoryErrorKind::Api => "Api",
+        }
+    }
+}
+
+/// A repository that could not be fetched during a multi-repository query
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepositoryError {
+    pub repository: String,
+    pub kind: RepositoryErrorKind,
+    pub message: String,
+}
+
+impl RepositoryError {
+    pub fn new(repository: &str, error: &IntegrationError) -> Self {
+        let kind = match error {
+            IntegrationError::Auth(_) | IntegrationError::PermissionDenied(_) => RepositoryErrorKind::Auth,
+            IntegrationError::NotFound(_) => RepositoryErrorKind::NotFound,
+            IntegrationError::RateLimit { .. } => RepositoryErrorKind::RateLimit,
+            IntegrationError::Network(_) => RepositoryErrorKind::Network,
+            _ => RepositoryErrorKind::Api,
+        };
+
+        Self {
+            repository: repository.to_string(),
+            kind,
+            message: error.to_string(),
+        }
+    }
+}
+
+/// Open PRs from the repositories that succeeded, plus the ones that failed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PrFetchResult {
+    pub prs: Vec<PullRequest>,
+    pub errors: Vec<RepositoryError>,
+}
+
+impl PrFetchResult {
+    pub fn new(prs: Vec<PullRequest>) -> Self {
+        Self {
+            prs,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Whether some repositories are missing from the result
+    pub fn is_partial(&self) -> bool {
+        !self.errors.is_empty()
+    }
 }
 
 /// Incident severity
@@ -212,13 +663,34 @@ pub struct Incident {
     pub runbook_url: Option<String>,
 }
 
-/// Search query for tickets
+/// Sprint a ticket search is restricted to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SprintFilter {
+    /// Any active sprint
+    Open,
+    /// Any completed sprint
+    Closed,
+    /// A sprint by id or name
+    Specific(String),
+}
+
+/// Search query for tickets.
+///
+/// All set filters must match; `any_of` adds groups of which at least one must match.
 #[derive(Debug, Clone, Default)]
 pub struct TicketSearchQuery {
     pub text: Option<String>,
     pub project: Option<String>,
     pub assignee: Option<String>,
+    pub reporter: Option<String>,
     pub status: Option<String>,
+    pub status_category: Option<StatusCategory>,
+    pub labels: Vec<String>,
+    pub priorities: Vec<Priority>,
+    pub issue_types: Vec<String>,
+    pub sprint: Option<SprintFilter>,
+    pub updated_since: Option<DateTime<Utc>>,
+    pub any_of: Vec<TicketSearchQuery>,
     pub limit: usize,
 }
 
@@ -240,12 +712,86 @@ impl TicketSearchQuery {
         self
     }
 
+    pub fn with_assignee(mut self, assignee: &str) -> Self {
+        self.assignee = Some(assignee.to_string());
+        self
+    }
+
+    pub fn with_reporter(mut self, reporter: &str) -> Self {
+        self.reporter = Some(reporter.to_string());
+        self
+    }
+
+    pub fn with_status(mut self, status: &str) -> Self {
+        self.status = Some(status.to_string());
+        self
+    }
+
+    pub fn with_status_category(mut self, category: StatusCategory) -> Self {
+        self.status_category = Some(category);
+        self
+    }
+
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.labels.push(label.to_string());
+        self
+    }
+
+    pub fn with_priority(mut self, priority: Priority) -> Self {
+        self.priorities.push(priority);
+        self
+    }
+
+    pub fn with_issue_type(mut self, issue_type: &str) -> Self {
+        self.issue_types.push(issue_type.to_string());
+        self
+    }
+
+    pub fn with_sprint(mut self, sprint: SprintFilter) -> Self {
+        self.sprint = Some(sprint);
+        self
+    }
+
+    pub fn updated_since(mut self, since: DateTime<Utc>) -> Self {
+        self.updated_since = Some(since);
+        self
+    }
+
+    /// Add a group of alternatives, at least one of which must match
+    pub fn with_any_of(mut self, alternatives: Vec<TicketSearchQuery>) -> Self {
+        self.any_of.extend(alternatives);
+        self
+    }
+
     pub fn with_limit(mut self, limit: usize) -> Self {
         self.limit = limit;
         self
     }
 }
 
+/// One page of results from a paginated API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Opaque token to request the following page, `None` on the last page
+    pub next_page_token: Option<String>,
+}
+
+impl<T> Page<T> {
+    pub fn new(items: Vec<T>, next_page_token: Option<String>) -> Self {
+        Self { items, next_page_token }
+    }
+
+    /// A page with nothing after it
+    pub fn last(items: Vec<T>) -> Self {
+        Self::new(items, None)
+    }
+
+    pub fn has_more(&self) -> bool {
+        self.next_page_token.is_some()
+    }
+}
+
 /// Filter for pull requests
 #[derive(Debug, Clone, Default)]
 pub struct PrFilter {
@@ -274,6 +820,21 @@ impl PrFilter {
         self.stale_only = true;
         self
     }
+
+    pub fn with_state(mut self, state: PrState) -> Self {
+        self.state = Some(state);
+        self
+    }
+
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Whether PRs closed in `state` are wanted; any closed state when unset
+    pub fn accepts_closed(&self, state: PrState) -> bool {
+        self.state.is_none_or(|wanted| wanted == state)
+    }
 }
 
 /// Repository trait for ticket operations (Jira)
@@ -282,8 +843,71 @@ pub trait TicketRepository: Send + Sync {
     /// Find a ticket by ID/key
     async fn find_by_id(&self, id: &str) -> Result<Ticket, IntegrationError>;
 
-    /// Search tickets
+    /// Search tickets, returning at most `query.limit` of them
     async fn search(&self, query: &TicketSearchQuery) -> Result<Vec<Ticket>, IntegrationError>;
+
+    /// Most recent comments of a ticket, newest first
+    async fn get_comments(&self, key: &str, limit: usize) -> Result<Vec<Comment>, IntegrationError>;
+
+    /// Search one page of tickets of up to `query.limit` items.
+    ///
+    /// Pass the `next_page_token` of the previous page to continue; repositories
+    /// without pagination return everything on a single page.
+    async fn search_page(
+        &self,
+        query: &TicketSearchQuery,
+        page_token: Option<&str>,
+    ) -> Result<Page<Ticket>, IntegrationError> {
+        if page_token.is_some() {
+            return Ok(Page::last(vec![]));
+        }
+        self.search(query).await.map(Page::last)
+    }
+}
+
+/// Write actions on tickets (Jira)
+#[async_trait]
+pub trait TicketCommands: Send + Sync {
+    /// Transitions the authenticated user can currently perform on a ticket
+    async fn get_transitions(&self, key: &str) -> Result<Vec<Transition>, IntegrationError>;
+
+    /// Move a ticket through one of its transitions
+    async fn transition(&self, key: &str, transition_id: &str) -> Result<(), IntegrationError>;
+
+    /// Change the assignee of a ticket
+    async fn assign(&self, key: &str, assignee: &Assignee) -> Result<(), IntegrationError>;
+
+    /// Add a plain text comment to a ticket
+    async fn add_comment(&self, key: &str, body: &str) -> Result<Comment, IntegrationError>;
+}
+
+/// Repository trait for ticket changelogs
+#[async_trait]
+pub trait TicketHistoryRepository: Send + Sync {
+    /// Status transitions of a ticket, oldest first
+    async fn get_status_history(&self, key: &str) -> Result<Vec<StatusTransition>, IntegrationError>;
+
+    /// Tickets matching a query, with their status transitions
+    async fn search_with_history(&self, query: &TicketSearchQuery) -> Result<Vec<TicketHistory>, IntegrationError>;
+}
+
+/// Repository trait for boards and sprints (Jira Agile)
+#[async_trait]
+pub trait SprintRepository: Send + Sync {
+    /// Boards, optionally only those of a project
+    async fn list_boards(&self, project: Option<&str>) -> Result<Vec<Board>, IntegrationError>;
+
+    /// The active sprint of a board, `None` if there is none or the board has no sprints
+    async fn get_active_sprint(&self, board_id: u64) -> Result<Option<Sprint>, IntegrationError>;
+
+    /// All tickets currently in a sprint
+    async fn get_sprint_issues(&self, sprint_id: u64) -> Result<Vec<SprintIssue>, IntegrationError>;
+
+    /// Tickets added to the sprint after it started
+    async fn get_scope_changes(&self, sprint: &Sprint) -> Result<Vec<SprintIssue>, IntegrationError> {
+        let issues = self.get_sprint_issues(sprint.id).await?;
+        Ok(issues.into_iter().filter(|i| i.is_added_after_start(sprint)).collect())
+    }
 }
 
 /// Repository trait for pull request operations (Git hosting)
@@ -301,6 +925,26 @@ pub trait PullRequestRepository: Send + Sync {
 
     /// Get all open PRs for repositories
     async fn get_open_prs(&self, filter: &PrFilter) -> Result<Vec<PullRequest>, IntegrationError>;
+
+    /// Get open PRs, reporting repositories that failed instead of dropping them
+    async fn fetch_open_prs(&self, filter: &PrFilter) -> Result<PrFetchResult, IntegrationError> {
+        Ok(PrFetchResult::new(self.get_open_prs(filter).await?))
+    }
+
+    /// Get PRs merged or declined since `since`, most recently closed first.
+    /// `filter.state` narrows them to `Merged` or `Declined`.
+    async fn get_closed_prs(
+        &self,
+        _since: DateTime<Utc>,
+        _filter: &PrFilter,
+    ) -> Result<ClosedPrFetchResult, IntegrationError> {
+        Err(IntegrationError::ConfigError("Closed pull requests are not supported".to_string()))
+    }
+
+    /// Get the latest `limit` review comments of a PR with its thread statistics
+    async fn get_review_comments(&self, _repo: &str, _id: &str, _limit: usize) -> Result<ReviewComments, IntegrationError> {
+        Err(IntegrationError::ConfigError("Review comments are not supported".to_string()))
+    }
 }
 
 /// Repository trait for metrics/incident operations (Monitoring)
@@ -329,6 +973,81 @@ mod tests {
         assert_eq!(PrState::Merged.as_str(), "Merged");
     }
 
+    #[test]
+    fn test_checks_status_combine() {
+        assert_eq!(ChecksStatus::combine([]), ChecksStatus::None);
+        assert_eq!(ChecksStatus::combine([ChecksStatus::Pass, ChecksStatus::None]), ChecksStatus::Pass);
+        assert_eq!(ChecksStatus::combine([ChecksStatus::Pass, ChecksStatus::Running]), ChecksStatus::Running);
+        assert_eq!(
+            ChecksStatus::combine([ChecksStatus::Running, ChecksStatus::Fail, ChecksStatus::Pass]),
+            ChecksStatus::Fail
+        );
+    }
+
+    #[test]
+    fn test_pull_request_review_states() {
+        let user = |id: &str| User {
+            id: id.to_string(),
+            name: id.to_string(),
+            email: None,
+            avatar_url: None,
+        };
+        let pr = PullRequest {
+            id: "1".to_string(),
+            repository: "repo1".to_string(),
+            title: "PR".to_string(),
+            description: None,
+            state: PrState::Open,
+            author: user("author"),
+            reviewers: vec![
+                Reviewer::new(user("alice"), ReviewState::Pending),
+                Reviewer::new(user("bob"), ReviewState::ChangesRequested),
+                Reviewer::new(user("carol"), ReviewState::Approved),
+            ],
+            source_branch: "feature".to_string(),
+            target_branch: "main".to_string(),
+            checks_status: ChecksStatus::None,
+            is_stale: false,
+            updated_at: Utc::now(),
+            created_at: Utc::now(),
+            url: String::new(),
+            labels: vec![],
+            diff_stats: None,
+            connection: None,
+        };
+
+        assert!(pr.is_waiting_on("alice"));
+        assert!(!pr.is_waiting_on("bob"));
+        assert!(!pr.is_waiting_on("carol"));
+        assert!(pr.has_changes_requested());
+        assert!(pr.reviewers[2].is_approved());
+    }
+
+    #[test]
+    fn test_repository_error_kind_from_integration_error() {
+        let err = RepositoryError::new("repo1", &IntegrationError::Auth("revoked".to_string()));
+        assert_eq!(err.repository, "repo1");
+        assert_eq!(err.kind, RepositoryErrorKind::Auth);
+
+        let err = RepositoryError::new("repo2", &IntegrationError::RateLimit { reset_at: None });
+        assert_eq!(err.kind, RepositoryErrorKind::RateLimit);
+
+        let err = RepositoryError::new("repo3", &IntegrationError::ParseError("bad".to_string()));
+        assert_eq!(err.kind, RepositoryErrorKind::Api);
+
+        let err = RepositoryError::new("repo4", &IntegrationError::PermissionDenied("read".to_string()));
+        assert_eq!(err.kind, RepositoryErrorKind::Auth);
+    }
+
+    #[test]
+    fn test_pr_fetch_result_is_partial() {
+        let mut result = PrFetchResult::new(vec![]);
+        assert!(!result.is_partial());
+
+        result.errors.push(RepositoryError::new("repo1", &IntegrationError::NotFound("gone".to_string())));
+        assert!(result.is_partial());
+    }
+
     #[test]
     fn test_severity_ordering() {
         assert!(Severity::Critical > Severity::High);
@@ -348,6 +1067,52 @@ mod tests {
         assert_eq!(query.limit, 5);
     }
 
+    #[test]
+    fn test_ticket_search_query_filters() {
+        let query = TicketSearchQuery::new()
+            .with_label("backend")
+            .with_label("urgent")
+            .with_priority(Priority::High)
+            .with_sprint(SprintFilter::Open)
+            .with_any_of(vec![
+                TicketSearchQuery::new().with_assignee("alice"),
+                TicketSearchQuery::new().with_reporter("alice"),
+            ]);
+
+        assert_eq!(query.labels, vec!["backend", "urgent"]);
+        assert_eq!(query.priorities, vec![Priority::High]);
+        assert_eq!(query.sprint, Some(SprintFilter::Open));
+        assert_eq!(query.any_of.len(), 2);
+    }
+
+    #[test]
+    fn test_page() {
+        let page = Page::new(vec![1, 2], Some("next".to_string()));
+        assert!(page.has_more());
+        assert!(!Page::last(vec![3]).has_more());
+    }
+
+    #[test]
+    fn test_sprint_days_left() {
+        use chrono::TimeZone;
+
+        let now = Utc.with_ymd_and_hms(2024, 3, 4, 9, 0, 0).unwrap();
+        let sprint = Sprint {
+            id: 42,
+            name: "Sprint 42".to_string(),
+            state: SprintState::Active,
+            goal: None,
+            start_date: Some(Utc.with_ymd_and_hms(2024, 2, 26, 9, 0, 0).unwrap()),
+            end_date: Some(Utc.with_ymd_and_hms(2024, 3, 8, 17, 0, 0).unwrap()),
+            complete_date: None,
+            board_id: Some(1),
+        };
+
+        assert_eq!(sprint.days_left(now), Some(5));
+        assert_eq!(sprint.days_left(Utc.with_ymd_and_hms(2024, 3, 9, 0, 0, 0).unwrap()), Some(0));
+        assert_eq!(Sprint { end_date: None, ..sprint }.days_left(now), None);
+    }
+
     #[test]
     fn test_pr_filter_builder() {
         let filter = PrFilter::new()
@@ -358,4 +1123,117 @@ mod tests {
         assert!(filter.stale_only);
         assert_eq!(filter.stale_threshold_hours, 48);
     }
+
+    #[test]
+    fn test_diff_stats_from_files() {
+        let stats = DiffStats::from_files(vec![
+            ChangedFile { path: "a.rs".to_string(), additions: 10, deletions: 2 },
+            ChangedFile { path: "b.rs".to_string(), additions: 0, deletions: 5 },
+        ]);
+
+        assert_eq!((stats.additions, stats.deletions, stats.changed_files), (10, 7, 2));
+        assert_eq!(stats.lines_changed(), 17);
+    }
+
+    fn comment(id: &str, thread: &str, author: &str, hour: u32, resolved: Option<bool>) -> ReviewComment {
+        use chrono::TimeZone;
+        let at = Utc.with_ymd_and_hms(2024, 3, 1, hour, 0, 0).unwrap();
+        ReviewComment {
+            id: id.to_string(),
+            thread_id: thread.to_string(),
+            author: User { id: author.to_string(), name: author.to_string(), email: None, avatar_url: None },
+            body: format!("Comment {}", id),
+            path: Some("src/lib.rs".to_string()),
+            line: Some(10),
+            resolved,
+            created_at: at,
+            updated_at: at,
+        }
+    }
+
+    #[test]
+    fn test_review_comments_summary() {
+        let comments = vec![
+            comment("3", "t2", "alice", 12, Some(false)),
+            comment("1", "t1", "bob", 9, Some(true)),
+            comment("2", "t2", "bob", 10, Some(false)),
+            comment("4", "c4", "carol", 11, None),
+        ];
+
+        let summary = ReviewComments::from_comments(comments, 2);
+
+        assert_eq!(summary.total, 4);
+        assert_eq!(summary.unresolved_threads, 1);
+        let ids: Vec<&str> = summary.comments.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["4", "3"]);
+        assert_eq!(summary.last_activity.as_ref().unwrap().user.name, "alice");
+    }
+
+    #[test]
+    fn test_review_comments_court() {
+        let pr: PullRequest = serde_json::from_value(serde_json::json!({
+            "id": "1", "repository": "repo", "title": "PR", "description": null, "state": "Open",
+            "author": {"id": "alice", "name": "alice", "email": null, "avatar_url": null},
+            "reviewers": [], "source_branch": "feature", "target_branch": "main",
+            "checks_status": "None", "is_stale": false,
+            "updated_at": "2024-03-01T00:00:00Z", "created_at": "2024-03-01T00:00:00Z", "url": ""
+        }))
+        .unwrap();
+
+        let author_last = ReviewComments::from_comments(vec![comment("1", "t1", "alice", 9, None)], 10);
+        assert_eq!(author_last.court(&pr), Some(PrCourt::Reviewers));
+
+        let reviewer_last = ReviewComments::from_comments(
+            vec![comment("1", "t1", "alice", 9, None), comment("2", "t1", "bob", 10, None)],
+            10,
+        );
+        assert_eq!(reviewer_last.court(&pr), Some(PrCourt::Author));
+        assert_eq!(ReviewComments::default().court(&pr), None);
+
+        // The author editing an old comment is not a reply
+        let mut edited = comment("1", "t1", "alice", 9, None);
+        edited.updated_at = edited.created_at + chrono::Duration::hours(5);
+        let reviewer_last = ReviewComments::from_comments(vec![edited, comment("2", "t1", "bob", 10, None)], 10);
+        assert_eq!(reviewer_last.court(&pr), Some(PrCourt::Author));
+    }
+
+    #[test]
+    fn test_closed_pr_durations() {
+        use chrono::TimeZone;
+        let pr: PullRequest = serde_json::from_value(serde_json::json!({
+            "id": "1", "repository": "repo", "title": "PR", "description": null, "state": "Merged",
+            "author": {"id": "alice", "name": "alice", "email": null, "avatar_url": null},
+            "reviewers": [], "source_branch": "feature", "target_branch": "main",
+            "checks_status": "None", "is_stale": false,
+            "updated_at": "2024-03-02T00:00:00Z", "created_at": "2024-03-01T00:00:00Z", "url": ""
+        }))
+        .unwrap();
+        let at = |hour: u32| Utc.with_ymd_and_hms(2024, 3, 1, hour, 0, 0).unwrap();
+
+        let mut closed = ClosedPullRequest {
+            pr,
+            closed_at: at(20),
+            merged_at: Some(at(20)),
+            merged_by: None,
+            first_review_at: Some(at(3)),
+        };
+        assert!(closed.is_merged());
+        assert_eq!(closed.lead_time(), Some(chrono::Duration::hours(20)));
+        assert_eq!(closed.time_to_first_review(), Some(chrono::Duration::hours(3)));
+
+        closed.pr.state = PrState::Declined;
+        closed.merged_at = None;
+        closed.first_review_at = None;
+        assert!(!closed.is_merged());
+        assert_eq!(closed.lead_time(), None);
+        assert_eq!(closed.time_to_first_review(), None);
+    }
+
+    #[test]
+    fn test_pr_filter_accepts_closed() {
+        assert!(PrFilter::new().accepts_closed(PrState::Declined));
+        let merged = PrFilter::new().with_state(PrState::Merged);
+        assert!(merged.accepts_closed(PrState::Merged));
+        assert!(!merged.accepts_closed(PrState::Declined));
+    }
 }
@@ -10,9 +10,15 @@ use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 use thiserror::Error;
 
+use crate::core::DEFAULT_GIT_CONNECTION;
+
 /// Service name used for keychain entries
 const SERVICE_NAME: &str = "com.em-cockpit.credentials";
 
+/// Entry listing the git connections with their own token, so a panic wipe
+/// can find them (keychains cannot be enumerated)
+const GIT_CONNECTIONS_INDEX: &str = "git_connections";
+
 /// Supported credential types for the EM Cockpit
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum CredentialKey {
@@ -122,6 +128,10 @@ impl CredentialManager {
     /// * `Ok(())` if the credential was stored successfully
     /// * `Err(CredentialError)` if storage failed
     pub fn store(&self, key: CredentialKey, value: &str) -> Result<(), CredentialError> {
+        self.store_account(key.as_str(), value)
+    }
+
+    fn store_account(&self, account: &str, value: &str) -> Result<(), CredentialError> {
         if value.is_empty() {
             return Err(CredentialError::InvalidData(
                 "Credential value cannot be empty".to_string(),
@@ -130,7 +140,7 @@ impl CredentialManager {
 
         match &self.backend {
             StorageBackend::Keychain { service_name } => {
-                let entry = keyring::Entry::new(service_name, key.as_str())
+                let entry = keyring::Entry::new(service_name, account)
                     .map_err(|e| CredentialError::StoreFailed(e.to_string()))?;
 
                 entry
@@ -141,11 +151,11 @@ impl CredentialManager {
                 let mut store = store
                     .write()
                     .map_err(|e| CredentialError::StoreFailed(e.to_string()))?;
-                store.insert(key.as_str().to_string(), value.to_string());
+                store.insert(account.to_string(), value.to_string());
             }
         }
 
-        log::debug!("Credential stored successfully: {}", key.as_str());
+        log::debug!("Credential stored successfully: {}", account);
         Ok(())
     }
 
@@ -158,19 +168,23 @@ impl CredentialManager {
     /// * `Ok(String)` containing the credential value
     /// * `Err(CredentialError::NotFound)` if the credential doesn't exist
     pub fn retrieve(&self, key: CredentialKey) -> Result<String, CredentialError> {
+        self.retrieve_account(key.as_str())
+    }
+
+    fn retrieve_account(&self, account: &str) -> Result<String, CredentialError> {
         match &self.backend {
             StorageBackend::Keychain { service_name } => {
-                let entry = keyring::Entry::new(service_name, key.as_str())
+                let entry = keyring::Entry::new(service_name, account)
                     .map_err(|e| CredentialError::NotFound(e.to_string()))?;
 
                 let password = entry.get_password().map_err(|e| match e {
                     keyring::Error::NoEntry => {
-                        CredentialError::NotFound(format!("Credential '{}' not found", key.as_str()))
+                        CredentialError::NotFound(format!("Credential '{}' not found", account))
                     }
                     _ => CredentialError::AccessDenied(e.to_string()),
                 })?;
 
-                log::debug!("Credential retrieved successfully: {}", key.as_str());
+                log::debug!("Credential retrieved successfully: {}", account);
                 Ok(password)
             }
             StorageBackend::InMemory { store } => {
@@ -179,10 +193,10 @@ impl CredentialManager {
                     .map_err(|e| CredentialError::NotFound(e.to_string()))?;
                 
                 store
-                    .get(key.as_str())
+                    .get(account)
                     .cloned()
                     .ok_or_else(|| {
-                        CredentialError::NotFound(format!("Credential '{}' not found", key.as_str()))
+                        CredentialError::NotFound(format!("Credential '{}' not found", account))
                     })
             }
         }
@@ -197,21 +211,25 @@ impl CredentialManager {
     /// * `Ok(())` if the credential was deleted successfully
     /// * `Err(CredentialError)` if deletion failed
     pub fn delete(&self, key: CredentialKey) -> Result<(), CredentialError> {
+        self.delete_account(key.as_str())
+    }
+
+    fn delete_account(&self, account: &str) -> Result<(), CredentialError> {
         match &self.backend {
             StorageBackend::Keychain { service_name } => {
-                let entry = keyring::Entry::new(service_name, key.as_str())
+                let entry = keyring::Entry::new(service_name, account)
                     .map_err(|e| CredentialError::DeleteFailed(e.to_string()))?;
 
                 match entry.delete_credential() {
                     Ok(()) => {
-                        log::debug!("Credential deleted successfully: {}", key.as_str());
+                        log::debug!("Credential deleted successfully: {}", account);
                         Ok(())
                     }
                     Err(keyring::Error::NoEntry) => {
-                        log::debug!("Credential already deleted or never existed: {}", key.as_str());
+                        log::debug!("Credential already deleted or never existed: {}", account);
                         Err(CredentialError::NotFound(format!(
                             "Credential '{}' not found",
-                            key.as_str()
+                            account
                         )))
                     }
                     Err(e) => Err(CredentialError::DeleteFailed(e.to_string())),
@@ -222,13 +240,13 @@ impl CredentialManager {
                     .write()
                     .map_err(|e| CredentialError::DeleteFailed(e.to_string()))?;
                 
-                if store.remove(key.as_str()).is_some() {
-                    log::debug!("Credential deleted successfully: {}", key.as_str());
+                if store.remove(account).is_some() {
+                    log::debug!("Credential deleted successfully: {}", account);
                     Ok(())
                 } else {
                     Err(CredentialError::NotFound(format!(
                         "Credential '{}' not found",
-                        key.as_str()
+                        account
                     )))
                 }
             }
@@ -247,6 +265,65 @@ impl CredentialManager {
         self.retrieve(key).is_ok()
     }
 
+    /// Keychain entry holding the token of a git connection.
+    ///
+    /// The default connection keeps using the `git_token` entry from before
+    /// multiple connections were supported.
+    pub fn git_token_account(connection_id: &str) -> String {
+        if connection_id == DEFAULT_GIT_CONNECTION {
+            CredentialKey::GitToken.as_str().to_string()
+        } else {
+            format!("{}:{}", CredentialKey::GitToken.as_str(), connection_id)
+        }
+    }
+
+    /// Store the token of a git connection in its own slot
+    pub fn store_git_token(&self, connection_id: &str, value: &str) -> Result<(), CredentialError> {
+        if connection_id.is_empty() || connection_id.contains(',') {
+            return Err(CredentialError::InvalidData(format!(
+                "Invalid git connection id: '{}'",
+                connection_id
+            )));
+        }
+
+        self.store_account(&Self::git_token_account(connection_id), value)?;
+
+        let mut connections = self.git_connections();
+        if connection_id != DEFAULT_GIT_CONNECTION && !connections.iter().any(|c| c == connection_id) {
+            connections.push(connection_id.to_string());
+            self.store_account(GIT_CONNECTIONS_INDEX, &connections.join(","))?;
+        }
+        Ok(())
+    }
+
+    /// Retrieve the token of a git connection
+    pub fn retrieve_git_token(&self, connection_id: &str) -> Result<String, CredentialError> {
+        self.retrieve_account(&Self::git_token_account(connection_id))
+    }
+
+    /// Delete the token of a git connection
+    pub fn delete_git_token(&self, connection_id: &str) -> Result<(), CredentialError> {
+        self.delete_account(&Self::git_token_account(connection_id))?;
+
+        let connections = self.git_connections();
+        let remaining: Vec<String> = connections.iter().filter(|c| *c != connection_id).cloned().collect();
+        if remaining.len() != connections.len() {
+            if remaining.is_empty() {
+                let _ = self.delete_account(GIT_CONNECTIONS_INDEX);
+            } else {
+                self.store_account(GIT_CONNECTIONS_INDEX, &remaining.join(","))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Ids of the non-default git connections that have a token slot
+    pub fn git_connections(&self) -> Vec<String> {
+        self.retrieve_account(GIT_CONNECTIONS_INDEX)
+            .map(|index| index.split(',').filter(|id| !id.is_empty()).map(str::to_string).collect())
+            .unwrap_or_default()
+    }
+
     /// Execute panic wipe - delete ALL stored credentials
     ///
     /// This is an emergency function to clear all sensitive data.
@@ -257,6 +334,22 @@ impl CredentialManager {
     pub fn panic_wipe(&self) -> Result<usize, CredentialError> {
         let mut deleted_count = 0;
 
+        for connection_id in self.git_connections() {
+            match self.delete_account(&Self::git_token_account(&connection_id)) {
+                Ok(()) => {
+                    deleted_count += 1;
+                    log::info!("Panic wipe: deleted git token of {}", connection_id);
+                }
+                Err(CredentialError::NotFound(_)) => {
+                    log::debug!("Panic wipe: git token of {} already gone", connection_id);
+                }
+                Err(e) => {
+                    log::error!("Panic wipe: failed to delete git token of {}: {}", connection_id, e);
+                }
+            }
+        }
+        let _ = self.delete_account(GIT_CONNECTIONS_INDEX);
+
         for key in CredentialKey::all() {
             match self.delete(key) {
                 Ok(()) => {
@@ -372,6 +465,41 @@ mod tests {
         assert!(!manager.exists(CredentialKey::GeminiApiKey));
     }
 
+    #[test]
+    fn test_git_connections_have_own_slots() {
+        let manager = test_manager();
+
+        manager.store_git_token(DEFAULT_GIT_CONNECTION, "default-token").unwrap();
+        manager.store_git_token("github", "github-token").unwrap();
+        manager.store_git_token("bitbucket", "bitbucket-token").unwrap();
+
+        assert_eq!(manager.retrieve_git_token("github").unwrap(), "github-token");
+        assert_eq!(manager.retrieve_git_token("bitbucket").unwrap(), "bitbucket-token");
+        // The default connection reuses the legacy slot
+        assert_eq!(manager.retrieve(CredentialKey::GitToken).unwrap(), "default-token");
+        assert_eq!(manager.git_connections(), vec!["github".to_string(), "bitbucket".to_string()]);
+
+        manager.delete_git_token("github").unwrap();
+        assert!(manager.retrieve_git_token("github").is_err());
+        assert_eq!(manager.git_connections(), vec!["bitbucket".to_string()]);
+
+        assert!(matches!(manager.store_git_token("", "t"), Err(CredentialError::InvalidData(_))));
+    }
+
+    #[test]
+    fn test_panic_wipe_clears_git_connection_tokens() {
+        let manager = test_manager();
+
+        manager.store_git_token("github", "github-token").unwrap();
+        manager.store_git_token("gitlab", "gitlab-token").unwrap();
+        manager.store(CredentialKey::JiraToken, "jira-token").unwrap();
+
+        assert_eq!(manager.panic_wipe().unwrap(), 3);
+        assert!(manager.retrieve_git_token("github").is_err());
+        assert!(manager.retrieve_git_token("gitlab").is_err());
+        assert!(manager.git_connections().is_empty());
+    }
+
     #[test]
     fn test_credential_key_as_str() {
         assert_eq!(CredentialKey::JiraToken.as_str(), "jira_token");
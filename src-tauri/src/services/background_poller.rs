@@ -7,9 +7,10 @@ use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::RwLock;
 use tokio::time::{interval, Interval};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 
 use crate::core::events::{AppEvent, SharedEventBus};
+use crate::integrations::rate_limit::{RateLimitBudget, RateLimitTracker, SEARCH_RESOURCE};
 use crate::system::TrayState;
 
 /// Polling configuration
@@ -27,6 +28,8 @@ pub struct PollerConfig {
     pub max_retries: usize,
     /// Backoff duration after failure
     pub retry_backoff: Duration,
+    /// Requests left untouched by polling so interactive use keeps working
+    pub rate_limit_reserve: u64,
 }
 
 impl Default for PollerConfig {
@@ -38,6 +41,7 @@ impl Default for PollerConfig {
             incident_polling_enabled: true,
             max_retries: 3,
             retry_backoff: Duration::from_secs(5),
+            rate_limit_reserve: 10,
         }
     }
 }
@@ -57,6 +61,11 @@ impl PollerConfig {
         self
     }
 
+    pub fn with_rate_limit_reserve(mut self, reserve: u64) -> Self {
+        self.rate_limit_reserve = reserve;
+        self
+    }
+
     pub fn disable_pr_polling(mut self) -> Self {
         self.pr_polling_enabled = false;
         self
@@ -123,6 +132,12 @@ pub struct PollerState {
     pub consecutive_pr_failures: usize,
     pub consecutive_incident_failures: usize,
     pub current_tray_state: TrayState,
+    /// PR polling is deferred until this time because of rate limits
+    pub pr_deferred_until: Option<DateTime<Utc>>,
+    /// Incident polling is deferred until this time because of rate limits
+    pub incident_deferred_until: Option<DateTime<Utc>>,
+    pub deferred_pr_polls: usize,
+    pub deferred_incident_polls: usize,
 }
 
 impl Default for PollerState {
@@ -135,6 +150,10 @@ impl Default for PollerState {
             consecutive_pr_failures: 0,
             consecutive_incident_failures: 0,
             current_tray_state: TrayState::Neutral,
+            pr_deferred_until: None,
+            incident_deferred_until: None,
+            deferred_pr_polls: 0,
+            deferred_incident_polls: 0,
         }
     }
 }
@@ -145,6 +164,8 @@ pub struct BackgroundPoller {
     state: Arc<RwLock<PollerState>>,
     event_bus: SharedEventBus,
     running: Arc<RwLock<bool>>,
+    pr_rate_limiters: Vec<RateLimitTracker>,
+    incident_rate_limiters: Vec<RateLimitTracker>,
 }
 
 impl BackgroundPoller {
@@ -155,9 +176,35 @@ impl BackgroundPoller {
             state: Arc::new(RwLock::new(PollerState::default())),
             event_bus,
             running: Arc::new(RwLock::new(false)),
+            pr_rate_limiters: Vec::new(),
+            incident_rate_limiters: Vec::new(),
         }
     }
 
+    /// Defer PR polling while this integration's rate limit is exhausted
+    pub fn with_pr_rate_limiter(mut self, tracker: RateLimitTracker) -> Self {
+        self.pr_rate_limiters.push(tracker);
+        self
+    }
+
+    /// Defer incident polling while this integration's rate limit is exhausted
+    pub fn with_incident_rate_limiter(mut self, tracker: RateLimitTracker) -> Self {
+        self.incident_rate_limiters.push(tracker);
+        self
+    }
+
+    /// Latest reset among the budgets down to the reserve. Search budgets are
+    /// skipped since polling never searches.
+    fn deferred_until(&self, trackers: &[RateLimitTracker]) -> Option<DateTime<Utc>> {
+        let now = Utc::now();
+        trackers
+            .iter()
+            .flat_map(|t| t.budgets())
+            .filter(|b| b.resource != SEARCH_RESOURCE)
+            .filter_map(|b| b.exhausted_until(self.config.rate_limit_reserve, now))
+            .max()
+    }
+
     /// Check if poller is running
     pub async fn is_running(&self) -> bool {
         *self.running.read().await
@@ -194,6 +241,22 @@ impl BackgroundPoller {
 
     /// Execute a single PR poll cycle
     pub async fn poll_prs(&self) -> PollResult<PrPollData> {
+        let deferred_until = self.deferred_until(&self.pr_rate_limiters);
+        {
+            let mut state = self.state.write().await;
+            state.pr_deferred_until = deferred_until;
+            if deferred_until.is_some() {
+                state.deferred_pr_polls += 1;
+            }
+        }
+        if let Some(until) = deferred_until {
+            log::info!("BackgroundPoller: Deferring PR poll until {}", until);
+            return PollResult::failure(
+                PrPollData::default(),
+                format!("Rate limited until {}", until.to_rfc3339()),
+            );
+        }
+
         log::debug!("BackgroundPoller: Polling PRs");
         
         let result = self.fetch_pr_data().await;
@@ -228,6 +291,22 @@ impl BackgroundPoller {
 
     /// Execute a single incident poll cycle
     pub async fn poll_incidents(&self) -> PollResult<IncidentPollData> {
+        let deferred_until = self.deferred_until(&self.incident_rate_limiters);
+        {
+            let mut state = self.state.write().await;
+            state.incident_deferred_until = deferred_until;
+            if deferred_until.is_some() {
+                state.deferred_incident_polls += 1;
+            }
+        }
+        if let Some(until) = deferred_until {
+            log::info!("BackgroundPoller: Deferring incident poll until {}", until);
+            return PollResult::failure(
+                IncidentPollData::default(),
+                format!("Rate limited until {}", until.to_rfc3339()),
+            );
+        }
+
         log::debug!("BackgroundPoller: Polling incidents");
         
         let result = self.fetch_incident_data().await;
@@ -318,6 +397,14 @@ impl BackgroundPoller {
             last_incident_poll: state.last_incident_poll,
             consecutive_pr_failures: state.consecutive_pr_failures,
             consecutive_incident_failures: state.consecutive_incident_failures,
+            pr_deferred_until: state.pr_deferred_until,
+            incident_deferred_until: state.incident_deferred_until,
+            rate_limits: self
+                .pr_rate_limiters
+                .iter()
+                .chain(&self.incident_rate_limiters)
+                .flat_map(|t| t.budgets())
+                .collect(),
         }
     }
 }
@@ -331,6 +418,10 @@ pub struct PollingStats {
     pub last_incident_poll: Option<chrono::DateTime<Utc>>,
     pub consecutive_pr_failures: usize,
     pub consecutive_incident_failures: usize,
+    pub pr_deferred_until: Option<DateTime<Utc>>,
+    pub incident_deferred_until: Option<DateTime<Utc>>,
+    /// Last known request budget of every rate-limited integration
+    pub rate_limits: Vec<RateLimitBudget>,
 }
 
 impl std::fmt::Debug for BackgroundPoller {
@@ -499,6 +590,87 @@ mod tests {
         assert_eq!(state.current_tray_state, TrayState::Neutral);
     }
 
+    fn exhausted_tracker(name: &str) -> RateLimitTracker {
+        use reqwest::header::{HeaderMap, HeaderValue};
+
+        let reset = (Utc::now() + chrono::Duration::minutes(30)).timestamp();
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("0"));
+        headers.insert("x-ratelimit-reset", HeaderValue::from_str(&reset.to_string()).unwrap());
+
+        let tracker = RateLimitTracker::new(name);
+        tracker.observe(403, &headers);
+        tracker
+    }
+
+    #[tokio::test]
+    async fn test_poll_prs_deferred_while_rate_limited() {
+        let event_bus = Arc::new(EventBus::new());
+        let event_count = Arc::new(AtomicUsize::new(0));
+        let event_count_clone = event_count.clone();
+        event_bus.subscribe(move |_| {
+            event_count_clone.fetch_add(1, Ordering::SeqCst);
+        });
+
+        let poller = BackgroundPoller::new(PollerConfig::default(), event_bus)
+            .with_pr_rate_limiter(exhausted_tracker("github"));
+
+        let result = poller.poll_prs().await;
+        assert!(!result.success);
+        assert!(result.error_message.unwrap().starts_with("Rate limited until"));
+        assert_eq!(event_count.load(Ordering::SeqCst), 0);
+
+        let stats = poller.get_stats().await;
+        assert_eq!(stats.pr_poll_count, 0);
+        assert_eq!(stats.consecutive_pr_failures, 0);
+        assert!(stats.pr_deferred_until.is_some());
+        assert_eq!(stats.rate_limits.len(), 1);
+        assert_eq!(stats.rate_limits[0].remaining, Some(0));
+
+        // Incident polling has its own budget
+        assert!(poller.poll_incidents().await.success);
+    }
+
+    #[tokio::test]
+    async fn test_poll_prs_respects_rate_limit_reserve() {
+        use reqwest::header::{HeaderMap, HeaderValue};
+
+        let reset = (Utc::now() + chrono::Duration::minutes(30)).timestamp();
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("25"));
+        headers.insert("x-ratelimit-reset", HeaderValue::from_str(&reset.to_string()).unwrap());
+        let tracker = RateLimitTracker::new("github");
+        tracker.observe(200, &headers);
+
+        let event_bus = Arc::new(EventBus::new());
+        let poller = BackgroundPoller::new(PollerConfig::default(), event_bus.clone())
+            .with_pr_rate_limiter(tracker.clone());
+        assert!(poller.poll_prs().await.success);
+
+        let config = PollerConfig::default().with_rate_limit_reserve(50);
+        let poller = BackgroundPoller::new(config, event_bus).with_pr_rate_limiter(tracker);
+        assert!(!poller.poll_prs().await.success);
+        assert_eq!(poller.get_state().await.deferred_pr_polls, 1);
+    }
+
+    #[tokio::test]
+    async fn test_poll_prs_ignores_search_budget() {
+        use reqwest::header::{HeaderMap, HeaderValue};
+
+        let reset = (Utc::now() + chrono::Duration::minutes(1)).timestamp();
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-resource", HeaderValue::from_static("search"));
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("8"));
+        headers.insert("x-ratelimit-reset", HeaderValue::from_str(&reset.to_string()).unwrap());
+        let tracker = RateLimitTracker::new("github");
+        tracker.observe(200, &headers);
+
+        let poller = BackgroundPoller::new(PollerConfig::default(), Arc::new(EventBus::new()))
+            .with_pr_rate_limiter(tracker);
+        assert!(poller.poll_prs().await.success);
+        assert_eq!(poller.get_stats().await.rate_limits.len(), 2);
+    }
+
     #[tokio::test]
     async fn test_multiple_poll_cycles() {
         let poller = create_test_poller();
@@ -9,7 +9,7 @@ use rusqlite::{params, Connection, OptionalExtension};
 use serde::{de::DeserializeOwned, Serialize};
 use std::num::NonZeroUsize;
 use std::path::PathBuf;
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, RwLock};
 use thiserror::Error;
 
 /// Default memory cache size (number of entries)
@@ -95,7 +95,8 @@ impl CacheEntry {
 /// ```
 pub struct CacheService {
     memory_cache: Arc<RwLock<LruCache<String, CacheEntry>>>,
-    db_connection: Option<Arc<RwLock<Connection>>>,
+    // A `Mutex` keeps the service `Sync`, which `Connection` on its own is not
+    db_connection: Option<Arc<Mutex<Connection>>>,
     config: CacheConfig,
 }
 
@@ -111,7 +112,7 @@ impl CacheService {
 
         Ok(Self {
             memory_cache: Arc::new(RwLock::new(LruCache::new(memory_size))),
-            db_connection: Some(Arc::new(RwLock::new(conn))),
+            db_connection: Some(Arc::new(Mutex::new(conn))),
             config: CacheConfig::default(),
         })
     }
@@ -135,7 +136,7 @@ impl CacheService {
             let conn = Connection::open(&path)
                 .map_err(|e| CacheError::DatabaseError(e.to_string()))?;
             Self::init_db(&conn)?;
-            Some(Arc::new(RwLock::new(conn)))
+            Some(Arc::new(Mutex::new(conn)))
         } else {
             None
         };
@@ -188,7 +189,7 @@ impl CacheService {
         // Store in SQLite (Tier 2) if available
         if let Some(ref db) = self.db_connection {
             let conn = db
-                .write()
+                .lock()
                 .map_err(|e| CacheError::LockError(e.to_string()))?;
 
             conn.execute(
@@ -226,7 +227,7 @@ impl CacheService {
         // Try Tier 2 (SQLite) if available
         if let Some(ref db) = self.db_connection {
             let conn = db
-                .read()
+                .lock()
                 .map_err(|e| CacheError::LockError(e.to_string()))?;
 
             let result: Option<(String, String)> = conn
@@ -263,10 +264,6 @@ impl CacheService {
                         .map_err(|e| CacheError::SerializationError(e.to_string()));
                 } else {
                     // Clean up expired entry
-                    drop(conn);
-                    let conn = db
-                        .write()
-                        .map_err(|e| CacheError::LockError(e.to_string()))?;
                     let _ = conn.execute("DELETE FROM cache WHERE key = ?1", params![key]);
                     return Err(CacheError::Expired(key.to_string()));
                 }
@@ -295,7 +292,7 @@ impl CacheService {
         // Remove from SQLite if available
         if let Some(ref db) = self.db_connection {
             let conn = db
-                .write()
+                .lock()
                 .map_err(|e| CacheError::LockError(e.to_string()))?;
 
             conn.execute("DELETE FROM cache WHERE key = ?1", params![key])
@@ -320,7 +317,7 @@ impl CacheService {
         // Clear SQLite if available
         if let Some(ref db) = self.db_connection {
             let conn = db
-                .write()
+                .lock()
                 .map_err(|e| CacheError::LockError(e.to_string()))?;
 
             conn.execute("DELETE FROM cache", [])
@@ -346,7 +343,7 @@ impl CacheService {
 
         // Try SQLite
         if let Some(ref db) = self.db_connection {
-            if let Ok(conn) = db.read() {
+            if let Ok(conn) = db.lock() {
                 let result: Option<String> = conn
                     .query_row(
                         "SELECT value FROM cache WHERE key = ?1",
@@ -373,7 +370,7 @@ impl CacheService {
         // Cleanup SQLite
         if let Some(ref db) = self.db_connection {
             let conn = db
-                .write()
+                .lock()
                 .map_err(|e| CacheError::LockError(e.to_string()))?;
 
             let now = Utc::now().to_rfc3339();
@@ -0,0 +1,460 @@
+//! Cycle Time Service
+//!
+//! Turns ticket status histories into flow metrics: lead time (created to done),
+//! cycle time (first in progress to done), time in each status and reopen
+//! counts, per ticket and aggregated per team.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::integrations::traits::{
+    IntegrationError, StatusCategory, TicketHistory, TicketHistoryRepository, TicketSearchQuery,
+};
+
+/// Label of tickets without a team
+pub const NO_TEAM: &str = "No team";
+
+/// Flow metrics of one ticket
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TicketFlowMetrics {
+    pub key: String,
+    pub summary: String,
+    pub team: Option<String>,
+    pub assignee: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// First move into an in-progress status
+    pub started_at: Option<DateTime<Utc>>,
+    /// Last move into a done status, `None` while the ticket is open
+    pub completed_at: Option<DateTime<Utc>>,
+    pub lead_time_hours: Option<f64>,
+    pub cycle_time_hours: Option<f64>,
+    /// Hours spent in each status, excluding the final done status
+    pub time_in_status_hours: HashMap<String, f64>,
+    /// Moves out of a done status
+    pub reopen_count: u32,
+}
+
+/// Flow metrics aggregated over a group of tickets
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamFlowMetrics {
+    pub team: String,
+    pub ticket_count: usize,
+    pub completed_count: usize,
+    pub avg_lead_time_hours: Option<f64>,
+    pub avg_cycle_time_hours: Option<f64>,
+    pub median_cycle_time_hours: Option<f64>,
+    /// 85% of tickets finish within this many hours of starting
+    pub p85_cycle_time_hours: Option<f64>,
+    /// Average hours per ticket in each status
+    pub time_in_status_hours: HashMap<String, f64>,
+    pub reopen_count: u32,
+    /// Share of tickets reopened at least once, in percent
+    pub reopen_rate_percent: f64,
+}
+
+/// Cycle time report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CycleTimeReport {
+    pub tickets: Vec<TicketFlowMetrics>,
+    /// Per team, largest first
+    pub teams: Vec<TeamFlowMetrics>,
+    pub overall: TeamFlowMetrics,
+    pub generated_at: DateTime<Utc>,
+}
+
+/// Cycle time configuration
+#[derive(Debug, Clone)]
+pub struct CycleTimeConfig {
+    /// Upper bound of tickets loaded for one report
+    pub max_tickets: usize,
+}
+
+impl Default for CycleTimeConfig {
+    fn default() -> Self {
+        Self { max_tickets: 500 }
+    }
+}
+
+impl CycleTimeConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_max_tickets(mut self, max_tickets: usize) -> Self {
+        self.max_tickets = max_tickets;
+        self
+    }
+}
+
+fn hours(duration: Duration) -> f64 {
+    duration.num_minutes() as f64 / 60.0
+}
+
+fn average(values: &[f64]) -> Option<f64> {
+    (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+}
+
+/// Nearest-rank percentile of sorted values
+fn percentile(sorted: &[f64], p: f64) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+/// Cycle Time Service
+pub struct CycleTimeService<R: TicketHistoryRepository> {
+    repo: Arc<R>,
+    config: CycleTimeConfig,
+}
+
+impl<R: TicketHistoryRepository> CycleTimeService<R> {
+    pub fn new(repo: Arc<R>, config: CycleTimeConfig) -> Self {
+        Self { repo, config }
+    }
+
+    /// Report on the tickets matching a query
+    pub async fn get_report(&self, query: &TicketSearchQuery) -> Result<CycleTimeReport, IntegrationError> {
+        let query = TicketSearchQuery {
+            limit: query.limit.min(self.config.max_tickets),
+            ..query.clone()
+        };
+        let histories = self.repo.search_with_history(&query).await?;
+        Ok(self.build_report(&histories, Utc::now()))
+    }
+
+    /// Report on the tickets completed in `[from, to)`, e.g. for a monthly review
+    pub async fn get_completed_report(
+        &self,
+        project: Option<&str>,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<CycleTimeReport, IntegrationError> {
+        // Completion is not searchable, but a ticket completed after `from` was updated after it too
+        let mut query = TicketSearchQuery::new()
+            .with_status_category(StatusCategory::Done)
+            .updated_since(from)
+            .with_limit(self.config.max_tickets);
+        if let Some(project) = project {
+            query = query.with_project(project);
+        }
+
+        let histories = self.repo.search_with_history(&query).await?;
+        let completed: Vec<TicketHistory> = histories
+            .into_iter()
+            .filter(|h| {
+                Self::ticket_metrics(h, to)
+                    .completed_at
+                    .is_some_and(|done| done >= from && done < to)
+            })
+            .collect();
+
+        Ok(self.build_report(&completed, Utc::now()))
+    }
+
+    /// Compute the report for tickets with their histories as of `now`
+    pub fn build_report(&self, histories: &[TicketHistory], now: DateTime<Utc>) -> CycleTimeReport {
+        let tickets: Vec<TicketFlowMetrics> = histories.iter().map(|h| Self::ticket_metrics(h, now)).collect();
+
+        let mut by_team: HashMap<&str, Vec<&TicketFlowMetrics>> = HashMap::new();
+        for ticket in &tickets {
+            by_team.entry(ticket.team.as_deref().unwrap_or(NO_TEAM)).or_default().push(ticket);
+        }
+
+        let mut teams: Vec<TeamFlowMetrics> = by_team
+            .into_iter()
+            .map(|(team, tickets)| Self::aggregate(team, &tickets))
+            .collect();
+        teams.sort_by(|a, b| b.ticket_count.cmp(&a.ticket_count).then_with(|| a.team.cmp(&b.team)));
+
+        CycleTimeReport {
+            overall: Self::aggregate("All", &tickets.iter().collect::<Vec<_>>()),
+            tickets,
+            teams,
+            generated_at: now,
+        }
+    }
+
+    /// Flow metrics of one ticket; open tickets accrue time in their status until `now`
+    pub fn ticket_metrics(history: &TicketHistory, now: DateTime<Utc>) -> TicketFlowMetrics {
+        let ticket = &history.ticket;
+        let transitions = &history.transitions;
+        let is_done = ticket.status.category == StatusCategory::Done;
+
+        let started_at = transitions
+            .iter()
+            .find(|t| t.to_category == Some(StatusCategory::InProgress))
+            .map(|t| t.at);
+        let completed_at = if is_done {
+            transitions
+                .iter()
+                .rev()
+                .find(|t| t.to_category == Some(StatusCategory::Done))
+                .map(|t| t.at)
+        } else {
+            None
+        };
+        let reopen_count = transitions
+            .iter()
+            .filter(|t| t.from_category == Some(StatusCategory::Done) && t.to_category != Some(StatusCategory::Done))
+            .count() as u32;
+
+        // Walk the statuses from creation; the first one is where the first transition left from
+        let mut time_in_status: HashMap<String, f64> = HashMap::new();
+        let mut status = transitions
+            .first()
+            .and_then(|t| t.from.clone())
+            .unwrap_or_else(|| ticket.status.name.clone());
+        let mut since = ticket.created_at;
+        for transition in transitions {
+            *time_in_status.entry(status).or_default() += hours(transition.at - since);
+            status = transition.to.clone();
+            since = transition.at;
+        }
+        if !is_done {
+            *time_in_status.entry(status).or_default() += hours(now - since);
+        }
+
+        TicketFlowMetrics {
+            key: ticket.key.clone(),
+            summary: ticket.summary.clone(),
+            team: ticket.team.clone(),
+            assignee: ticket.assignee.as_ref().map(|u| u.name.clone()),
+            created_at: ticket.created_at,
+            started_at,
+            completed_at,
+            lead_time_hours: completed_at.map(|done| hours(done - ticket.created_at)),
+            cycle_time_hours: started_at
+                .zip(completed_at)
+                .filter(|(start, done)| start <= done)
+                .map(|(start, done)| hours(done - start)),
+            time_in_status_hours: time_in_status,
+            reopen_count,
+        }
+    }
+
+    fn aggregate(team: &str, tickets: &[&TicketFlowMetrics]) -> TeamFlowMetrics {
+        let lead_times: Vec<f64> = tickets.iter().filter_map(|t| t.lead_time_hours).collect();
+        let mut cycle_times: Vec<f64> = tickets.iter().filter_map(|t| t.cycle_time_hours).collect();
+        cycle_times.sort_by(f64::total_cmp);
+
+        let mut time_in_status: HashMap<String, f64> = HashMap::new();
+        for ticket in tickets {
+            for (status, hours) in &ticket.time_in_status_hours {
+                *time_in_status.entry(status.clone()).or_default() += hours;
+            }
+        }
+        for total in time_in_status.values_mut() {
+            *total /= tickets.len() as f64;
+        }
+
+        let reopened = tickets.iter().filter(|t| t.reopen_count > 0).count();
+
+        TeamFlowMetrics {
+            team: team.to_string(),
+            ticket_count: tickets.len(),
+            completed_count: tickets.iter().filter(|t| t.completed_at.is_some()).count(),
+            avg_lead_time_hours: average(&lead_times),
+            avg_cycle_time_hours: average(&cycle_times),
+            median_cycle_time_hours: percentile(&cycle_times, 0.5),
+            p85_cycle_time_hours: percentile(&cycle_times, 0.85),
+            time_in_status_hours: time_in_status,
+            reopen_count: tickets.iter().map(|t| t.reopen_count).sum(),
+            reopen_rate_percent: if tickets.is_empty() {
+                0.0
+            } else {
+                reopened as f64 / tickets.len() as f64 * 100.0
+            },
+        }
+    }
+}
+
+// Debug implementation
+impl<R: TicketHistoryRepository> std::fmt::Debug for CycleTimeService<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CycleTimeService")
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::traits::{StatusTransition, Ticket, TicketStatus};
+    use async_trait::async_trait;
+    use chrono::TimeZone;
+
+    struct MockHistoryRepo {
+        histories: Vec<TicketHistory>,
+    }
+
+    #[async_trait]
+    impl TicketHistoryRepository for MockHistoryRepo {
+        async fn get_status_history(&self, key: &str) -> Result<Vec<StatusTransition>, IntegrationError> {
+            self.histories
+                .iter()
+                .find(|h| h.ticket.key == key)
+                .map(|h| h.transitions.clone())
+                .ok_or_else(|| IntegrationError::NotFound(key.to_string()))
+        }
+
+        async fn search_with_history(&self, _query: &TicketSearchQuery) -> Result<Vec<TicketHistory>, IntegrationError> {
+            Ok(self.histories.clone())
+        }
+    }
+
+    fn at(day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 3, day, hour, 0, 0).unwrap()
+    }
+
+    fn category(status: &str) -> StatusCategory {
+        match status {
+            "To Do" => StatusCategory::Todo,
+            "Done" => StatusCategory::Done,
+            _ => StatusCategory::InProgress,
+        }
+    }
+
+    /// Ticket created on Mar 1 09:00 moving through `(status, day, hour)` steps
+    fn history(key: &str, team: Option<&str>, steps: &[(&str, u32, u32)]) -> TicketHistory {
+        let mut previous = "To Do";
+        let transitions = steps
+            .iter()
+            .map(|&(to, day, hour)| {
+                let transition = StatusTransition {
+                    at: at(day, hour),
+                    author: None,
+                    from: Some(previous.to_string()),
+                    to: to.to_string(),
+                    from_category: Some(category(previous)),
+                    to_category: Some(category(to)),
+                };
+                previous = to;
+                transition
+            })
+            .collect();
+
+        TicketHistory {
+            ticket: Ticket {
+                id: key.to_string(),
+                key: key.to_string(),
+                summary: format!("Ticket {}", key),
+                description: None,
+                description_markdown: None,
+                status: TicketStatus {
+                    name: previous.to_string(),
+                    category: category(previous),
+                },
+                assignee: None,
+                reporter: None,
+                priority: None,
+                sprint: None,
+                story_points: None,
+                epic: None,
+                team: team.map(str::to_string),
+                labels: vec![],
+                updated_at: at(1, 9),
+                created_at: at(1, 9),
+            },
+            transitions,
+        }
+    }
+
+    fn service(histories: Vec<TicketHistory>) -> CycleTimeService<MockHistoryRepo> {
+        CycleTimeService::new(Arc::new(MockHistoryRepo { histories }), CycleTimeConfig::new())
+    }
+
+    #[test]
+    fn test_ticket_metrics() {
+        let history = history("PAY-1", Some("Payments"), &[("In Progress", 2, 9), ("In Review", 3, 9), ("Done", 3, 21)]);
+
+        let metrics = CycleTimeService::<MockHistoryRepo>::ticket_metrics(&history, at(10, 9));
+
+        assert_eq!(metrics.started_at, Some(at(2, 9)));
+        assert_eq!(metrics.completed_at, Some(at(3, 21)));
+        assert_eq!(metrics.lead_time_hours, Some(60.0));
+        assert_eq!(metrics.cycle_time_hours, Some(36.0));
+        assert_eq!(metrics.time_in_status_hours["To Do"], 24.0);
+        assert_eq!(metrics.time_in_status_hours["In Review"], 12.0);
+        assert!(!metrics.time_in_status_hours.contains_key("Done"));
+        assert_eq!(metrics.reopen_count, 0);
+    }
+
+    #[test]
+    fn test_reopened_ticket() {
+        let history = history(
+            "PAY-2",
+            None,
+            &[("In Progress", 2, 9), ("Done", 2, 17), ("In Progress", 4, 9), ("Done", 5, 9)],
+        );
+
+        let metrics = CycleTimeService::<MockHistoryRepo>::ticket_metrics(&history, at(10, 9));
+
+        assert_eq!(metrics.reopen_count, 1);
+        // Completion is the final move to done, time in done between is counted
+        assert_eq!(metrics.completed_at, Some(at(5, 9)));
+        assert_eq!(metrics.cycle_time_hours, Some(72.0));
+        assert_eq!(metrics.time_in_status_hours["Done"], 40.0);
+    }
+
+    #[test]
+    fn test_open_ticket_accrues_current_status() {
+        let history = history("PAY-3", None, &[("In Progress", 2, 9)]);
+
+        let metrics = CycleTimeService::<MockHistoryRepo>::ticket_metrics(&history, at(4, 9));
+
+        assert_eq!(metrics.completed_at, None);
+        assert_eq!(metrics.cycle_time_hours, None);
+        assert_eq!(metrics.time_in_status_hours["In Progress"], 48.0);
+    }
+
+    #[test]
+    fn test_team_aggregation() {
+        let service = service(vec![]);
+        let histories = vec![
+            history("PAY-1", Some("Payments"), &[("In Progress", 2, 9), ("Done", 3, 9)]),
+            history("PAY-2", Some("Payments"), &[("In Progress", 2, 9), ("Done", 5, 9)]),
+            history("PAY-3", Some("Payments"), &[("In Progress", 2, 9), ("Done", 2, 21), ("In Progress", 3, 9), ("Done", 4, 9)]),
+            history("OPS-1", None, &[("In Progress", 2, 9)]),
+        ];
+
+        let report = service.build_report(&histories, at(10, 9));
+
+        let payments = &report.teams[0];
+        assert_eq!(payments.team, "Payments");
+        assert_eq!((payments.ticket_count, payments.completed_count), (3, 3));
+        assert_eq!(payments.median_cycle_time_hours, Some(48.0));
+        assert_eq!(payments.p85_cycle_time_hours, Some(72.0));
+        assert_eq!(payments.avg_lead_time_hours, Some(72.0));
+        assert_eq!(payments.reopen_count, 1);
+        assert!((payments.reopen_rate_percent - 33.3).abs() < 0.1);
+
+        assert_eq!(report.teams[1].team, NO_TEAM);
+        assert_eq!(report.overall.ticket_count, 4);
+    }
+
+    #[test]
+    fn test_percentile() {
+        assert_eq!(percentile(&[], 0.5), None);
+        assert_eq!(percentile(&[4.0], 0.85), Some(4.0));
+        assert_eq!(percentile(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0], 0.85), Some(9.0));
+    }
+
+    #[tokio::test]
+    async fn test_completed_report_keeps_period() {
+        let service = service(vec![
+            history("PAY-1", None, &[("In Progress", 2, 9), ("Done", 3, 9)]),
+            history("PAY-2", None, &[("In Progress", 2, 9), ("Done", 12, 9)]),
+            history("PAY-3", None, &[("In Progress", 2, 9)]),
+        ]);
+
+        let report = service.get_completed_report(Some("PAY"), at(1, 0), at(10, 0)).await.unwrap();
+
+        let keys: Vec<&str> = report.tickets.iter().map(|t| t.key.as_str()).collect();
+        assert_eq!(keys, vec!["PAY-1"]);
+    }
+}
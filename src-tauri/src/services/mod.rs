@@ -7,11 +7,17 @@ mod search_service;
 mod pr_aggregator;
 mod incident_monitor;
 mod background_poller;
+mod sprint_health;
+mod cycle_time;
 
 pub use cache_service::CacheService;
 pub use cache_service::CacheError;
 pub use cache_service::CacheConfig;
 pub use search_service::{SearchService, SearchResult, SearchResultType, SearchResultMetadata};
-pub use pr_aggregator::{PrAggregator, PrSummary};
+pub use pr_aggregator::{PrAggregator, PrGrouping, PrSize, PrSummary};
 pub use incident_monitor::{IncidentMonitor, IncidentSummary};
 pub use background_poller::{BackgroundPoller, PollerConfig, PollerState, PollingStats};
+pub use sprint_health::{
+    SprintDataPoint, SprintForecast, SprintHealthConfig, SprintHealthReport, SprintHealthService, SprintTicket,
+};
+pub use cycle_time::{CycleTimeConfig, CycleTimeReport, CycleTimeService, TeamFlowMetrics, TicketFlowMetrics};
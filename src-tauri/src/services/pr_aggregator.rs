@@ -1,7 +1,7 @@
 //! PR Aggregator Service
 //!
 //! Aggregates pull requests from multiple repositories,
-//! providing stale detection and smart grouping.
+//! providing stale detection, size buckets and smart grouping.
 
 use async_trait::async_trait;
 use chrono::{DateTime, Duration, Utc};
@@ -9,7 +9,10 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use crate::integrations::traits::{IntegrationError, PrFilter, PrState, PullRequest, PullRequestRepository};
+use crate::integrations::traits::{
+    ChecksStatus, IntegrationError, PrFetchResult, PrFilter, PrState, PullRequest, PullRequestRepository,
+    RepositoryError,
+};
 use crate::services::CacheService;
 use crate::system::TrayState;
 
@@ -18,16 +21,28 @@ use crate::system::TrayState;
 pub struct PrSummary {
     /// Total open PRs
     pub total_open: usize,
-    /// PRs pending review (assigned to user)
+    /// PRs waiting on the user's review
     pub pending_review: usize,
+    /// PRs blocked by a reviewer requesting changes
+    #[serde(default)]
+    pub changes_requested: usize,
     /// Stale PRs (exceeding threshold)
     pub stale_count: usize,
+    /// PRs whose CI checks are failing
+    #[serde(default)]
+    pub failing_checks: usize,
+    /// PRs of size L or XL, which are slow to get reviewed
+    #[serde(default)]
+    pub large_count: usize,
     /// PRs by repository
     pub by_repository: HashMap<String, usize>,
     /// Oldest stale PR age in hours
     pub oldest_stale_hours: Option<i64>,
     /// Tray state based on PR status
     pub tray_state: TrayState,
+    /// Repositories that could not be fetched
+    #[serde(default)]
+    pub failed_repositories: Vec<RepositoryError>,
 }
 
 impl PrSummary {
@@ -45,6 +60,11 @@ impl PrSummary {
             TrayState::Neutral
         }
     }
+
+    /// Number of repositories missing from this summary
+    pub fn failed_count(&self) -> usize {
+        self.failed_repositories.len()
+    }
 }
 
 /// PR grouping options
@@ -53,6 +73,62 @@ pub enum PrGrouping {
     ByRepository,
     ByAuthor,
     ByAge,
+    BySize,
+}
+
+/// Label of the size group for PRs whose diff is unknown
+pub const UNKNOWN_SIZE: &str = "Unknown size";
+
+/// PR size bucket by lines changed (added plus removed)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum PrSize {
+    /// Fewer than 10 lines
+    #[serde(rename = "XS")]
+    ExtraSmall,
+    /// 10 to 99 lines
+    #[serde(rename = "S")]
+    Small,
+    /// 100 to 399 lines
+    #[serde(rename = "M")]
+    Medium,
+    /// 400 to 999 lines
+    #[serde(rename = "L")]
+    Large,
+    /// 1000 lines or more
+    #[serde(rename = "XL")]
+    ExtraLarge,
+}
+
+impl PrSize {
+    pub fn from_lines(lines: u64) -> Self {
+        match lines {
+            0..=9 => PrSize::ExtraSmall,
+            10..=99 => PrSize::Small,
+            100..=399 => PrSize::Medium,
+            400..=999 => PrSize::Large,
+            _ => PrSize::ExtraLarge,
+        }
+    }
+
+    /// Size of a PR, `None` when the provider did not report its diff
+    pub fn of(pr: &PullRequest) -> Option<Self> {
+        pr.diff_stats.as_ref().map(|d| Self::from_lines(d.lines_changed()))
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PrSize::ExtraSmall => "XS",
+            PrSize::Small => "S",
+            PrSize::Medium => "M",
+            PrSize::Large => "L",
+            PrSize::ExtraLarge => "XL",
+        }
+    }
+
+    /// Whether the PR is large enough to stall reviews
+    pub fn is_large(&self) -> bool {
+        *self >= PrSize::Large
+    }
 }
 
 /// Grouped PR result
@@ -135,8 +211,8 @@ impl<R: PullRequestRepository> PrAggregator<R> {
             }
         }
 
-        let prs = self.fetch_all_prs().await?;
-        let summary = self.compute_summary(&prs);
+        let fetched = self.fetch_all_prs_with_failures().await?;
+        let summary = self.compute_summary(&fetched.prs, &fetched.errors);
 
         // Cache result
         if let Some(ref cache) = self.cache {
@@ -154,17 +230,32 @@ impl<R: PullRequestRepository> PrAggregator<R> {
         self.repo.get_open_prs(&filter).await
     }
 
-    /// Fetch PRs pending review by the user
+    /// Fetch all open PRs, keeping track of repositories that failed
+    pub async fn fetch_all_prs_with_failures(&self) -> Result<PrFetchResult, IntegrationError> {
+        let filter = PrFilter::new()
+            .with_repositories(self.config.repositories.clone());
+
+        self.repo.fetch_open_prs(&filter).await
+    }
+
+    /// Fetch PRs the user still has to review
     pub async fn get_pending_review(&self) -> Result<Vec<PullRequest>, IntegrationError> {
         if let Some(ref user_id) = self.user_id {
             let filter = PrFilter::new()
                 .with_repositories(self.config.repositories.clone());
-            self.repo.find_by_reviewer(user_id, &filter).await
+            let prs = self.repo.find_by_reviewer(user_id, &filter).await?;
+            Ok(prs.into_iter().filter(|pr| pr.is_waiting_on(user_id)).collect())
         } else {
             Ok(Vec::new())
         }
     }
 
+    /// Get PRs blocked by requested changes
+    pub async fn get_changes_requested(&self) -> Result<Vec<PullRequest>, IntegrationError> {
+        let prs = self.fetch_all_prs().await?;
+        Ok(prs.into_iter().filter(|pr| pr.has_changes_requested()).collect())
+    }
+
     /// Get stale PRs
     pub async fn get_stale_prs(&self) -> Result<Vec<PullRequest>, IntegrationError> {
         let prs = self.fetch_all_prs().await?;
@@ -177,6 +268,19 @@ impl<R: PullRequestRepository> PrAggregator<R> {
             .collect())
     }
 
+    /// Get L and XL PRs, largest first
+    pub async fn get_large_prs(&self) -> Result<Vec<PullRequest>, IntegrationError> {
+        let mut prs: Vec<PullRequest> = self
+            .fetch_all_prs()
+            .await?
+            .into_iter()
+            .filter(|pr| PrSize::of(pr).is_some_and(|size| size.is_large()))
+            .collect();
+
+        prs.sort_by_key(|pr| std::cmp::Reverse(pr.diff_stats.as_ref().map_or(0, |d| d.lines_changed())));
+        Ok(prs)
+    }
+
     /// Group PRs by a specific criteria
     pub fn group_prs(&self, prs: &[PullRequest], grouping: PrGrouping) -> Vec<GroupedPrs> {
         let mut groups: HashMap<String, Vec<PullRequest>> = HashMap::new();
@@ -186,6 +290,9 @@ impl<R: PullRequestRepository> PrAggregator<R> {
                 PrGrouping::ByRepository => pr.repository.clone(),
                 PrGrouping::ByAuthor => pr.author.name.clone(),
                 PrGrouping::ByAge => self.age_bucket(pr),
+                PrGrouping::BySize => PrSize::of(pr)
+                    .map_or(UNKNOWN_SIZE, |size| size.as_str())
+                    .to_string(),
             };
             groups.entry(key).or_default().push(pr.clone());
         }
@@ -208,8 +315,12 @@ impl<R: PullRequestRepository> PrAggregator<R> {
             })
             .collect();
 
-        // Sort by stale count descending
-        result.sort_by(|a, b| b.stale_count.cmp(&a.stale_count));
+        // Sort by stale count descending, sizes from the largest down
+        if grouping == PrGrouping::BySize {
+            result.sort_by_key(|group| std::cmp::Reverse(group.prs.first().and_then(PrSize::of)));
+        } else {
+            result.sort_by(|a, b| b.stale_count.cmp(&a.stale_count));
+        }
         result
     }
 
@@ -227,7 +338,7 @@ impl<R: PullRequestRepository> PrAggregator<R> {
         }
     }
 
-    fn compute_summary(&self, prs: &[PullRequest]) -> PrSummary {
+    fn compute_summary(&self, prs: &[PullRequest], failures: &[RepositoryError]) -> PrSummary {
         let threshold = Duration::hours(self.config.stale_threshold_hours);
         let now = Utc::now();
 
@@ -237,13 +348,21 @@ impl<R: PullRequestRepository> PrAggregator<R> {
             .collect();
 
         let pending_review = if let Some(ref user_id) = self.user_id {
-            prs.iter()
-                .filter(|pr| pr.reviewers.iter().any(|r| r.user.id == *user_id || r.user.name == *user_id))
-                .count()
+            prs.iter().filter(|pr| pr.is_waiting_on(user_id)).count()
         } else {
             0
         };
 
+        let changes_requested = prs.iter().filter(|pr| pr.has_changes_requested()).count();
+        let failing_checks = prs
+            .iter()
+            .filter(|pr| pr.checks_status == ChecksStatus::Fail)
+            .count();
+        let large_count = prs
+            .iter()
+            .filter(|pr| PrSize::of(pr).is_some_and(|size| size.is_large()))
+            .count();
+
         let mut by_repository: HashMap<String, usize> = HashMap::new();
         for pr in prs {
             *by_repository.entry(pr.repository.clone()).or_default() += 1;
@@ -254,13 +373,24 @@ impl<R: PullRequestRepository> PrAggregator<R> {
             .map(|pr| now.signed_duration_since(pr.updated_at).num_hours())
             .max();
 
+        let mut tray_state = PrSummary::calculate_tray_state(stale_prs.len(), pending_review);
+        // Broken builds and a partial view must not look healthy
+        let needs_attention = failing_checks > 0 || !failures.is_empty();
+        if needs_attention && tray_state.priority() < TrayState::Amber.priority() {
+            tray_state = TrayState::Amber;
+        }
+
         PrSummary {
             total_open: prs.len(),
             pending_review,
+            changes_requested,
             stale_count: stale_prs.len(),
+            failing_checks,
+            large_count,
             by_repository,
             oldest_stale_hours,
-            tray_state: PrSummary::calculate_tray_state(stale_prs.len(), pending_review),
+            tray_state,
+            failed_repositories: failures.to_vec(),
         }
     }
 }
@@ -278,16 +408,22 @@ impl<R: PullRequestRepository> std::fmt::Debug for PrAggregator<R> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::integrations::traits::{ChecksStatus, Reviewer, User};
+    use crate::integrations::traits::{DiffStats, ReviewState, Reviewer, User};
     use std::sync::Mutex;
 
     struct MockPrRepo {
         prs: Mutex<Vec<PullRequest>>,
+        failures: Vec<RepositoryError>,
     }
 
     impl MockPrRepo {
         fn new(prs: Vec<PullRequest>) -> Self {
-            Self { prs: Mutex::new(prs) }
+            Self { prs: Mutex::new(prs), failures: Vec::new() }
+        }
+
+        fn with_failures(mut self, failures: Vec<RepositoryError>) -> Self {
+            self.failures = failures;
+            self
         }
     }
 
@@ -305,7 +441,7 @@ mod tests {
             let prs = self.prs.lock().unwrap();
             Ok(prs
                 .iter()
-                .filter(|pr| pr.reviewers.iter().any(|r| r.user.id == user_id || r.user.name == user_id))
+                .filter(|pr| pr.reviewers.iter().any(|r| r.is_user(user_id)))
                 .cloned()
                 .collect())
         }
@@ -322,6 +458,13 @@ mod tests {
             };
             Ok(result.into_iter().take(filter.limit).collect())
         }
+
+        async fn fetch_open_prs(&self, filter: &PrFilter) -> Result<PrFetchResult, IntegrationError> {
+            Ok(PrFetchResult {
+                prs: self.get_open_prs(filter).await?,
+                errors: self.failures.clone(),
+            })
+        }
     }
 
     fn create_test_pr(id: &str, repo: &str, age_hours: i64) -> PullRequest {
@@ -345,20 +488,38 @@ mod tests {
             updated_at: Utc::now() - Duration::hours(age_hours),
             created_at: Utc::now() - Duration::hours(age_hours + 10),
             url: format!("https://example.com/pr/{}", id),
+            labels: vec![],
+            diff_stats: None,
+            connection: None,
         }
     }
 
+    fn create_sized_pr(id: &str, additions: u64, deletions: u64) -> PullRequest {
+        let mut pr = create_test_pr(id, "repo1", 10);
+        pr.diff_stats = Some(DiffStats {
+            additions,
+            deletions,
+            changed_files: 1,
+            files: vec![],
+        });
+        pr
+    }
+
     fn create_pr_with_reviewer(id: &str, reviewer_id: &str) -> PullRequest {
+        create_pr_with_review(id, reviewer_id, ReviewState::Pending)
+    }
+
+    fn create_pr_with_review(id: &str, reviewer_id: &str, state: ReviewState) -> PullRequest {
         let mut pr = create_test_pr(id, "repo1", 12);
-        pr.reviewers = vec![Reviewer {
-            user: User {
+        pr.reviewers = vec![Reviewer::new(
+            User {
                 id: reviewer_id.to_string(),
                 name: reviewer_id.to_string(),
                 email: None,
                 avatar_url: None,
             },
-            approved: false,
-        }];
+            state,
+        )];
         pr
     }
 
@@ -424,6 +585,29 @@ mod tests {
         assert_eq!(pending[0].id, "1");
     }
 
+    #[tokio::test]
+    async fn test_pending_review_skips_submitted_reviews() {
+        let prs = vec![
+            create_pr_with_review("1", "user1", ReviewState::Pending),
+            create_pr_with_review("2", "user1", ReviewState::Approved),
+            create_pr_with_review("3", "user1", ReviewState::ChangesRequested),
+            create_pr_with_review("4", "user2", ReviewState::ChangesRequested),
+        ];
+        let repo = Arc::new(MockPrRepo::new(prs));
+        let aggregator = PrAggregator::new(repo, PrAggregatorConfig::new()).with_user_id("user1");
+
+        let pending = aggregator.get_pending_review().await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].id, "1");
+
+        let blocked = aggregator.get_changes_requested().await.unwrap();
+        assert_eq!(blocked.len(), 2);
+
+        let summary = aggregator.get_summary().await.unwrap();
+        assert_eq!(summary.pending_review, 1);
+        assert_eq!(summary.changes_requested, 2);
+    }
+
     #[tokio::test]
     async fn test_get_summary() {
         let prs = vec![
@@ -444,6 +628,49 @@ mod tests {
         assert_eq!(summary.tray_state, TrayState::Amber);
     }
 
+    #[tokio::test]
+    async fn test_get_summary_reports_failed_repositories() {
+        let prs = vec![create_test_pr("1", "repo1", 10)];
+        let failures = vec![
+            RepositoryError::new("repo2", &IntegrationError::Auth("revoked".to_string())),
+            RepositoryError::new("repo3", &IntegrationError::RateLimit { reset_at: None }),
+        ];
+        let repo = Arc::new(MockPrRepo::new(prs).with_failures(failures));
+        let aggregator = PrAggregator::new(repo, PrAggregatorConfig::new());
+
+        let summary = aggregator.get_summary().await.unwrap();
+
+        assert_eq!(summary.total_open, 1);
+        assert_eq!(summary.failed_count(), 2);
+        assert_eq!(summary.failed_repositories[0].repository, "repo2");
+        // Would be green without the failures
+        assert_eq!(summary.tray_state, TrayState::Amber);
+    }
+
+    #[tokio::test]
+    async fn test_get_summary_counts_failing_checks() {
+        let mut failing = create_test_pr("1", "repo1", 10);
+        failing.checks_status = ChecksStatus::Fail;
+        let mut running = create_test_pr("2", "repo1", 10);
+        running.checks_status = ChecksStatus::Running;
+        let prs = vec![failing, running, create_test_pr("3", "repo2", 10)];
+        let repo = Arc::new(MockPrRepo::new(prs));
+        let aggregator = PrAggregator::new(repo, PrAggregatorConfig::new());
+
+        let summary = aggregator.get_summary().await.unwrap();
+
+        assert_eq!(summary.failing_checks, 1);
+        assert_eq!(summary.stale_count, 0);
+        assert_eq!(summary.tray_state, TrayState::Amber);
+    }
+
+    #[test]
+    fn test_pr_summary_deserializes_without_failures() {
+        let json = r#"{"total_open":1,"pending_review":0,"stale_count":0,"by_repository":{},"oldest_stale_hours":null,"tray_state":"Green"}"#;
+        let summary: PrSummary = serde_json::from_str(json).unwrap();
+        assert_eq!(summary.failed_count(), 0);
+    }
+
     #[test]
     fn test_group_prs_by_repository() {
         let prs = vec![
@@ -496,4 +723,54 @@ mod tests {
         assert_eq!(groups[0].label, "repo2");
         assert_eq!(groups[0].stale_count, 2);
     }
+
+    #[test]
+    fn test_pr_size_buckets() {
+        assert_eq!(PrSize::from_lines(0), PrSize::ExtraSmall);
+        assert_eq!(PrSize::from_lines(10), PrSize::Small);
+        assert_eq!(PrSize::from_lines(399), PrSize::Medium);
+        assert_eq!(PrSize::from_lines(400), PrSize::Large);
+        assert_eq!(PrSize::from_lines(5000), PrSize::ExtraLarge);
+        assert!(PrSize::Large.is_large());
+        assert!(!PrSize::Medium.is_large());
+
+        // Additions and deletions both count
+        assert_eq!(PrSize::of(&create_sized_pr("1", 300, 150)), Some(PrSize::Large));
+        assert_eq!(PrSize::of(&create_test_pr("2", "repo1", 10)), None);
+        assert_eq!(serde_json::to_string(&PrSize::ExtraLarge).unwrap(), "\"XL\"");
+    }
+
+    #[test]
+    fn test_group_prs_by_size() {
+        let prs = vec![
+            create_sized_pr("1", 5, 0),
+            create_sized_pr("2", 1200, 40),
+            create_test_pr("3", "repo1", 10),
+            create_sized_pr("4", 2, 1),
+        ];
+        let aggregator = PrAggregator::new(Arc::new(MockPrRepo::new(vec![])), PrAggregatorConfig::new());
+
+        let groups = aggregator.group_prs(&prs, PrGrouping::BySize);
+
+        let labels: Vec<&str> = groups.iter().map(|g| g.label.as_str()).collect();
+        assert_eq!(labels, vec!["XL", "XS", UNKNOWN_SIZE]);
+        assert_eq!(groups[1].prs.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_large_prs_called_out() {
+        let prs = vec![
+            create_sized_pr("1", 500, 0),
+            create_sized_pr("2", 20, 5),
+            create_sized_pr("3", 900, 900),
+            create_test_pr("4", "repo1", 10),
+        ];
+        let aggregator = PrAggregator::new(Arc::new(MockPrRepo::new(prs)), PrAggregatorConfig::new());
+
+        let large: Vec<String> = aggregator.get_large_prs().await.unwrap().into_iter().map(|pr| pr.id).collect();
+        assert_eq!(large, vec!["3", "1"]);
+
+        let summary = aggregator.get_summary().await.unwrap();
+        assert_eq!(summary.large_count, 2);
+    }
 }
@@ -9,7 +9,7 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 use crate::integrations::traits::{
-    IntegrationError, PrFilter, PullRequest, Ticket, TicketRepository, TicketSearchQuery,
+    Comment, IntegrationError, PrFilter, PullRequest, Ticket, TicketRepository, TicketSearchQuery,
 };
 use crate::services::CacheService;
 
@@ -160,10 +160,24 @@ impl SearchQuery {
         self
     }
 
-    /// Check if query looks like a ticket ID (e.g., PROJ-123)
+    /// Check if query looks like a ticket ID (e.g., PROJ-123, #123 or owner/repo#123)
     pub fn is_ticket_id(&self) -> bool {
-        let pattern = regex::Regex::new(r"^[A-Z]+-\d+$").unwrap();
-        pattern.is_match(&self.text.to_uppercase())
+        self.ticket_id().is_some()
+    }
+
+    /// The ticket ID the query refers to, with Jira keys upper-cased
+    pub fn ticket_id(&self) -> Option<String> {
+        let jira_key = regex::Regex::new(r"^[A-Z]+-\d+$").unwrap();
+        let issue_ref = regex::Regex::new(r"^([\w.-]+(/[\w.-]+)+)?#\d+$").unwrap();
+
+        let upper = self.text.to_uppercase();
+        if jira_key.is_match(&upper) {
+            Some(upper)
+        } else if issue_ref.is_match(&self.text) {
+            Some(self.text.clone())
+        } else {
+            None
+        }
     }
 
     /// Check if query looks like a PR number
@@ -234,8 +248,8 @@ impl<T: TicketRepository> SearchService<T> {
 
     async fn search_tickets(&self, query: &SearchQuery) -> Result<Vec<SearchResult>, IntegrationError> {
         // If it looks like a ticket ID, try direct lookup first
-        if query.is_ticket_id() {
-            match self.ticket_repo.find_by_id(&query.text.to_uppercase()).await {
+        if let Some(id) = query.ticket_id() {
+            match self.ticket_repo.find_by_id(&id).await {
                 Ok(ticket) => {
                     let mut result = SearchResult::from_ticket(&ticket);
                     result.relevance_score = 2.0; // Boost exact match
@@ -258,6 +272,25 @@ impl<T: TicketRepository> SearchService<T> {
         Ok(tickets.iter().map(SearchResult::from_ticket).collect())
     }
 
+    /// Latest comments of a ticket, for the detail panel
+    pub async fn get_comments(&self, key: &str, limit: usize) -> Result<Vec<Comment>, IntegrationError> {
+        let cache_key = format!("comments:{}:{}", key, limit);
+        if let Some(ref cache) = self.cache {
+            if let Ok(cached) = cache.get::<Vec<Comment>>(&cache_key) {
+                log::debug!("Comments cache hit for: {}", key);
+                return Ok(cached);
+            }
+        }
+
+        let comments = self.ticket_repo.get_comments(key, limit).await?;
+
+        if let Some(ref cache) = self.cache {
+            let _ = cache.set(&cache_key, &comments, self.cache_ttl);
+        }
+
+        Ok(comments)
+    }
+
     /// Get recent search suggestions
     pub fn get_suggestions(&self, _prefix: &str) -> Vec<String> {
         // In a real implementation, this would track recent searches
@@ -283,14 +316,30 @@ mod tests {
     // Mock ticket repository for testing
     struct MockTicketRepo {
         tickets: Mutex<Vec<Ticket>>,
+        comments: Mutex<Vec<(String, Comment)>>,
     }
 
     impl MockTicketRepo {
         fn new(tickets: Vec<Ticket>) -> Self {
             Self {
                 tickets: Mutex::new(tickets),
+                comments: Mutex::new(vec![]),
             }
         }
+
+        fn add_comment(&self, key: &str, body: &str) {
+            self.comments.lock().unwrap().push((
+                key.to_string(),
+                Comment {
+                    id: body.to_string(),
+                    author: None,
+                    body: body.to_string(),
+                    body_markdown: body.to_string(),
+                    created_at: Utc::now(),
+                    updated_at: Utc::now(),
+                },
+            ));
+        }
     }
 
     #[async_trait]
@@ -304,6 +353,17 @@ mod tests {
                 .ok_or_else(|| IntegrationError::NotFound(format!("Ticket {} not found", id)))
         }
 
+        async fn get_comments(&self, key: &str, limit: usize) -> Result<Vec<Comment>, IntegrationError> {
+            let comments = self.comments.lock().unwrap();
+            Ok(comments
+                .iter()
+                .rev()
+                .filter(|(k, _)| k == key)
+                .take(limit)
+                .map(|(_, c)| c.clone())
+                .collect())
+        }
+
         async fn search(&self, query: &TicketSearchQuery) -> Result<Vec<Ticket>, IntegrationError> {
             let tickets = self.tickets.lock().unwrap();
             let text = query.text.as_deref().unwrap_or("").to_lowercase();
@@ -328,6 +388,7 @@ mod tests {
             key: key.to_string(),
             summary: summary.to_string(),
             description: None,
+            description_markdown: None,
             status: TicketStatus {
                 name: "Open".to_string(),
                 category: StatusCategory::Todo,
@@ -336,6 +397,9 @@ mod tests {
             reporter: None,
             priority: None,
             sprint: None,
+            story_points: None,
+            epic: None,
+            team: None,
             labels: vec![],
             updated_at: Utc::now(),
             created_at: Utc::now(),
@@ -360,6 +424,18 @@ mod tests {
         assert!(!SearchQuery::new("123").is_ticket_id());
         assert!(!SearchQuery::new("PROJ").is_ticket_id());
         assert!(!SearchQuery::new("-123").is_ticket_id());
+        assert!(SearchQuery::new("#123").is_ticket_id());
+        assert!(SearchQuery::new("acme/api#123").is_ticket_id());
+        assert!(SearchQuery::new("group/sub.group/my-project#7").is_ticket_id());
+        assert!(!SearchQuery::new("api#123").is_ticket_id());
+        assert!(!SearchQuery::new("acme/api#").is_ticket_id());
+    }
+
+    #[test]
+    fn test_search_query_ticket_id() {
+        assert_eq!(SearchQuery::new("proj-123").ticket_id().as_deref(), Some("PROJ-123"));
+        assert_eq!(SearchQuery::new("Acme/API#12").ticket_id().as_deref(), Some("Acme/API#12"));
+        assert_eq!(SearchQuery::new("refund").ticket_id(), None);
     }
 
     #[test]
@@ -436,6 +512,21 @@ mod tests {
         assert!(results[0].relevance_score > 1.0); // ID match boost
     }
 
+    #[tokio::test]
+    async fn test_search_service_find_by_issue_reference() {
+        let tickets = vec![
+            create_test_ticket("acme/api#42", "Refunds fail"),
+            create_test_ticket("PROJ-42", "Other"),
+        ];
+        let repo = Arc::new(MockTicketRepo::new(tickets));
+        let service = SearchService::new(repo);
+
+        let results = service.search(&SearchQuery::new("acme/api#42")).await.unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "acme/api#42");
+    }
+
     #[tokio::test]
     async fn test_search_service_text_search() {
         let tickets = vec![
@@ -486,4 +577,23 @@ mod tests {
 
         assert_eq!(results.len(), 5);
     }
+
+    #[tokio::test]
+    async fn test_get_comments_is_cached() {
+        let repo = Arc::new(MockTicketRepo::new(vec![]));
+        repo.add_comment("T-1", "first");
+        repo.add_comment("T-1", "second");
+        repo.add_comment("T-2", "other");
+
+        let cache = Arc::new(CacheService::new_in_memory().unwrap());
+        let service = SearchService::new(repo.clone()).with_cache(cache);
+
+        let comments = service.get_comments("T-1", 1).await.unwrap();
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].body, "second");
+
+        repo.add_comment("T-1", "third");
+        let cached = service.get_comments("T-1", 1).await.unwrap();
+        assert_eq!(cached[0].body, "second");
+    }
 }
@@ -0,0 +1,496 @@
+//! Sprint Health Service
+//!
+//! Builds a health report for the active sprint of a board: burndown and
+//! burnup series, scope creep, carry-over, blocked work and a completion
+//! forecast based on the pace so far.
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::integrations::traits::{
+    IntegrationError, Sprint, SprintIssue, SprintRepository, StatusCategory, Ticket,
+};
+use crate::services::CacheService;
+
+/// One day of the burndown / burnup chart
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SprintDataPoint {
+    pub date: NaiveDate,
+    /// Total scope at the end of the day, including tickets added so far
+    pub scope: f64,
+    /// Work done by the end of the day
+    pub completed: f64,
+    /// Work left at the end of the day
+    pub remaining: f64,
+    /// Remaining work on a straight line from the committed scope to zero
+    pub ideal_remaining: Option<f64>,
+}
+
+/// When the sprint is expected to be done at the pace so far
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SprintForecast {
+    /// Points (or tickets) completed per day
+    pub daily_rate: f64,
+    pub projected_completion: DateTime<Utc>,
+    /// Whether the projection lands before the sprint end
+    pub on_track: bool,
+}
+
+/// Ticket listed in a report section
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SprintTicket {
+    pub key: String,
+    pub summary: String,
+    pub status: String,
+    pub assignee: Option<String>,
+    pub story_points: Option<f64>,
+}
+
+impl From<&Ticket> for SprintTicket {
+    fn from(ticket: &Ticket) -> Self {
+        Self {
+            key: ticket.key.clone(),
+            summary: ticket.summary.clone(),
+            status: ticket.status.name.clone(),
+            assignee: ticket.assignee.as_ref().map(|u| u.name.clone()),
+            story_points: ticket.story_points,
+        }
+    }
+}
+
+/// Health of a sprint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SprintHealthReport {
+    pub sprint: Sprint,
+    pub days_left: Option<i64>,
+    /// Whether work is measured in story points, otherwise in tickets
+    pub uses_story_points: bool,
+    pub total_scope: f64,
+    pub completed: f64,
+    /// Scope at sprint start
+    pub committed_scope: f64,
+    /// Scope added after the sprint started
+    pub added_scope: f64,
+    /// Added scope relative to the committed scope, in percent
+    pub scope_creep_percent: f64,
+    pub added: Vec<SprintTicket>,
+    /// Tickets left unfinished in the previous sprint
+    pub carried_over: Vec<SprintTicket>,
+    pub blocked: Vec<SprintTicket>,
+    pub unassigned_in_progress: Vec<SprintTicket>,
+    pub series: Vec<SprintDataPoint>,
+    pub forecast: Option<SprintForecast>,
+    pub generated_at: DateTime<Utc>,
+}
+
+impl SprintHealthReport {
+    /// One-line status for the HUD, e.g. "Sprint 42: 6 days left, 18/30 points done"
+    pub fn headline(&self) -> String {
+        let unit = if self.uses_story_points { "points" } else { "tickets" };
+        let progress = format!("{}/{} {} done", self.completed, self.total_scope, unit);
+
+        match self.days_left {
+            Some(1) => format!("{}: 1 day left, {}", self.sprint.name, progress),
+            Some(days) => format!("{}: {} days left, {}", self.sprint.name, days, progress),
+            None => format!("{}: {}", self.sprint.name, progress),
+        }
+    }
+}
+
+/// Sprint health configuration
+#[derive(Debug, Clone)]
+pub struct SprintHealthConfig {
+    /// Labels marking a ticket as blocked, besides a status named "Blocked"
+    pub blocked_labels: Vec<String>,
+    pub refresh_interval: Duration,
+}
+
+impl Default for SprintHealthConfig {
+    fn default() -> Self {
+        Self {
+            blocked_labels: vec!["blocked".to_string(), "impediment".to_string()],
+            refresh_interval: Duration::minutes(5),
+        }
+    }
+}
+
+impl SprintHealthConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_blocked_labels(mut self, labels: Vec<String>) -> Self {
+        self.blocked_labels = labels;
+        self
+    }
+}
+
+/// Sprint Health Service
+pub struct SprintHealthService<S: SprintRepository> {
+    repo: Arc<S>,
+    config: SprintHealthConfig,
+    cache: Option<Arc<CacheService>>,
+}
+
+impl<S: SprintRepository> SprintHealthService<S> {
+    pub fn new(repo: Arc<S>, config: SprintHealthConfig) -> Self {
+        Self {
+            repo,
+            config,
+            cache: None,
+        }
+    }
+
+    pub fn with_cache(mut self, cache: Arc<CacheService>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Report on the active sprint of a board, `None` if no sprint is running
+    pub async fn get_report(&self, board_id: u64) -> Result<Option<SprintHealthReport>, IntegrationError> {
+        let cache_key = format!("sprint_health:{}", board_id);
+        if let Some(ref cache) = self.cache {
+            if let Ok(cached) = cache.get::<SprintHealthReport>(&cache_key) {
+                return Ok(Some(cached));
+            }
+        }
+
+        let Some(sprint) = self.repo.get_active_sprint(board_id).await? else {
+            return Ok(None);
+        };
+        let issues = self.repo.get_sprint_issues(sprint.id).await?;
+        let report = self.build_report(sprint, &issues, Utc::now());
+
+        if let Some(ref cache) = self.cache {
+            let _ = cache.set(&cache_key, &report, self.config.refresh_interval);
+        }
+
+        Ok(Some(report))
+    }
+
+    /// Compute the report for a sprint and its tickets as of `now`
+    pub fn build_report(&self, sprint: Sprint, issues: &[SprintIssue], now: DateTime<Utc>) -> SprintHealthReport {
+        let uses_story_points = issues.iter().any(|i| i.ticket.story_points.is_some());
+        let weight = |issue: &SprintIssue| {
+            if uses_story_points {
+                issue.ticket.story_points.unwrap_or(0.0)
+            } else {
+                1.0
+            }
+        };
+        let sum = |filter: &dyn Fn(&SprintIssue) -> bool| -> f64 {
+            issues.iter().filter(|i| filter(i)).map(weight).sum()
+        };
+        let list = |filter: &dyn Fn(&SprintIssue) -> bool| -> Vec<SprintTicket> {
+            issues.iter().filter(|i| filter(i)).map(|i| SprintTicket::from(&i.ticket)).collect()
+        };
+
+        let total_scope = sum(&|_| true);
+        let completed = sum(&|i| i.completed_at.is_some());
+        let added_scope = sum(&|i| i.is_added_after_start(&sprint));
+        let committed_scope = total_scope - added_scope;
+        let scope_creep_percent = if committed_scope > 0.0 {
+            added_scope / committed_scope * 100.0
+        } else {
+            0.0
+        };
+        let is_open = |i: &SprintIssue| i.ticket.status.category != StatusCategory::Done;
+
+        SprintHealthReport {
+            days_left: sprint.days_left(now),
+            uses_story_points,
+            total_scope,
+            completed,
+            committed_scope,
+            added_scope,
+            scope_creep_percent,
+            added: list(&|i| i.is_added_after_start(&sprint)),
+            carried_over: list(&|i| i.carried_over_from.is_some()),
+            blocked: list(&|i| is_open(i) && self.is_blocked(&i.ticket)),
+            unassigned_in_progress: list(&|i| {
+                i.ticket.status.category == StatusCategory::InProgress && i.ticket.assignee.is_none()
+            }),
+            series: self.series(&sprint, issues, committed_scope, &weight, now),
+            forecast: self.forecast(&sprint, total_scope, completed, now),
+            generated_at: now,
+            sprint,
+        }
+    }
+
+    fn is_blocked(&self, ticket: &Ticket) -> bool {
+        ticket.status.name.to_lowercase().contains("block")
+            || ticket
+                .labels
+                .iter()
+                .any(|label| self.config.blocked_labels.iter().any(|b| b.eq_ignore_ascii_case(label)))
+    }
+
+    /// Daily scope and progress from the sprint start until today (or the end date)
+    fn series(
+        &self,
+        sprint: &Sprint,
+        issues: &[SprintIssue],
+        committed_scope: f64,
+        weight: &dyn Fn(&SprintIssue) -> f64,
+        now: DateTime<Utc>,
+    ) -> Vec<SprintDataPoint> {
+        let Some(start) = sprint.start_date else {
+            return Vec::new();
+        };
+        let last = sprint.end_date.map_or(now, |end| end.min(now)).date_naive();
+        let sprint_days = sprint
+            .end_date
+            .map(|end| (end.date_naive() - start.date_naive()).num_days().max(1) as f64);
+
+        start
+            .date_naive()
+            .iter_days()
+            .take_while(|date| *date <= last)
+            .enumerate()
+            .map(|(day, date)| {
+                let day_end = (date + Duration::days(1)).and_hms_opt(0, 0, 0).unwrap().and_utc();
+                let in_scope = issues.iter().filter(|i| !matches!(i.added_at, Some(added) if added >= day_end));
+                let scope: f64 = in_scope.clone().map(weight).sum();
+                let completed: f64 = in_scope
+                    .filter(|i| i.completed_at.is_some_and(|done| done < day_end))
+                    .map(weight)
+                    .sum();
+
+                SprintDataPoint {
+                    date,
+                    scope,
+                    completed,
+                    remaining: scope - completed,
+                    ideal_remaining: sprint_days
+                        .map(|days| (committed_scope * (1.0 - day as f64 / days)).max(0.0)),
+                }
+            })
+            .collect()
+    }
+
+    /// Project the completion date from the pace since the sprint started
+    fn forecast(
+        &self,
+        sprint: &Sprint,
+        total_scope: f64,
+        completed: f64,
+        now: DateTime<Utc>,
+    ) -> Option<SprintForecast> {
+        let start = sprint.start_date.filter(|start| *start < now)?;
+        // Less than a day of history would wildly overstate the pace
+        let elapsed_days = ((now - start).num_minutes() as f64 / (24.0 * 60.0)).max(1.0);
+        let daily_rate = completed / elapsed_days;
+        let remaining = (total_scope - completed).max(0.0);
+
+        let projected_completion = if remaining == 0.0 {
+            now
+        } else if daily_rate > 0.0 {
+            now + Duration::minutes((remaining / daily_rate * 24.0 * 60.0).round() as i64)
+        } else {
+            return None;
+        };
+
+        Some(SprintForecast {
+            daily_rate,
+            projected_completion,
+            on_track: sprint.end_date.is_none_or(|end| projected_completion <= end),
+        })
+    }
+}
+
+// Debug implementation
+impl<S: SprintRepository> std::fmt::Debug for SprintHealthService<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SprintHealthService")
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::traits::{Board, SprintState, TicketStatus, User};
+    use async_trait::async_trait;
+    use chrono::TimeZone;
+
+    struct MockSprintRepo {
+        sprint: Option<Sprint>,
+        issues: Vec<SprintIssue>,
+    }
+
+    #[async_trait]
+    impl SprintRepository for MockSprintRepo {
+        async fn list_boards(&self, _project: Option<&str>) -> Result<Vec<Board>, IntegrationError> {
+            Ok(vec![])
+        }
+
+        async fn get_active_sprint(&self, _board_id: u64) -> Result<Option<Sprint>, IntegrationError> {
+            Ok(self.sprint.clone())
+        }
+
+        async fn get_sprint_issues(&self, _sprint_id: u64) -> Result<Vec<SprintIssue>, IntegrationError> {
+            Ok(self.issues.clone())
+        }
+    }
+
+    fn at(day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 3, day, hour, 0, 0).unwrap()
+    }
+
+    fn sprint() -> Sprint {
+        Sprint {
+            id: 42,
+            name: "Sprint 42".to_string(),
+            state: SprintState::Active,
+            goal: None,
+            start_date: Some(at(4, 9)),
+            end_date: Some(at(14, 17)),
+            complete_date: None,
+            board_id: Some(1),
+        }
+    }
+
+    fn issue(key: &str, points: Option<f64>, category: StatusCategory) -> SprintIssue {
+        SprintIssue {
+            ticket: Ticket {
+                id: key.to_string(),
+                key: key.to_string(),
+                summary: format!("Ticket {}", key),
+                description: None,
+                description_markdown: None,
+                status: TicketStatus {
+                    name: format!("{:?}", category),
+                    category,
+                },
+                assignee: Some(User {
+                    id: "jdoe".to_string(),
+                    name: "Jane Doe".to_string(),
+                    email: None,
+                    avatar_url: None,
+                }),
+                reporter: None,
+                priority: None,
+                sprint: Some("Sprint 42".to_string()),
+                story_points: points,
+                epic: None,
+                team: None,
+                labels: vec![],
+                updated_at: at(4, 9),
+                created_at: at(1, 9),
+            },
+            added_at: None,
+            carried_over_from: None,
+            completed_at: None,
+        }
+    }
+
+    fn sample_issues() -> Vec<SprintIssue> {
+        let mut done = issue("PAY-1", Some(5.0), StatusCategory::Done);
+        done.completed_at = Some(at(5, 15));
+        let mut done_late = issue("PAY-2", Some(3.0), StatusCategory::Done);
+        done_late.completed_at = Some(at(7, 11));
+
+        let mut added = issue("PAY-3", Some(2.0), StatusCategory::Todo);
+        added.added_at = Some(at(6, 10));
+
+        let mut carried = issue("PAY-4", Some(8.0), StatusCategory::InProgress);
+        carried.carried_over_from = Some("Sprint 41".to_string());
+        carried.ticket.assignee = None;
+
+        let mut blocked = issue("PAY-5", Some(2.0), StatusCategory::InProgress);
+        blocked.ticket.labels = vec!["Blocked".to_string()];
+
+        vec![done, done_late, added, carried, blocked]
+    }
+
+    fn service() -> SprintHealthService<MockSprintRepo> {
+        SprintHealthService::new(
+            Arc::new(MockSprintRepo { sprint: Some(sprint()), issues: sample_issues() }),
+            SprintHealthConfig::new(),
+        )
+    }
+
+    #[test]
+    fn test_scope_and_progress() {
+        let report = service().build_report(sprint(), &sample_issues(), at(8, 9));
+
+        assert!(report.uses_story_points);
+        assert_eq!(report.total_scope, 20.0);
+        assert_eq!(report.completed, 8.0);
+        assert_eq!(report.committed_scope, 18.0);
+        assert_eq!(report.added_scope, 2.0);
+        assert!((report.scope_creep_percent - 11.1).abs() < 0.1);
+        assert_eq!(report.added[0].key, "PAY-3");
+        assert_eq!(report.carried_over[0].key, "PAY-4");
+        assert_eq!(report.headline(), "Sprint 42: 7 days left, 8/20 points done");
+    }
+
+    #[test]
+    fn test_blocked_and_unassigned() {
+        let report = service().build_report(sprint(), &sample_issues(), at(8, 9));
+
+        let blocked: Vec<&str> = report.blocked.iter().map(|t| t.key.as_str()).collect();
+        assert_eq!(blocked, vec!["PAY-5"]);
+        assert_eq!(report.unassigned_in_progress.len(), 1);
+        assert_eq!(report.unassigned_in_progress[0].key, "PAY-4");
+    }
+
+    #[test]
+    fn test_burndown_series() {
+        let report = service().build_report(sprint(), &sample_issues(), at(8, 9));
+        let series = &report.series;
+
+        // Mar 4 through Mar 8
+        assert_eq!(series.len(), 5);
+        assert_eq!((series[0].scope, series[0].completed), (18.0, 0.0));
+        assert_eq!((series[1].scope, series[1].completed), (18.0, 5.0));
+        assert_eq!(series[2].scope, 20.0);
+        assert_eq!(series[3].remaining, 12.0);
+        assert_eq!(series[0].ideal_remaining, Some(18.0));
+        assert_eq!(series[4].ideal_remaining, Some(18.0 * 0.6));
+    }
+
+    #[test]
+    fn test_forecast() {
+        let service = service();
+
+        // 8 points in 4 days: 12 left at 2 per day is 6 more days, before the end on Mar 14
+        let report = service.build_report(sprint(), &sample_issues(), at(8, 9));
+        let forecast = report.forecast.unwrap();
+        assert_eq!(forecast.daily_rate, 2.0);
+        assert_eq!(forecast.projected_completion, at(14, 9));
+        assert!(forecast.on_track);
+
+        let report = service.build_report(sprint(), &sample_issues(), at(11, 9));
+        assert!(!report.forecast.unwrap().on_track);
+
+        let untouched = vec![issue("PAY-9", Some(3.0), StatusCategory::Todo)];
+        assert!(service.build_report(sprint(), &untouched, at(8, 9)).forecast.is_none());
+    }
+
+    #[test]
+    fn test_counts_tickets_without_story_points() {
+        let mut done = issue("OPS-1", None, StatusCategory::Done);
+        done.completed_at = Some(at(5, 9));
+        let issues = vec![done, issue("OPS-2", None, StatusCategory::Todo)];
+
+        let report = service().build_report(sprint(), &issues, at(8, 9));
+
+        assert!(!report.uses_story_points);
+        assert_eq!((report.completed, report.total_scope), (1.0, 2.0));
+        assert!(report.headline().ends_with("1/2 tickets done"));
+    }
+
+    #[tokio::test]
+    async fn test_get_report_without_active_sprint() {
+        let idle = SprintHealthService::new(
+            Arc::new(MockSprintRepo { sprint: None, issues: vec![] }),
+            SprintHealthConfig::new(),
+        );
+
+        assert!(idle.get_report(1).await.unwrap().is_none());
+        assert!(service().get_report(1).await.unwrap().is_some());
+    }
+}
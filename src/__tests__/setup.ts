@@ -21,9 +21,13 @@ const mockInvoke = vi.fn().mockImplementation((cmd: string, args?: any) => {
       return Promise.resolve({
         totalOpen: 0,
         pendingReview: 0,
+        changesRequested: 0,
         staleCount: 0,
+        failingChecks: 0,
+        largeCount: 0,
         byRepository: {},
         trayState: 'neutral',
+        failedRepositories: [],
       });
     
     case 'get_prs':
@@ -13,6 +13,8 @@ import type {
   Settings,
   CommandError,
   PrGroup,
+  ReviewComments,
+  RepoDiscovery,
 } from '../types';
 
 // ===== Search Commands =====
@@ -73,6 +75,19 @@ export async function getPrsGroupedByAge(): Promise<{ groups: PrGroup[]; totalCo
   return invoke('get_prs_grouped_by_age');
 }
 
+export async function getPrsGroupedBySize(): Promise<{ groups: PrGroup[]; totalCount: number }> {
+  return invoke('get_prs_grouped_by_size');
+}
+
+export async function getPrReviewComments(
+  repository: string,
+  id: string,
+  connection?: string,
+  limit?: number
+): Promise<ReviewComments> {
+  return invoke<ReviewComments>('get_pr_review_comments', { repository, id, connection, limit });
+}
+
 export async function refreshPrs(): Promise<PrSummary> {
   return invoke<PrSummary>('refresh_prs');
 }
@@ -125,26 +140,33 @@ export async function saveJiraConfig(config: {
 }
 
 export async function saveGitConfig(config: {
+  id: string;
   provider: string;
   baseUrl?: string;
   workspace?: string;
   username: string;
   repositories: string[];
   hasToken: boolean;
+  discovery?: RepoDiscovery;
+  githubApi?: 'rest' | 'graphql';
 }): Promise<void> {
   return invoke<void>('save_git_config', { config });
 }
 
-export async function saveCredential(credentialType: string, value: string): Promise<void> {
-  return invoke<void>('save_credential', { request: { credentialType, value } });
+export async function deleteGitConfig(id: string): Promise<void> {
+  return invoke<void>('delete_git_config', { id });
+}
+
+export async function saveCredential(credentialType: string, value: string, connectionId?: string): Promise<void> {
+  return invoke<void>('save_credential', { request: { credentialType, value, connectionId } });
 }
 
-export async function deleteCredential(credentialType: string): Promise<void> {
-  return invoke<void>('delete_credential', { credentialType });
+export async function deleteCredential(credentialType: string, connectionId?: string): Promise<void> {
+  return invoke<void>('delete_credential', { credentialType, connectionId });
 }
 
-export async function hasCredential(credentialType: string): Promise<boolean> {
-  return invoke<boolean>('has_credential', { credentialType });
+export async function hasCredential(credentialType: string, connectionId?: string): Promise<boolean> {
+  return invoke<boolean>('has_credential', { credentialType, connectionId });
 }
 
 export async function saveShortcuts(shortcuts: {
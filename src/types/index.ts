@@ -27,6 +27,135 @@ export interface SearchResponse {
   query: string;
 }
 
+// ===== Ticket Types =====
+export interface TicketDetail {
+  id: string;
+  key: string;
+  summary: string;
+  description?: string;
+  descriptionMarkdown?: string;
+  status: string;
+  statusCategory: 'Todo' | 'InProgress' | 'Done';
+  assignee?: string;
+  reporter?: string;
+  priority?: string;
+  sprint?: string;
+  storyPoints?: number;
+  epic?: string;
+  team?: string;
+  labels: string[];
+  updatedAt: string;
+  createdAt: string;
+}
+
+export interface TicketComment {
+  id: string;
+  author?: string;
+  authorAvatar?: string;
+  body: string;
+  bodyMarkdown: string;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface TicketTransition {
+  id: string;
+  name: string;
+  toStatus: string;
+  toStatusCategory: string;
+}
+
+export interface AssignTicketParams {
+  key: string;
+  assignee?: string | null;
+  assignToMe?: boolean;
+}
+
+// ===== Sprint Types =====
+export interface SprintTicket {
+  key: string;
+  summary: string;
+  status: string;
+  assignee?: string;
+  storyPoints?: number;
+}
+
+export interface SprintDataPoint {
+  date: string;
+  scope: number;
+  completed: number;
+  remaining: number;
+  idealRemaining?: number;
+}
+
+export interface SprintForecast {
+  dailyRate: number;
+  projectedCompletion: string;
+  onTrack: boolean;
+}
+
+export interface SprintHealth {
+  sprintId: number;
+  sprintName: string;
+  goal?: string;
+  startDate?: string;
+  endDate?: string;
+  daysLeft?: number;
+  headline: string;
+  unit: 'points' | 'tickets';
+  totalScope: number;
+  completed: number;
+  committedScope: number;
+  addedScope: number;
+  scopeCreepPercent: number;
+  added: SprintTicket[];
+  carriedOver: SprintTicket[];
+  blocked: SprintTicket[];
+  unassignedInProgress: SprintTicket[];
+  series: SprintDataPoint[];
+  forecast?: SprintForecast;
+  generatedAt: string;
+}
+
+// ===== Analytics Types =====
+export interface CycleTimeParams {
+  project?: string;
+  from: string;
+  to: string;
+}
+
+export interface TicketFlowMetrics {
+  key: string;
+  summary: string;
+  team?: string;
+  assignee?: string;
+  completedAt?: string;
+  leadTimeHours?: number;
+  cycleTimeHours?: number;
+  timeInStatusHours: Record<string, number>;
+  reopenCount: number;
+}
+
+export interface TeamFlowMetrics {
+  team: string;
+  ticketCount: number;
+  completedCount: number;
+  avgLeadTimeHours?: number;
+  avgCycleTimeHours?: number;
+  medianCycleTimeHours?: number;
+  p85CycleTimeHours?: number;
+  timeInStatusHours: Record<string, number>;
+  reopenCount: number;
+  reopenRatePercent: number;
+}
+
+export interface CycleTimeReport {
+  tickets: TicketFlowMetrics[];
+  teams: TeamFlowMetrics[];
+  overall: TeamFlowMetrics;
+  generatedAt: string;
+}
+
 // ===== PR Types =====
 export interface User {
   id: string;
@@ -34,8 +163,11 @@ export interface User {
   avatar?: string;
 }
 
+export type ReviewState = 'Pending' | 'Commented' | 'ChangesRequested' | 'Approved';
+
 export interface Reviewer {
   user: User;
+  state: ReviewState;
   approved: boolean;
 }
 
@@ -54,14 +186,38 @@ export interface PrItem {
   updatedAt: string;
   url: string;
   ageHours: number;
+  /** Git connection the PR came from */
+  connection?: string;
+  labels?: string[];
+  /** Diff size, absent when the provider did not report it */
+  additions?: number;
+  deletions?: number;
+  changedFiles?: number;
+  size?: PrSize;
+}
+
+/** Size bucket by lines changed: XS < 10, S < 100, M < 400, L < 1000, XL */
+export type PrSize = 'XS' | 'S' | 'M' | 'L' | 'XL';
+
+export type RepositoryErrorKind = 'Auth' | 'NotFound' | 'RateLimit' | 'Network' | 'Api';
+
+export interface RepositoryError {
+  repository: string;
+  kind: RepositoryErrorKind;
+  message: string;
 }
 
 export interface PrSummary {
   totalOpen: number;
   pendingReview: number;
+  changesRequested: number;
   staleCount: number;
+  failingChecks: number;
+  /** PRs of size L or XL */
+  largeCount: number;
   byRepository: Record<string, number>;
   trayState: TrayState;
+  failedRepositories: RepositoryError[];
 }
 
 export interface PrGroup {
@@ -70,6 +226,32 @@ export interface PrGroup {
   staleCount: number;
 }
 
+export interface ReviewComment {
+  id: string;
+  threadId: string;
+  author: User;
+  body: string;
+  /** File and line the comment is anchored to, absent on the conversation */
+  path?: string;
+  line?: number;
+  resolved?: boolean;
+  createdAt: string;
+  updatedAt: string;
+}
+
+/** Whose turn it is on a PR */
+export type PrCourt = 'author' | 'reviewers';
+
+export interface ReviewComments {
+  /** Latest comments, oldest first */
+  comments: ReviewComment[];
+  totalCount: number;
+  unresolvedThreads: number;
+  lastActivityBy?: User;
+  lastActivityAt?: string;
+  court?: PrCourt;
+}
+
 // ===== Incident Types =====
 export type Severity = 'low' | 'medium' | 'high' | 'critical';
 
@@ -102,16 +284,41 @@ export interface JiraConfig {
   baseUrl: string;
   username: string;
   defaultProject?: string;
+  deployment?: 'cloud' | 'datacenter';
   hasToken: boolean;
+  fieldMapping?: JiraFieldMapping;
+}
+
+export interface JiraFieldMapping {
+  sprint?: string;
+  storyPoints?: string;
+  epic?: string;
+  team?: string;
 }
 
 export interface GitConfig {
+  /** Connection id, unique among git connections */
+  id: string;
   provider: string;
   baseUrl?: string;
   workspace?: string;
   username: string;
   repositories: string[];
   hasToken: boolean;
+  discovery?: RepoDiscovery;
+  /** GitHub only: fetch PRs through the REST or the GraphQL API */
+  githubApi?: 'rest' | 'graphql';
+}
+
+export interface RepoDiscovery {
+  /** GitHub org/user, Bitbucket workspace or GitLab group; defaults to the workspace */
+  owner?: string;
+  /** Bitbucket project key */
+  project?: string;
+  include: string[];
+  exclude: string[];
+  includeArchived?: boolean;
+  refreshIntervalMinutes?: number;
 }
 
 export interface GeminiConfig {
@@ -127,7 +334,7 @@ export interface GrafanaConfig {
 
 export interface IntegrationConfig {
   jira?: JiraConfig;
-  git?: GitConfig;
+  git: GitConfig[];
   gemini?: GeminiConfig;
   grafana?: GrafanaConfig;
 }